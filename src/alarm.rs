@@ -1,10 +1,18 @@
+use anyhow::Context;
 use chrono::{DateTime, Utc};
 use clap::{Arg, ArgMatches, Command};
 use std::collections::{HashMap, HashSet};
+use std::process;
+use std::thread;
 use std::time::SystemTime;
 use tabled::{builder::Builder, settings::Style};
 
-use crate::edge::new_client;
+use crate::client::new_client;
+use crate::edge::EdgeClient;
+use crate::fanout;
+use crate::format::{self, Format};
+use crate::query::Query;
+use crate::{exit_with, OrExit};
 
 fn parse_time_filter(input: &str) -> Result<String, String> {
     if let Ok(dt) = DateTime::parse_from_rfc3339(input) {
@@ -32,26 +40,14 @@ pub(crate) fn subcommand() -> clap::Command {
         .about("Manage alarms")
         .subcommand_required(true)
         .subcommand(
-            Command::new("list").about("List active alarms").arg(
-                Arg::new("output")
-                    .long("output")
-                    .short('o')
-                    .value_parser(["short", "wide"])
-                    .default_value("short")
-                    .help("Change the output format"),
-            ),
+            Command::new("list")
+                .about("List active alarms")
+                .arg(format::arg(&["short", "wide"])),
         )
         .subcommand(
             Command::new("history")
                 .about("View alarm history")
-                .arg(
-                    Arg::new("output")
-                        .long("output")
-                        .short('o')
-                        .value_parser(["short", "wide", "detailed"])
-                        .default_value("short")
-                        .help("Change the output format"),
-                )
+                .arg(format::arg(&["short", "wide", "detailed"]))
                 .arg(
                     Arg::new("limit")
                         .long("limit")
@@ -75,14 +71,7 @@ pub(crate) fn subcommand() -> clap::Command {
                 .subcommand(
                     Command::new("list")
                         .about("List historical alarms")
-                        .arg(
-                            Arg::new("output")
-                                .long("output")
-                                .short('o')
-                                .value_parser(["short", "wide", "detailed"])
-                                .default_value("short")
-                                .help("Change the output format"),
-                        )
+                        .arg(format::arg(&["short", "wide", "detailed"]))
                         .arg(
                             Arg::new("limit")
                                 .long("limit")
@@ -112,8 +101,12 @@ pub(crate) fn subcommand() -> clap::Command {
 pub(crate) fn run(args: &ArgMatches) {
     match args.subcommand() {
         Some(("list", sub_args)) => {
-            match sub_args.get_one::<String>("output").map(|s| s.as_str()) {
-                Some("wide") => list_wide(),
+            match (
+                format::structured(sub_args),
+                sub_args.get_one::<String>("output").map(|s| s.as_str()),
+            ) {
+                (Some(format), _) => list_structured(format),
+                (None, Some("wide")) => list_wide(),
                 _ => list(),
             }
         }
@@ -142,9 +135,13 @@ fn run_history(args: &ArgMatches) {
                     std::process::exit(1);
                 })
             });
-            match sub_args.get_one::<String>("output").map(|s| s.as_str()) {
-                Some("wide") => history_list_wide(limit, from_date, to_date),
-                Some("detailed") => history_list_detailed(limit, from_date, to_date),
+            match (
+                format::structured(sub_args),
+                sub_args.get_one::<String>("output").map(|s| s.as_str()),
+            ) {
+                (Some(format), _) => history_list_structured(limit, from_date, to_date, format),
+                (None, Some("wide")) => history_list_wide(limit, from_date, to_date),
+                (None, Some("detailed")) => history_list_detailed(limit, from_date, to_date),
                 _ => history_list(limit, from_date, to_date),
             }
         }
@@ -162,9 +159,13 @@ fn run_history(args: &ArgMatches) {
                     std::process::exit(1);
                 })
             });
-            match args.get_one::<String>("output").map(|s| s.as_str()) {
-                Some("wide") => history_list_wide(limit, from_date, to_date),
-                Some("detailed") => history_list_detailed(limit, from_date, to_date),
+            match (
+                format::structured(args),
+                args.get_one::<String>("output").map(|s| s.as_str()),
+            ) {
+                (Some(format), _) => history_list_structured(limit, from_date, to_date, format),
+                (None, Some("wide")) => history_list_wide(limit, from_date, to_date),
+                (None, Some("detailed")) => history_list_detailed(limit, from_date, to_date),
                 _ => history_list(limit, from_date, to_date),
             }
         }
@@ -202,55 +203,146 @@ fn format_time_ago(time_str: &str) -> String {
     }
 }
 
+fn list_structured(format: Format) {
+    let client = new_client();
+    format::print_items(format, client.alarm_pages(Query::new()).items())
+        .or_exit("Failed to list alarms");
+}
+
+/// `alarm list` against several contexts at once
+pub(crate) fn list_contexts(args: &ArgMatches, contexts: &[String]) {
+    let ok = match (
+        format::structured(args),
+        args.get_one::<String>("output").map(|s| s.as_str()),
+    ) {
+        (Some(format), _) => fanout::print_structured(
+            format,
+            fanout::run(contexts, |client| {
+                client.list_alarms().context("Failed to list alarms")
+            }),
+        ),
+        (None, Some("wide")) => {
+            eprintln!("-o wide is not supported across contexts");
+            process::exit(1);
+        }
+        _ => fanout::print_table(&LIST_HEADER, fanout::run(contexts, list_records)),
+    };
+    if !ok {
+        process::exit(1);
+    }
+}
+
 fn list() {
     let client = new_client();
-    let alarms = client.list_alarms().expect("Failed to list alarms");
+    let records = list_records(&client).unwrap_or_else(|e| {
+        exit_with(e);
+    });
 
-    if alarms.is_empty() {
+    if records.is_empty() {
         println!("No active alarms found");
         return;
     }
 
-    let inputs = client
-        .list_inputs_by_ids(
-            alarms
-                .iter()
-                .filter_map(|a| a.input_id.as_ref())
-                .chain(alarms.iter().filter_map(|a| a.affected_input.as_ref()))
-                .cloned()
-                .collect(),
-        )
-        .expect("Failed to list inputs");
-    let outputs = client
-        .list_outputs_by_ids(
-            alarms
-                .iter()
-                .filter_map(|a| a.output_id.as_ref())
-                .chain(alarms.iter().filter_map(|a| a.affected_output.as_ref()))
-                .cloned()
-                .collect(),
-        )
-        .expect("Failed to list outputs");
+    let mut builder = Builder::default();
+    builder.push_record(LIST_HEADER);
+    for record in records {
+        builder.push_record(record);
+    }
 
-    let input_map: HashMap<String, String> = inputs
-        .into_iter()
-        .map(|input| (input.id, input.name))
-        .collect();
-    let output_map: HashMap<String, String> = outputs
-        .into_iter()
-        .map(|output| (output.id, output.name))
-        .collect();
+    let mut table = builder.build();
+    table.with(Style::empty());
+    println!("{}", table);
+}
 
-    let mut builder = Builder::default();
-    builder.push_record([
-        "Time Ago",
-        "Severity",
-        "Cause",
-        "Message",
-        "Appliance",
-        "Entity",
-    ]);
+/// The names of the inputs, outputs and ports referenced by alarms, by id
+struct Names {
+    inputs: HashMap<String, String>,
+    outputs: HashMap<String, String>,
+    ports: HashMap<String, String>,
+}
+
+/// Looks up the names of the inputs, outputs and ports with the given ids
+/// concurrently
+fn lookup_names<'a>(
+    client: &EdgeClient,
+    input_ids: impl Iterator<Item = &'a String>,
+    output_ids: impl Iterator<Item = &'a String>,
+    port_ids: impl Iterator<Item = &'a String>,
+) -> anyhow::Result<Names> {
+    fn unique<'a>(ids: impl Iterator<Item = &'a String>) -> Vec<String> {
+        ids.filter(|id| !id.is_empty())
+            .cloned()
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect()
+    }
+    let (input_ids, output_ids, port_ids) =
+        (unique(input_ids), unique(output_ids), unique(port_ids));
+
+    let (inputs, outputs, ports) = thread::scope(|s| {
+        let inputs = s.spawn(|| client.list_inputs_by_ids(input_ids));
+        let outputs = s.spawn(|| client.list_outputs_by_ids(output_ids));
+        let ports = s.spawn(|| client.list_ports_by_ids(port_ids));
+        (
+            inputs.join().expect("Input request thread panicked"),
+            outputs.join().expect("Output request thread panicked"),
+            ports.join().expect("Port request thread panicked"),
+        )
+    });
+
+    Ok(Names {
+        inputs: inputs
+            .context("Failed to list inputs")?
+            .into_iter()
+            .map(|input| (input.id, input.name))
+            .collect(),
+        outputs: outputs
+            .context("Failed to list outputs")?
+            .into_iter()
+            .map(|output| (output.id, output.name))
+            .collect(),
+        ports: ports
+            .context("Failed to list ports")?
+            .into_iter()
+            .map(|port| (port.id, port.name))
+            .collect(),
+    })
+}
+
+pub(crate) const LIST_HEADER: [&str; 6] = [
+    "Time Ago",
+    "Severity",
+    "Cause",
+    "Message",
+    "Appliance",
+    "Entity",
+];
+
+/// The rows of `alarm list`, one per active alarm
+pub(crate) fn list_records(client: &EdgeClient) -> anyhow::Result<Vec<Vec<String>>> {
+    let alarms = client.list_alarms().context("Failed to list alarms")?;
+    if alarms.is_empty() {
+        return Ok(Vec::new());
+    }
 
+    let Names {
+        inputs: input_map,
+        outputs: output_map,
+        ..
+    } = lookup_names(
+        client,
+        alarms
+            .iter()
+            .filter_map(|a| a.input_id.as_ref())
+            .chain(alarms.iter().filter_map(|a| a.affected_input.as_ref())),
+        alarms
+            .iter()
+            .filter_map(|a| a.output_id.as_ref())
+            .chain(alarms.iter().filter_map(|a| a.affected_output.as_ref())),
+        std::iter::empty(),
+    )?;
+
+    let mut records = Vec::new();
     for alarm in alarms {
         let time_ago = alarm
             .raised_at
@@ -317,70 +409,45 @@ fn list() {
 
         let message = alarm.text.as_deref().unwrap_or("-");
 
-        builder.push_record([
-            &time_ago,
-            &alarm.alarm_severity,
-            &alarm.alarm_cause,
-            message,
-            appliance,
-            &entity,
+        records.push(vec![
+            time_ago,
+            alarm.alarm_severity.to_owned(),
+            alarm.alarm_cause.to_owned(),
+            message.to_owned(),
+            appliance.to_owned(),
+            entity,
         ]);
     }
 
-    let mut table = builder.build();
-    table.with(Style::empty());
-    println!("{}", table);
+    Ok(records)
 }
 
 fn list_wide() {
     let client = new_client();
-    let alarms = client.list_alarms().expect("Failed to list alarms");
+    let alarms = client.list_alarms().or_exit("Failed to list alarms");
 
     if alarms.is_empty() {
         println!("No active alarms found");
         return;
     }
 
-    let inputs = client
-        .list_inputs_by_ids(
-            alarms
-                .iter()
-                .filter_map(|a| a.input_id.as_ref())
-                .chain(alarms.iter().filter_map(|a| a.affected_input.as_ref()))
-                .cloned()
-                .collect(),
-        )
-        .expect("Failed to list inputs");
-    let outputs = client
-        .list_outputs_by_ids(
-            alarms
-                .iter()
-                .filter_map(|a| a.output_id.as_ref())
-                .chain(alarms.iter().filter_map(|a| a.affected_output.as_ref()))
-                .cloned()
-                .collect(),
-        )
-        .expect("Failed to list outputs");
-    let ports = client
-        .list_ports_by_ids(
-            alarms
-                .iter()
-                .filter_map(|a| a.physical_port_id.as_ref())
-                .cloned()
-                .collect(),
-        )
-        .expect("Failed to list ports");
-
-    let input_map: HashMap<String, String> = inputs
-        .into_iter()
-        .map(|input| (input.id, input.name))
-        .collect();
-    let output_map: HashMap<String, String> = outputs
-        .into_iter()
-        .map(|output| (output.id, output.name))
-        .collect();
-    let port_map: HashMap<String, String> =
-        ports.into_iter().map(|port| (port.id, port.name)).collect();
+    let Names {
+        inputs: input_map,
+        outputs: output_map,
+        ports: port_map,
+    } = lookup_names(
+        &client,
+        alarms
+            .iter()
+            .filter_map(|a| a.input_id.as_ref())
+            .chain(alarms.iter().filter_map(|a| a.affected_input.as_ref())),
+        alarms
+            .iter()
+            .filter_map(|a| a.output_id.as_ref())
+            .chain(alarms.iter().filter_map(|a| a.affected_output.as_ref())),
+        alarms.iter().filter_map(|a| a.physical_port_id.as_ref()),
+    )
+    .unwrap_or_else(|e| exit_with(e));
 
     let mut builder = Builder::default();
     builder.push_record([
@@ -493,59 +560,41 @@ fn list_wide() {
     println!("{}", table);
 }
 
+fn history_list_structured(
+    limit: usize,
+    from_date: Option<String>,
+    to_date: Option<String>,
+    format: Format,
+) {
+    let client = new_client();
+    let alarms = client
+        .list_alarm_history(limit, from_date, to_date)
+        .or_exit("Failed to list alarm history");
+    format::print(format, &alarms);
+}
+
 fn history_list(limit: usize, from_date: Option<String>, to_date: Option<String>) {
     let client = new_client();
     let alarms = client
         .list_alarm_history(limit, from_date, to_date)
-        .expect("Failed to list alarm history");
+        .or_exit("Failed to list alarm history");
 
     if alarms.is_empty() {
         println!("No alarm history found");
         return;
     }
 
-    let input_ids: HashSet<String> = alarms
-        .iter()
-        .filter_map(|a| a.input_id.as_ref())
-        .filter(|id| !id.is_empty())
-        .cloned()
-        .collect();
-    let input_ids: Vec<String> = Vec::from_iter(input_ids);
-
-    let output_ids: HashSet<String> = alarms
-        .iter()
-        .filter_map(|a| a.output_id.as_ref())
-        .filter(|id| !id.is_empty())
-        .cloned()
-        .collect();
-    let output_ids: Vec<String> = Vec::from_iter(output_ids);
-
-    let mut inputs = Vec::new();
-    for chunk in input_ids.chunks(50) {
-        inputs.extend(
-            client
-                .list_inputs_by_ids(chunk.to_vec())
-                .expect("Failed to list inputs"),
-        );
-    }
-
-    let mut outputs = Vec::new();
-    for chunk in output_ids.chunks(50) {
-        outputs.extend(
-            client
-                .list_outputs_by_ids(chunk.to_vec())
-                .expect("Failed to list outputs"),
-        );
-    }
-
-    let input_map: HashMap<String, String> = inputs
-        .into_iter()
-        .map(|input| (input.id, input.name))
-        .collect();
-    let output_map: HashMap<String, String> = outputs
-        .into_iter()
-        .map(|output| (output.id, output.name))
-        .collect();
+    let Names {
+        inputs: input_map,
+        outputs: output_map,
+        ..
+    } = lookup_names(
+        &client,
+        alarms.iter().filter_map(|a| a.input_id.as_ref()),
+        alarms.iter().filter_map(|a| a.output_id.as_ref()),
+        std::iter::empty(),
+    )
+    .unwrap_or_else(|e| exit_with(e));
 
     let mut builder = Builder::default();
     builder.push_record([
@@ -627,74 +676,24 @@ fn history_list_wide(limit: usize, from_date: Option<String>, to_date: Option<St
     let client = new_client();
     let alarms = client
         .list_alarm_history(limit, from_date, to_date)
-        .expect("Failed to list alarm history");
+        .or_exit("Failed to list alarm history");
 
     if alarms.is_empty() {
         println!("No alarm history found");
         return;
     }
 
-    let input_ids: HashSet<String> = alarms
-        .iter()
-        .filter_map(|a| a.input_id.as_ref())
-        .filter(|id| !id.is_empty())
-        .cloned()
-        .collect();
-    let input_ids: Vec<String> = Vec::from_iter(input_ids);
-
-    let output_ids: HashSet<String> = alarms
-        .iter()
-        .filter_map(|a| a.output_id.as_ref())
-        .filter(|id| !id.is_empty())
-        .cloned()
-        .collect();
-    let output_ids: Vec<String> = Vec::from_iter(output_ids);
-
-    let port_ids: HashSet<String> = alarms
-        .iter()
-        .filter_map(|a| a.physical_port_id.as_ref())
-        .filter(|id| !id.is_empty())
-        .cloned()
-        .collect();
-    let port_ids: Vec<String> = Vec::from_iter(port_ids);
-
-    let mut inputs = Vec::new();
-    for chunk in input_ids.chunks(50) {
-        inputs.extend(
-            client
-                .list_inputs_by_ids(chunk.to_vec())
-                .expect("Failed to list inputs"),
-        );
-    }
-
-    let mut outputs = Vec::new();
-    for chunk in output_ids.chunks(50) {
-        outputs.extend(
-            client
-                .list_outputs_by_ids(chunk.to_vec())
-                .expect("Failed to list outputs"),
-        );
-    }
-
-    let mut ports = Vec::new();
-    for chunk in port_ids.chunks(50) {
-        ports.extend(
-            client
-                .list_ports_by_ids(chunk.to_vec())
-                .expect("Failed to list ports"),
-        );
-    }
-
-    let input_map: HashMap<String, String> = inputs
-        .into_iter()
-        .map(|input| (input.id, input.name))
-        .collect();
-    let output_map: HashMap<String, String> = outputs
-        .into_iter()
-        .map(|output| (output.id, output.name))
-        .collect();
-    let port_map: HashMap<String, String> =
-        ports.into_iter().map(|port| (port.id, port.name)).collect();
+    let Names {
+        inputs: input_map,
+        outputs: output_map,
+        ports: port_map,
+    } = lookup_names(
+        &client,
+        alarms.iter().filter_map(|a| a.input_id.as_ref()),
+        alarms.iter().filter_map(|a| a.output_id.as_ref()),
+        alarms.iter().filter_map(|a| a.physical_port_id.as_ref()),
+    )
+    .unwrap_or_else(|e| exit_with(e));
 
     let mut builder = Builder::default();
     builder.push_record([
@@ -794,74 +793,24 @@ fn history_list_detailed(limit: usize, from_date: Option<String>, to_date: Optio
     let client = new_client();
     let alarms = client
         .list_alarm_history(limit, from_date, to_date)
-        .expect("Failed to list alarm history");
+        .or_exit("Failed to list alarm history");
 
     if alarms.is_empty() {
         println!("No alarm history found");
         return;
     }
 
-    let input_ids: HashSet<String> = alarms
-        .iter()
-        .filter_map(|a| a.input_id.as_ref())
-        .filter(|id| !id.is_empty())
-        .cloned()
-        .collect();
-    let input_ids: Vec<String> = Vec::from_iter(input_ids);
-
-    let output_ids: HashSet<String> = alarms
-        .iter()
-        .filter_map(|a| a.output_id.as_ref())
-        .filter(|id| !id.is_empty())
-        .cloned()
-        .collect();
-    let output_ids: Vec<String> = Vec::from_iter(output_ids);
-
-    let port_ids: HashSet<String> = alarms
-        .iter()
-        .filter_map(|a| a.physical_port_id.as_ref())
-        .filter(|id| !id.is_empty())
-        .cloned()
-        .collect();
-    let port_ids: Vec<String> = Vec::from_iter(port_ids);
-
-    let mut inputs = Vec::new();
-    for chunk in input_ids.chunks(50) {
-        inputs.extend(
-            client
-                .list_inputs_by_ids(chunk.to_vec())
-                .expect("Failed to list inputs"),
-        );
-    }
-
-    let mut outputs = Vec::new();
-    for chunk in output_ids.chunks(50) {
-        outputs.extend(
-            client
-                .list_outputs_by_ids(chunk.to_vec())
-                .expect("Failed to list outputs"),
-        );
-    }
-
-    let mut ports = Vec::new();
-    for chunk in port_ids.chunks(50) {
-        ports.extend(
-            client
-                .list_ports_by_ids(chunk.to_vec())
-                .expect("Failed to list ports"),
-        );
-    }
-
-    let input_map: HashMap<String, String> = inputs
-        .into_iter()
-        .map(|input| (input.id, input.name))
-        .collect();
-    let output_map: HashMap<String, String> = outputs
-        .into_iter()
-        .map(|output| (output.id, output.name))
-        .collect();
-    let port_map: HashMap<String, String> =
-        ports.into_iter().map(|port| (port.id, port.name)).collect();
+    let Names {
+        inputs: input_map,
+        outputs: output_map,
+        ports: port_map,
+    } = lookup_names(
+        &client,
+        alarms.iter().filter_map(|a| a.input_id.as_ref()),
+        alarms.iter().filter_map(|a| a.output_id.as_ref()),
+        alarms.iter().filter_map(|a| a.physical_port_id.as_ref()),
+    )
+    .unwrap_or_else(|e| exit_with(e));
 
     let mut first = true;
     for alarm in alarms.iter() {
@@ -1,37 +1,48 @@
-use std::{fmt, process};
+use std::process;
 
 use anyhow::{anyhow, Context};
 use clap::{Arg, ArgMatches, Command};
 use tabled::{builder::Builder, settings::Style};
 
-use crate::edge::{new_client, Appliance, ApplianceHealthState, AppliancePortType, EdgeClient};
+use crate::client::new_client;
+use crate::edge::{Appliance, ApplianceHealthState, EdgeClient};
+use crate::format::{self, Format};
+use crate::{exit_with, OrExit};
 use crate::{green, red};
 
 pub(crate) fn subcommand() -> clap::Command {
     Command::new("appliance")
         .about("Manage appliances")
         .subcommand_required(true)
-        .subcommand(Command::new("list"))
+        .subcommand(Command::new("list").arg(format::arg(&["short"])))
         .subcommand(
-            Command::new("show").arg(
-                Arg::new("name")
-                    .required(true)
-                    .help("The appliance name to show details for"),
-            ),
+            Command::new("show")
+                .arg(
+                    Arg::new("name")
+                        .required(true)
+                        .help("The appliance name to show details for"),
+                )
+                .arg(format::arg(&["text"])),
         )
         .subcommand(
-            Command::new("inputs").about("List appliance inputs").arg(
-                Arg::new("name")
-                    .required(true)
-                    .help("The appliance name to show details for"),
-            ),
+            Command::new("inputs")
+                .about("List appliance inputs")
+                .arg(
+                    Arg::new("name")
+                        .required(true)
+                        .help("The appliance name to show details for"),
+                )
+                .arg(format::arg(&["short"])),
         )
         .subcommand(
-            Command::new("outputs").about("List appliance outputs").arg(
-                Arg::new("name")
-                    .required(true)
-                    .help("The appliance name to show details for"),
-            ),
+            Command::new("outputs")
+                .about("List appliance outputs")
+                .arg(
+                    Arg::new("name")
+                        .required(true)
+                        .help("The appliance name to show details for"),
+                )
+                .arg(format::arg(&["short"])),
         )
         .subcommand(
             Command::new("delete").arg(
@@ -59,9 +70,9 @@ pub(crate) fn subcommand() -> clap::Command {
 
 pub(crate) fn run(subcmd: &ArgMatches) {
     match subcmd.subcommand() {
-        Some(("list", _)) | None => {
+        Some(("list", args)) => {
             let client = new_client();
-            list(client)
+            list(client, format::structured(args))
         }
         Some(("show", args)) => {
             let client = new_client();
@@ -69,7 +80,7 @@ pub(crate) fn run(subcmd: &ArgMatches) {
                 .get_one::<String>("name")
                 .map(|s| s.as_str())
                 .expect("Appliance name is mandatory");
-            show(client, name)
+            show(client, name, format::structured(args))
         }
         Some(("delete", args)) => {
             let client = new_client();
@@ -93,7 +104,7 @@ pub(crate) fn run(subcmd: &ArgMatches) {
                 .get_one::<String>("name")
                 .map(|s| s.as_str())
                 .expect("Appliance name is mandatory");
-            inputs(client, name)
+            inputs(client, name, format::structured(args))
         }
         Some(("outputs", args)) => {
             let client = new_client();
@@ -101,7 +112,7 @@ pub(crate) fn run(subcmd: &ArgMatches) {
                 .get_one::<String>("name")
                 .map(|s| s.as_str())
                 .expect("Appliance name is mandatory");
-            outputs(client, name)
+            outputs(client, name, format::structured(args))
         }
         Some(("config", args)) => {
             let client = new_client();
@@ -123,31 +134,23 @@ pub(crate) fn run(subcmd: &ArgMatches) {
     }
 }
 
-impl fmt::Display for ApplianceHealthState {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            Self::Connected => write!(f, "{} connected", green!("✓")),
-            Self::Missing => write!(f, "{} missing", red!("✗")),
-            Self::NeverConnected => write!(f, "{} never connected", red!("✗")),
-        }
-    }
-}
-
-impl fmt::Display for AppliancePortType {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::Ip => f.pad("IP"),
-            Self::Coax => f.pad("Coax"),
-            Self::Videon => f.pad("Videon"),
-            Self::Ndi => f.pad("Ndi"),
-        }
+fn health_state(state: &ApplianceHealthState) -> String {
+    match state {
+        ApplianceHealthState::Connected => format!("{} connected", green!("✓")),
+        ApplianceHealthState::Missing => format!("{} missing", red!("✗")),
+        ApplianceHealthState::NeverConnected => format!("{} never connected", red!("✗")),
     }
 }
 
-fn list(client: EdgeClient) {
+fn list(client: EdgeClient, format: Option<Format>) {
     let appliances = client
         .list_appliances()
-        .expect("Failed to fetch appliance list");
+        .or_exit("Failed to fetch appliance list");
+
+    if let Some(format) = format {
+        format::print(format, &appliances);
+        return;
+    }
 
     let mut builder = Builder::default();
     builder.push_record(["Name", "ID", "Type", "State"]);
@@ -158,7 +161,7 @@ fn list(client: EdgeClient) {
             appliance.kind,
             appliance
                 .health
-                .map(|h| h.state.to_string())
+                .map(|h| health_state(&h.state))
                 .unwrap_or("unknown".to_owned()),
         ])
     }
@@ -168,7 +171,7 @@ fn list(client: EdgeClient) {
     println!("{}", table)
 }
 
-fn show(client: EdgeClient, name: &str) {
+fn show(client: EdgeClient, name: &str, format: Option<Format>) {
     let appliances = match client.find_appliances(name) {
         Ok(appls) => appls,
         Err(e) => {
@@ -182,6 +185,11 @@ fn show(client: EdgeClient, name: &str) {
         process::exit(1)
     }
 
+    if let Some(format) = format {
+        format::print(format, &appliances);
+        return;
+    }
+
     for appliance in appliances {
         let group = client.get_group(&appliance.owner);
         let group_name = group.map(|g| g.name).unwrap_or("unknown".to_owned());
@@ -275,16 +283,20 @@ fn delete(client: &EdgeClient, name: &str) -> anyhow::Result<()> {
     Ok(())
 }
 
-fn inputs(client: EdgeClient, name: &str) {
+fn inputs(client: EdgeClient, name: &str, format: Option<Format>) {
     let appliance = get_appliance(&client, name);
     let inputs = match client.get_appliance_inputs(&appliance.id) {
         Ok(inputs) => inputs,
         Err(e) => {
-            eprintln!("Failed to list inputs for appliance: {}", e);
-            process::exit(1);
+            exit_with(anyhow::Error::from(e).context("Failed to list inputs for appliance"));
         }
     };
 
+    if let Some(format) = format {
+        format::print(format, &inputs);
+        return;
+    }
+
     let mut builder = Builder::default();
     builder.push_record(["Name", "Group", "Status"]);
 
@@ -303,16 +315,20 @@ fn inputs(client: EdgeClient, name: &str) {
     println!("{}", table)
 }
 
-fn outputs(client: EdgeClient, name: &str) {
+fn outputs(client: EdgeClient, name: &str, format: Option<Format>) {
     let appliance = get_appliance(&client, name);
     let outputs = match client.get_appliance_outputs(&appliance.id) {
         Ok(outputs) => outputs,
         Err(e) => {
-            eprintln!("Failed to list inputs for appliance: {}", e);
-            process::exit(1);
+            exit_with(anyhow::Error::from(e).context("Failed to list inputs for appliance"));
         }
     };
 
+    if let Some(format) = format {
+        format::print(format, &outputs);
+        return;
+    }
+
     let mut builder = Builder::default();
     builder.push_record(["Name", "Group", "Status"]);
 
@@ -336,8 +352,7 @@ fn config(client: EdgeClient, name: &str) {
     let config = match client.get_appliance_config(&appliance.id) {
         Ok(config) => config,
         Err(e) => {
-            eprintln!("Failed to get appliance config: {}", e);
-            process::exit(1);
+            exit_with(anyhow::Error::from(e).context("Failed to get appliance config"));
         }
     };
     println!("{}", serde_json::to_string_pretty(&config).unwrap());
@@ -347,8 +362,7 @@ fn restart(client: EdgeClient, name: &str) {
     let appliance = get_appliance(&client, name);
     eprintln!("Restarting appliance {}", appliance.name);
     if let Err(e) = client.restart_appliance(&appliance.id) {
-        eprintln!("Failed to restart appliance: {}", e);
-        process::exit(1);
+        exit_with(anyhow::Error::from(e).context("Failed to restart appliance"));
     }
     eprintln!("Appliance {} restarted", appliance.name)
 }
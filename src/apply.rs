@@ -0,0 +1,320 @@
+use std::{fmt, process};
+
+use anyhow::{anyhow, Context};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+
+use crate::client::new_client;
+use crate::edge::{EdgeClient, NewGroup, NewGroupRecipientList, NewOutputRecipientList, NewRegion};
+use crate::exit_with;
+use crate::manifest::{
+    self, GroupListManifest, InputManifest, Manifest, OutputListManifest, OutputManifest,
+    RegionManifest, Resolver,
+};
+
+pub(crate) fn subcommand() -> clap::Command {
+    Command::new("apply")
+        .about("Create or update objects from manifests")
+        .arg(
+            Arg::new("filename")
+                .short('f')
+                .long("filename")
+                .required(true)
+                .action(ArgAction::Append)
+                .help("Manifest file or directory of manifests, - to read from stdin"),
+        )
+        .arg(
+            Arg::new("dry-run")
+                .long("dry-run")
+                .num_args(0)
+                .help("Show what would be changed without changing anything"),
+        )
+}
+
+pub(crate) fn run(args: &ArgMatches) {
+    let mut manifests = Vec::new();
+    for path in args
+        .get_many::<String>("filename")
+        .expect("Filename is mandatory")
+    {
+        match manifest::load(path) {
+            Ok(m) => manifests.extend(m),
+            Err(e) => {
+                exit_with(e);
+            }
+        }
+    }
+    manifest::sort(&mut manifests);
+    let dry_run = args.get_flag("dry-run");
+
+    let client = new_client();
+    let mut resolver = Resolver::new(&client);
+    let mut failed = false;
+    for manifest in manifests {
+        match apply(&client, &mut resolver, &manifest, dry_run) {
+            Ok(action) if dry_run => {
+                println!(
+                    "{}/{} {} (dry run)",
+                    manifest.kind(),
+                    manifest.name(),
+                    action
+                )
+            }
+            Ok(action) => println!("{}/{} {}", manifest.kind(), manifest.name(), action),
+            Err(e) => {
+                eprintln!(
+                    "Failed to apply {}/{}: {:#}",
+                    manifest.kind(),
+                    manifest.name(),
+                    e
+                );
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        process::exit(1);
+    }
+}
+
+enum Action {
+    Created,
+    Configured,
+    Unchanged,
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Created => write!(f, "created"),
+            Self::Configured => write!(f, "configured"),
+            Self::Unchanged => write!(f, "unchanged"),
+        }
+    }
+}
+
+fn apply(
+    client: &EdgeClient,
+    resolver: &mut Resolver,
+    desired: &Manifest,
+    dry_run: bool,
+) -> anyhow::Result<Action> {
+    match desired {
+        Manifest::Region(region) => match manifest::find_region(client, &region.name)? {
+            None => {
+                if !dry_run {
+                    client
+                        .create_region(NewRegion {
+                            name: region.name.to_owned(),
+                            external: region.external.clone(),
+                        })
+                        .context("Failed to create region")?;
+                }
+                Ok(Action::Created)
+            }
+            Some(live) => {
+                if Manifest::Region(RegionManifest::from_region(&live)).same_as(desired) {
+                    Ok(Action::Unchanged)
+                } else {
+                    Err(anyhow!(
+                        "Region already exists as {}, regions cannot be updated",
+                        live.external
+                    ))
+                }
+            }
+        },
+        Manifest::Group(group) => match manifest::find_group(client, &group.name)? {
+            None => {
+                if !dry_run {
+                    client
+                        .create_group(NewGroup {
+                            name: group.name.to_owned(),
+                            appliance_secret: uuid::Uuid::new_v4().to_string(),
+                        })
+                        .context("Failed to create group")?;
+                }
+                Ok(Action::Created)
+            }
+            // The name is all there is to a group
+            Some(_) => Ok(Action::Unchanged),
+        },
+        Manifest::Input(input) => match manifest::find_input(client, &input.name)? {
+            None => {
+                let new = input.to_new_input(resolver)?;
+                if !dry_run {
+                    client.create_input(new).context("Failed to create input")?;
+                }
+                Ok(Action::Created)
+            }
+            Some(mut live) => {
+                let mut current = Manifest::Input(InputManifest::from_input(&live, resolver)?);
+                current.normalize(desired);
+                if current.same_as(desired) {
+                    return Ok(Action::Unchanged);
+                }
+                input.update(&mut live, resolver)?;
+                if !dry_run {
+                    client
+                        .update_input(&live)
+                        .context("Failed to update input")?;
+                }
+                Ok(Action::Configured)
+            }
+        },
+        Manifest::Output(output) => match manifest::find_output(client, &output.name)? {
+            None => {
+                let new = output.to_new_output(resolver)?;
+                if !dry_run {
+                    client
+                        .create_output(new)
+                        .context("Failed to create output")?;
+                }
+                Ok(Action::Created)
+            }
+            Some(mut live) => {
+                let mut current = Manifest::Output(OutputManifest::from_output(&live, resolver)?);
+                current.normalize(desired);
+                if current.same_as(desired) {
+                    return Ok(Action::Unchanged);
+                }
+                output.update(&mut live, resolver)?;
+                if !dry_run {
+                    client
+                        .update_output(&live)
+                        .context("Failed to update output")?;
+                }
+                Ok(Action::Configured)
+            }
+        },
+        Manifest::OutputList(list) => {
+            let outputs = list
+                .outputs
+                .iter()
+                .map(|name| {
+                    manifest::find_output(client, name)?
+                        .map(|o| o.id)
+                        .ok_or_else(|| anyhow!("Output not found: {}", name))
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            match manifest::find_output_list(client, &list.name)? {
+                None => {
+                    let group = resolver.group_id(list.group.as_deref().unwrap_or("system"))?;
+                    if !dry_run {
+                        client
+                            .create_output_recipient_list(NewOutputRecipientList {
+                                name: list.name.to_owned(),
+                                description: list.description.clone(),
+                                group,
+                                add_outputs: outputs,
+                            })
+                            .context("Failed to create output list")?;
+                    }
+                    Ok(Action::Created)
+                }
+                Some(live) => {
+                    let mut current = OutputListManifest::from_list(&live, client, resolver)?;
+                    current.outputs.clone_from(&list.outputs);
+                    let mut current = Manifest::OutputList(current);
+                    current.normalize(desired);
+                    if !current.same_as(desired) {
+                        return Err(anyhow!("Only the outputs of an output list can be updated"));
+                    }
+
+                    let members = client
+                        .get_output_list_members(&live.id)
+                        .context("Failed to get output list members")?
+                        .into_iter()
+                        .map(|o| o.id)
+                        .collect::<Vec<_>>();
+                    let (add, remove) = membership_changes(&members, &outputs);
+                    if add.is_empty() && remove.is_empty() {
+                        return Ok(Action::Unchanged);
+                    }
+                    if !dry_run {
+                        if !add.is_empty() {
+                            client
+                                .add_output_to_list(&live.id, &live.name, add)
+                                .context("Failed to add outputs to list")?;
+                        }
+                        if !remove.is_empty() {
+                            client
+                                .remove_output_from_list(&live.id, &live.name, remove)
+                                .context("Failed to remove outputs from list")?;
+                        }
+                    }
+                    Ok(Action::Configured)
+                }
+            }
+        }
+        Manifest::GroupList(list) => {
+            let groups = list
+                .groups
+                .iter()
+                .map(|name| resolver.group_id(name))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            match manifest::find_group_list(client, &list.name)? {
+                None => {
+                    let group = resolver.group_id(list.group.as_deref().unwrap_or("system"))?;
+                    if !dry_run {
+                        client
+                            .create_group_recipient_list(NewGroupRecipientList {
+                                name: list.name.to_owned(),
+                                description: list.description.clone(),
+                                group,
+                                add_groups: groups,
+                            })
+                            .context("Failed to create group list")?;
+                    }
+                    Ok(Action::Created)
+                }
+                Some(live) => {
+                    let mut current = GroupListManifest::from_list(&live, client, resolver)?;
+                    current.groups.clone_from(&list.groups);
+                    let mut current = Manifest::GroupList(current);
+                    current.normalize(desired);
+                    if !current.same_as(desired) {
+                        return Err(anyhow!("Only the groups of a group list can be updated"));
+                    }
+
+                    let members = client
+                        .get_group_list_members(&live.id)
+                        .context("Failed to get group list members")?
+                        .into_iter()
+                        .map(|g| g.id)
+                        .collect::<Vec<_>>();
+                    let (add, remove) = membership_changes(&members, &groups);
+                    if add.is_empty() && remove.is_empty() {
+                        return Ok(Action::Unchanged);
+                    }
+                    if !dry_run {
+                        if !add.is_empty() {
+                            client
+                                .add_group_to_list(&live.id, &live.name, add)
+                                .context("Failed to add groups to list")?;
+                        }
+                        if !remove.is_empty() {
+                            client
+                                .remove_group_from_list(&live.id, &live.name, remove)
+                                .context("Failed to remove groups from list")?;
+                        }
+                    }
+                    Ok(Action::Configured)
+                }
+            }
+        }
+    }
+}
+
+// The IDs to add to and remove from a list to go from `current` to `desired`
+fn membership_changes(current: &[String], desired: &[String]) -> (Vec<String>, Vec<String>) {
+    let add = desired
+        .iter()
+        .filter(|id| !current.contains(id))
+        .cloned()
+        .collect();
+    let remove = current
+        .iter()
+        .filter(|id| !desired.contains(id))
+        .cloned()
+        .collect();
+    (add, remove)
+}
@@ -1,22 +1,11 @@
-use std::{fmt, process};
-
-use crate::edge::{EdgeClient, Product};
-
-impl fmt::Display for Product {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            Self::NimbraEdge => f.pad("Nimbra Edge"),
-            Self::ConnectIt => f.pad("Connect iT"),
-        }
-    }
-}
+use crate::edge::EdgeClient;
+use crate::exit_with;
 
 pub fn show(client: EdgeClient) {
     let info = match client.get_build_info() {
         Ok(info) => info,
         Err(e) => {
-            eprintln!("Failed to get build info: {}", e);
-            process::exit(1);
+            exit_with(anyhow::Error::from(e).context("Failed to get build info"));
         }
     };
 
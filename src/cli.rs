@@ -1,6 +1,9 @@
 use crate::alarm;
 use crate::appliance;
+use crate::apply;
 use crate::context;
+use crate::diff;
+use crate::export;
 use crate::group;
 use crate::group_list;
 use crate::health;
@@ -14,18 +17,75 @@ use crate::settings;
 use crate::token;
 use crate::tunnels;
 
-use clap::Command;
+use clap::{Arg, Command};
+use clap_complete::ArgValueCompleter;
 
 pub(crate) fn build() -> Command {
-    Command::new("edgectl")
+    let command = Command::new("edgectl")
         .about("Nimbra Edge CLI")
         .version(option_env!("VERSION").unwrap_or("unknown"))
         .subcommand_required(true)
+        .arg(
+            Arg::new("context")
+                .long("context")
+                .global(true)
+                .help("The context to use instead of the current one, also set by EDGE_CONTEXT")
+                .add(ArgValueCompleter::new(context::context_name_completer)),
+        )
+        .arg(
+            Arg::new("all-contexts")
+                .long("all-contexts")
+                .global(true)
+                .num_args(0)
+                .conflicts_with_all(["context", "contexts"])
+                .help("Run the command against every context and merge the results"),
+        )
+        .arg(
+            Arg::new("contexts")
+                .long("contexts")
+                .global(true)
+                .value_delimiter(',')
+                .conflicts_with("context")
+                .help("Run the command against a comma separated list of contexts and merge the results")
+                .add(ArgValueCompleter::new(context::context_name_completer)),
+        )
+        .arg(
+            Arg::new("verbose")
+                .long("verbose")
+                .short('v')
+                .global(true)
+                .num_args(0)
+                .help("Log every request to the Edge API on stderr"),
+        )
+        .arg(
+            Arg::new("connect-timeout")
+                .long("connect-timeout")
+                .global(true)
+                .value_parser(humantime::parse_duration)
+                .help("How long to wait for a connection to the Edge API [default: 10s]"),
+        )
+        .arg(
+            Arg::new("request-timeout")
+                .long("request-timeout")
+                .global(true)
+                .value_parser(humantime::parse_duration)
+                .help("How long to wait for a request to the Edge API [default: 30s]"),
+        )
+        .arg(
+            Arg::new("retries")
+                .long("retries")
+                .global(true)
+                .value_parser(clap::value_parser!(u32))
+                .help("How many times to retry failed requests to the Edge API [default: 3]"),
+        )
         .subcommand(alarm::subcommand())
         .subcommand(input::subcommand())
         .subcommand(output::subcommand())
         .subcommand(output_list::subcommand())
         .subcommand(appliance::subcommand())
+        .subcommand(apply::subcommand())
+        .subcommand(diff::subcommand())
+        .subcommand(export::subcommand())
         .subcommand(group::subcommand())
         .subcommand(group_list::subcommand())
         .subcommand(region::subcommand())
@@ -36,6 +96,10 @@ pub(crate) fn build() -> Command {
         .subcommand(login::subcommand())
         .subcommand(token::subcommand())
         .subcommand(context::subcommand())
-        .subcommand(Command::new("build-info").about("Show build information for installation"))
-        .subcommand(Command::new("tui").about("Open interactive TUI"))
+        .subcommand(Command::new("build-info").about("Show build information for installation"));
+
+    #[cfg(feature = "tui")]
+    let command = command.subcommand(Command::new("tui").about("Open interactive TUI"));
+
+    command
 }
@@ -0,0 +1,109 @@
+use std::env;
+use std::sync::OnceLock;
+
+use anyhow::{anyhow, Context};
+
+use crate::config::Config;
+use crate::credentials;
+use crate::edge::EdgeClient;
+use crate::token;
+use crate::OrExit;
+
+// The context selected with the global --context argument
+static CONTEXT: OnceLock<String> = OnceLock::new();
+
+/// Makes `new_client` use the named context instead of the current one
+pub(crate) fn set_context(name: &str) {
+    let _ = CONTEXT.set(name.to_owned());
+}
+
+/// The context selected with --context, EDGE_CONTEXT or `context use`
+pub(crate) fn selected_context() -> Option<String> {
+    CONTEXT
+        .get()
+        .cloned()
+        .or_else(|| env::var("EDGE_CONTEXT").ok())
+        .or_else(|| Config::load().context)
+}
+
+/// Creates a client for the selected context, exiting if that fails
+pub(crate) fn new_client() -> EdgeClient {
+    client().or_exit("Failed to connect")
+}
+
+/// Creates a client for a named context, exiting if that fails
+pub(crate) fn new_client_for_context(name: &str) -> EdgeClient {
+    client_for_context(name).or_exit("Failed to connect")
+}
+
+pub(crate) fn client() -> anyhow::Result<EdgeClient> {
+    // An explicitly selected context wins over both the environment and the
+    // current context, so that one-off commands never touch the config file
+    if let Some(name) = CONTEXT
+        .get()
+        .cloned()
+        .or_else(|| env::var("EDGE_CONTEXT").ok())
+    {
+        return client_for_context(&name);
+    }
+
+    let config = Config::load();
+    if ["EDGE_URL", "EDGE_PASSWORD", "EDGE_TOKEN"]
+        .iter()
+        .all(|var| env::var(var).is_err())
+    {
+        if let (Some(name), Some(_)) = (&config.context, config.get_current_context()) {
+            return client_for_context(name);
+        }
+    }
+
+    let url = env::var("EDGE_URL")
+        .ok()
+        .or_else(|| config.get_current_context().map(|c| c.url.clone()))
+        .ok_or_else(|| {
+            anyhow!("No URL provided, either via config or env var. Try:\nedgectl login")
+        })?;
+
+    if let Ok(password) = env::var("EDGE_PASSWORD") {
+        let client = EdgeClient::with_url(&url)?;
+        let username = env::var("EDGE_USER").unwrap_or_else(|_| "admin".to_owned());
+        client
+            .login(username, password)
+            .context("Failed to authenticate")?;
+        return Ok(client);
+    }
+
+    let token = match env::var("EDGE_TOKEN") {
+        Ok(token) => token,
+        Err(_) => {
+            let (Some(name), Some(context)) = (&config.context, config.get_current_context())
+            else {
+                return Err(anyhow!(
+                    "No credentials found. Run 'edgectl login' to authenticate."
+                ));
+            };
+            credentials::token(name, context)?
+        }
+    };
+
+    Ok(EdgeClient::with_url_and_token(&url, &token)?)
+}
+
+/// Creates a client for a named context from the config, regardless of which
+/// context is current
+pub(crate) fn client_for_context(name: &str) -> anyhow::Result<EdgeClient> {
+    let config = Config::load();
+    let context = config
+        .contexts
+        .get(name)
+        .cloned()
+        .ok_or_else(|| anyhow!("Context '{}' does not exist", name))?;
+    let context = token::check_expiry(name, context);
+    let token = credentials::token(name, &context)?;
+
+    Ok(EdgeClient::with_options(
+        &context.url,
+        &token,
+        &context.http_options(),
+    )?)
+}
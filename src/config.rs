@@ -5,15 +5,74 @@ use std::fs;
 use std::io::Write;
 use std::path::PathBuf;
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+use crate::credentials::{self, CredentialStore};
+use crate::http;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct ContextConfig {
     pub url: String,
+    /// Empty when the token is kept in `credential_store`
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub token: String,
     pub token_name: String,
     pub username: String,
+    /// When the token expires, as RFC 3339. Unknown for contexts created by
+    /// older versions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<String>,
+    /// Replace the token automatically when it is about to expire
+    #[serde(default)]
+    pub auto_rotate: bool,
+    /// Keep the token out of the config file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credential_store: Option<CredentialStore>,
+    /// How long to wait for a connection, e.g. 5s
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connect_timeout: Option<String>,
+    /// How long to wait for a whole request, e.g. 1m
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_timeout: Option<String>,
+    /// How many times to retry failed requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retries: Option<u32>,
+    /// Extra CA certificates to trust, in PEM
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ca_file: Option<String>,
+    /// A client certificate and its key, in PEM
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_cert: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_key: Option<String>,
+    /// Accept any server certificate, only meant for labs
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub insecure_skip_verify: bool,
+    /// An HTTP(S) or SOCKS proxy URL
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Default)]
+impl ContextConfig {
+    pub fn http_options(&self) -> http::Options {
+        let parse = |name: &str, value: &Option<String>| {
+            let value = value.as_ref()?;
+            humantime::parse_duration(value)
+                .inspect_err(|e| eprintln!("Warning: ignoring invalid {} '{}': {}", name, value, e))
+                .ok()
+        };
+        http::Options {
+            connect_timeout: parse("connect_timeout", &self.connect_timeout),
+            request_timeout: parse("request_timeout", &self.request_timeout),
+            retries: self.retries,
+            ca_file: self.ca_file.as_ref().map(PathBuf::from),
+            client_cert: self.client_cert.as_ref().map(PathBuf::from),
+            client_key: self.client_key.as_ref().map(PathBuf::from),
+            insecure_skip_verify: self.insecure_skip_verify,
+            proxy: self.proxy.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
 pub struct Config {
     pub context: Option<String>,
     #[serde(default)]
@@ -50,7 +109,17 @@ impl Config {
             fs::create_dir_all(parent)?;
         }
 
-        let contents = toml::to_string_pretty(self).context("Failed to serialize config")?;
+        // Tokens of contexts with a credential store never end up in the file
+        let mut config = self.clone();
+        for (name, context) in config.contexts.iter_mut() {
+            if context.credential_store.is_some() && !context.token.is_empty() {
+                credentials::store(name, context)
+                    .with_context(|| format!("Failed to store the token of '{}'", name))?;
+                context.token.clear();
+            }
+        }
+
+        let contents = toml::to_string_pretty(&config).context("Failed to serialize config")?;
 
         let mut file = fs::File::create(&path).context("Failed to open config")?;
 
@@ -1,12 +1,13 @@
 use crate::config::Config;
-use clap::{Arg, ArgMatches, Command};
+use crate::credentials::{self, CredentialStore};
+use clap::{Arg, ArgAction, ArgGroup, ArgMatches, Command};
 use clap_complete::{ArgValueCompleter, CompletionCandidate};
 use std::ffi::OsStr;
 use std::process;
 use tabled::builder::Builder;
 use tabled::settings::Style;
 
-fn context_name_completer(current: &OsStr) -> Vec<CompletionCandidate> {
+pub(crate) fn context_name_completer(current: &OsStr) -> Vec<CompletionCandidate> {
     let config = Config::load();
     let current_str = current.to_str().unwrap_or("");
     config
@@ -41,6 +42,39 @@ pub fn subcommand() -> Command {
                     .add(ArgValueCompleter::new(context_name_completer)),
             ),
         )
+        .subcommand(
+            Command::new("migrate-credentials")
+                .about("Move the tokens of contexts to another credential store")
+                .arg(
+                    Arg::new("names")
+                        .num_args(0..)
+                        .action(ArgAction::Append)
+                        .help("The contexts to migrate, all contexts if none are given")
+                        .add(ArgValueCompleter::new(context_name_completer)),
+                )
+                .arg(
+                    Arg::new("helper")
+                        .long("helper")
+                        .help("Keep the tokens in a git style credential helper, run as <HELPER> get|store|erase"),
+                )
+                .arg(
+                    Arg::new("encrypted")
+                        .long("encrypted")
+                        .num_args(0)
+                        .help("Keep the tokens in a file encrypted with a passphrase from EDGE_CREDENTIALS_PASSPHRASE or a prompt"),
+                )
+                .arg(
+                    Arg::new("plain")
+                        .long("plain")
+                        .num_args(0)
+                        .help("Keep the tokens in the config file"),
+                )
+                .group(
+                    ArgGroup::new("store")
+                        .args(["helper", "encrypted", "plain"])
+                        .required(true),
+                ),
+        )
 }
 
 pub fn run(args: &ArgMatches) {
@@ -51,6 +85,7 @@ pub fn run(args: &ArgMatches) {
             use_context(args.get_one::<String>("name").expect("name is required"))
         }
         Some(("delete", args)) => delete(args.get_one::<String>("name").expect("name is required")),
+        Some(("migrate-credentials", args)) => migrate_credentials(args),
         _ => {
             eprintln!("Unknown subcommand. Use --help for usage information.");
             process::exit(1);
@@ -67,12 +102,23 @@ fn list() {
     }
 
     let mut builder = Builder::default();
-    builder.push_record(["Current", "Name", "URL", "Username"]);
+    builder.push_record(["Current", "Name", "URL", "Username", "Credentials"]);
 
     for (name, context) in config.list_contexts() {
         let is_current = config.context.as_ref() == Some(name);
         let current_marker = if is_current { "*" } else { "" };
-        builder.push_record([current_marker, name, &context.url, &context.username]);
+        let store = context
+            .credential_store
+            .as_ref()
+            .map(|store| store.to_string())
+            .unwrap_or("config".to_owned());
+        builder.push_record([
+            current_marker,
+            name,
+            &context.url,
+            &context.username,
+            &store,
+        ]);
     }
 
     let mut table = builder.build();
@@ -119,6 +165,14 @@ fn delete(name: &str) {
         process::exit(1);
     }
 
+    let context = config.contexts[name].clone();
+    if let Err(e) = credentials::erase(name, &context) {
+        eprintln!(
+            "Warning: failed to remove the token from the credential store: {:#}",
+            e
+        );
+    }
+
     if let Err(e) = config.delete_context(name) {
         eprintln!("Failed to delete context: {}", e);
         process::exit(1);
@@ -131,3 +185,67 @@ fn delete(name: &str) {
 
     println!("Deleted context '{}'", name);
 }
+
+fn migrate_credentials(args: &ArgMatches) {
+    let store = if let Some(command) = args.get_one::<String>("helper") {
+        Some(CredentialStore::Helper {
+            command: command.to_owned(),
+        })
+    } else if args.get_flag("encrypted") {
+        Some(CredentialStore::Encrypted)
+    } else {
+        None
+    };
+
+    let mut config = Config::load();
+    let names = match args.get_many::<String>("names") {
+        Some(names) => names.cloned().collect::<Vec<_>>(),
+        None => config
+            .list_contexts()
+            .into_iter()
+            .map(|(n, _)| n.clone())
+            .collect(),
+    };
+
+    let mut migrated = Vec::new();
+    for name in &names {
+        let Some(context) = config.contexts.get_mut(name) else {
+            eprintln!("Context '{}' not found.", name);
+            process::exit(1);
+        };
+        if context.credential_store == store {
+            continue;
+        }
+        let token = credentials::token(name, context).unwrap_or_else(|e| {
+            eprintln!("Failed to read the token of '{}': {:#}", name, e);
+            process::exit(1);
+        });
+        migrated.push((name, context.clone()));
+        // Saving the config moves the token to the new store
+        context.token = token;
+        context.credential_store = store.clone();
+    }
+
+    if let Err(e) = config.save() {
+        eprintln!("Failed to save config: {:#}", e);
+        process::exit(1);
+    }
+
+    // Only clean up once the tokens are safely in the new store
+    for (name, context) in &migrated {
+        if let Err(e) = credentials::erase(name, context) {
+            eprintln!(
+                "Warning: failed to remove the token of '{}' from the old credential store: {:#}",
+                name, e
+            );
+        }
+    }
+
+    println!(
+        "Migrated {} context(s) to {}",
+        migrated.len(),
+        store
+            .map(|s| s.to_string())
+            .unwrap_or("the config file".to_owned())
+    );
+}
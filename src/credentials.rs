@@ -0,0 +1,318 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io::{ErrorKind, IsTerminal, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+
+use anyhow::{anyhow, Context};
+use openssl::base64;
+use openssl::hash::MessageDigest;
+use openssl::pkcs5::pbkdf2_hmac;
+use openssl::rand::rand_bytes;
+use openssl::symm::{decrypt_aead, encrypt_aead, Cipher};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{Config, ContextConfig};
+
+/// Where the token of a context is kept when it is not in the config file
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum CredentialStore {
+    /// A git style credential helper. The command is run with `get`, `store`
+    /// or `erase` as the last argument and `key=value` lines on stdin
+    Helper { command: String },
+    /// A file next to the config, encrypted with a passphrase from
+    /// EDGE_CREDENTIALS_PASSPHRASE or a prompt
+    Encrypted,
+}
+
+impl std::fmt::Display for CredentialStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CredentialStore::Helper { command } => write!(f, "helper ({})", command),
+            CredentialStore::Encrypted => write!(f, "encrypted"),
+        }
+    }
+}
+
+/// Returns the token of a context, wherever it is stored
+pub fn token(name: &str, context: &ContextConfig) -> anyhow::Result<String> {
+    // A token that is set but not saved yet wins over the store
+    if !context.token.is_empty() {
+        return Ok(context.token.clone());
+    }
+    match &context.credential_store {
+        None => Err(anyhow!("Context '{}' has no token", name)),
+        Some(CredentialStore::Helper { command }) => {
+            let output = helper(command, "get", &helper_input(name, context, None))?;
+            let token = helper_token(&output)
+                .ok_or_else(|| anyhow!("The credential helper returned no token for '{}'", name))?;
+            Ok(token.to_owned())
+        }
+        Some(CredentialStore::Encrypted) => {
+            let file = EncryptedFile::load()?;
+            let entry = file.contexts.get(name).ok_or_else(|| {
+                anyhow!("No token for '{}' in the encrypted credential store", name)
+            })?;
+            entry.decrypt(&passphrase()?)
+        }
+    }
+}
+
+/// Saves the token of a context in its store
+pub fn store(name: &str, context: &ContextConfig) -> anyhow::Result<()> {
+    match &context.credential_store {
+        None => Ok(()),
+        Some(CredentialStore::Helper { command }) => helper(
+            command,
+            "store",
+            &helper_input(name, context, Some(&context.token)),
+        )
+        .map(|_| ()),
+        Some(CredentialStore::Encrypted) => {
+            let _lock = FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+            let mut file = EncryptedFile::load()?;
+            file.contexts.insert(
+                name.to_owned(),
+                Entry::encrypt(&context.token, &passphrase()?)?,
+            );
+            file.save()
+        }
+    }
+}
+
+/// Removes the token of a context from its store
+pub fn erase(name: &str, context: &ContextConfig) -> anyhow::Result<()> {
+    match &context.credential_store {
+        None => Ok(()),
+        Some(CredentialStore::Helper { command }) => {
+            helper(command, "erase", &helper_input(name, context, None)).map(|_| ())
+        }
+        Some(CredentialStore::Encrypted) => {
+            let _lock = FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+            let mut file = EncryptedFile::load()?;
+            if file.contexts.remove(name).is_some() {
+                file.save()?;
+            }
+            Ok(())
+        }
+    }
+}
+
+fn helper_input(name: &str, context: &ContextConfig, token: Option<&str>) -> String {
+    let mut input = format!("context={}\nurl={}\n", name, context.url);
+    if let Some(token) = token {
+        input.push_str(&format!("token={}\n", token));
+    }
+    input.push('\n');
+    input
+}
+
+// The token in the output of a helper `get`: the `token=` line of git style
+// `key=value` output, or the only line of a helper that just prints the token
+fn helper_token(output: &str) -> Option<&str> {
+    let lines = output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>();
+    if let Some(token) = lines.iter().find_map(|line| line.strip_prefix("token=")) {
+        return Some(token.trim()).filter(|token| !token.is_empty());
+    }
+    let is_key_value = |line: &&str| {
+        line.split_once('=').is_some_and(|(key, _)| {
+            !key.is_empty()
+                && key
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c == '_' || c == '-')
+        })
+    };
+    match lines.as_slice() {
+        [token] if !is_key_value(token) => Some(token),
+        _ => None,
+    }
+}
+
+fn helper(command: &str, operation: &str, input: &str) -> anyhow::Result<String> {
+    // The helper may come with arguments, e.g. `pass-helper --store edge`
+    let mut words = command.split_whitespace();
+    let program = words
+        .next()
+        .ok_or_else(|| anyhow!("The credential helper is empty"))?;
+    let mut child = Command::new(program)
+        .args(words)
+        .arg(operation)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .with_context(|| format!("Failed to start credential helper {}", command))?;
+    // Helpers that only print a token are free to ignore stdin
+    match child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(input.as_bytes())
+    {
+        Err(e) if e.kind() != ErrorKind::BrokenPipe => {
+            return Err(e).context("Failed to write to the credential helper")
+        }
+        _ => {}
+    }
+    let output = child
+        .wait_with_output()
+        .context("Failed to run the credential helper")?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "Credential helper {} {} exited with {}",
+            command,
+            operation,
+            output.status
+        ));
+    }
+    String::from_utf8(output.stdout).context("The credential helper returned invalid UTF-8")
+}
+
+// Clients for several contexts can be created in parallel
+static FILE_LOCK: Mutex<()> = Mutex::new(());
+static PASSPHRASE: Mutex<Option<String>> = Mutex::new(None);
+
+const PBKDF2_ITERATIONS: usize = 600_000;
+
+fn passphrase() -> anyhow::Result<String> {
+    let mut cached = PASSPHRASE.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(passphrase) = cached.as_ref() {
+        return Ok(passphrase.clone());
+    }
+    let passphrase = match env::var("EDGE_CREDENTIALS_PASSPHRASE") {
+        Ok(passphrase) => passphrase,
+        Err(_) if std::io::stdin().is_terminal() => {
+            rpassword::prompt_password("Passphrase for the edgectl credential store: ")
+                .context("Failed to read passphrase")?
+        }
+        Err(_) => {
+            return Err(anyhow!(
+                "EDGE_CREDENTIALS_PASSPHRASE is required to use the encrypted credential store in non-interactive mode"
+            ))
+        }
+    };
+    *cached = Some(passphrase.clone());
+    Ok(passphrase)
+}
+
+fn file_path() -> anyhow::Result<PathBuf> {
+    Config::config_path()
+        .map(|path| path.with_file_name("credentials"))
+        .ok_or_else(|| anyhow!("Failed to determine config directory"))
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct EncryptedFile {
+    #[serde(default)]
+    contexts: BTreeMap<String, Entry>,
+}
+
+impl EncryptedFile {
+    fn load() -> anyhow::Result<Self> {
+        let path = file_path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        toml::from_str(&contents).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    fn save(&self) -> anyhow::Result<()> {
+        let path = file_path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let contents = toml::to_string_pretty(self).context("Failed to serialize credentials")?;
+        let mut file = fs::File::create(&path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mut perms = file.metadata()?.permissions();
+            perms.set_mode(0o600);
+            fs::set_permissions(&path, perms)?;
+        }
+
+        file.write_all(contents.as_bytes())?;
+        Ok(())
+    }
+}
+
+/// A token encrypted with AES-256-GCM, with the key derived from the
+/// passphrase with PBKDF2. All fields are base64.
+#[derive(Debug, Serialize, Deserialize)]
+struct Entry {
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+    tag: String,
+}
+
+impl Entry {
+    fn encrypt(token: &str, passphrase: &str) -> anyhow::Result<Self> {
+        let mut salt = [0; 16];
+        let mut nonce = [0; 12];
+        rand_bytes(&mut salt)?;
+        rand_bytes(&mut nonce)?;
+        let key = derive_key(passphrase, &salt)?;
+
+        let mut tag = [0; 16];
+        let ciphertext = encrypt_aead(
+            Cipher::aes_256_gcm(),
+            &key,
+            Some(&nonce),
+            &[],
+            token.as_bytes(),
+            &mut tag,
+        )
+        .context("Failed to encrypt token")?;
+
+        Ok(Entry {
+            salt: base64::encode_block(&salt),
+            nonce: base64::encode_block(&nonce),
+            ciphertext: base64::encode_block(&ciphertext),
+            tag: base64::encode_block(&tag),
+        })
+    }
+
+    fn decrypt(&self, passphrase: &str) -> anyhow::Result<String> {
+        let salt = base64::decode_block(&self.salt)?;
+        let nonce = base64::decode_block(&self.nonce)?;
+        let ciphertext = base64::decode_block(&self.ciphertext)?;
+        let tag = base64::decode_block(&self.tag)?;
+        let key = derive_key(passphrase, &salt)?;
+
+        let token = decrypt_aead(
+            Cipher::aes_256_gcm(),
+            &key,
+            Some(&nonce),
+            &[],
+            &ciphertext,
+            &tag,
+        )
+        .map_err(|_| anyhow!("Failed to decrypt token, wrong passphrase?"))?;
+        String::from_utf8(token).context("The decrypted token is invalid UTF-8")
+    }
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> anyhow::Result<[u8; 32]> {
+    let mut key = [0; 32];
+    pbkdf2_hmac(
+        passphrase.as_bytes(),
+        salt,
+        PBKDF2_ITERATIONS,
+        MessageDigest::sha256(),
+        &mut key,
+    )
+    .context("Failed to derive key")?;
+    Ok(key)
+}
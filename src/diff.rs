@@ -0,0 +1,222 @@
+use std::process;
+
+use clap::{Arg, ArgAction, ArgMatches, Command};
+
+use crate::client::new_client;
+use crate::format::{self, Format};
+use crate::manifest::{self, Resolver};
+use crate::OrExit;
+use crate::{green, grey, red};
+
+// Unchanged lines shown around each change
+const CONTEXT: usize = 3;
+
+pub(crate) fn subcommand() -> clap::Command {
+    Command::new("diff")
+        .about("Show how manifests differ from the live objects")
+        .long_about(
+            "Show how manifests differ from the live objects. \
+            Exits with 1 if there are differences and 2 if the diff could not be made.",
+        )
+        .arg(
+            Arg::new("filename")
+                .short('f')
+                .long("filename")
+                .required(true)
+                .action(ArgAction::Append)
+                .help("Manifest file or directory of manifests, - to read from stdin"),
+        )
+}
+
+pub(crate) fn run(args: &ArgMatches) {
+    let mut manifests = Vec::new();
+    for path in args
+        .get_many::<String>("filename")
+        .expect("Filename is mandatory")
+    {
+        match manifest::load(path) {
+            Ok(m) => manifests.extend(m),
+            Err(e) => {
+                eprintln!("{:#}", e);
+                process::exit(2);
+            }
+        }
+    }
+
+    let client = new_client();
+    let mut resolver = Resolver::new(&client);
+    let mut drift = false;
+    let mut failed = false;
+    for desired in manifests {
+        let live = match manifest::live(&client, &mut resolver, &desired) {
+            Ok(live) => live,
+            Err(e) => {
+                eprintln!(
+                    "Failed to get {}/{}: {:#}",
+                    desired.kind(),
+                    desired.name(),
+                    e
+                );
+                failed = true;
+                continue;
+            }
+        };
+        if live.as_ref().is_some_and(|live| live.same_as(&desired)) {
+            continue;
+        }
+        drift = true;
+
+        let old = live
+            .map(|live| format::to_string(Format::Yaml, &live))
+            .transpose()
+            .or_exit("Failed to serialize manifest")
+            .unwrap_or_default();
+        let new = format::to_string(Format::Yaml, &desired).or_exit("Failed to serialize manifest");
+        println!("--- live/{}/{}", desired.kind(), desired.name());
+        println!("+++ manifest/{}/{}", desired.kind(), desired.name());
+        for line in unified_diff(&old, &new) {
+            match line.chars().next() {
+                Some('-') => println!("{}", red!(line)),
+                Some('+') => println!("{}", green!(line)),
+                Some('@') => println!("{}", grey!(line)),
+                _ => println!("{}", line),
+            }
+        }
+    }
+
+    if failed {
+        process::exit(2);
+    }
+    if drift {
+        process::exit(1);
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Edit<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+// The shortest edit script from `old` to `new`, based on the longest common
+// subsequence of lines. Manifests are small enough for the quadratic table.
+fn edits<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Edit<'a>> {
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut edits = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            edits.push(Edit::Same(old[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            edits.push(Edit::Removed(old[i]));
+            i += 1;
+        } else {
+            edits.push(Edit::Added(new[j]));
+            j += 1;
+        }
+    }
+    edits.extend(old[i..].iter().map(|l| Edit::Removed(l)));
+    edits.extend(new[j..].iter().map(|l| Edit::Added(l)));
+    edits
+}
+
+/// The lines of a unified diff from `old` to `new`, without file headers
+fn unified_diff(old: &str, new: &str) -> Vec<String> {
+    let old = old.lines().collect::<Vec<_>>();
+    let new = new.lines().collect::<Vec<_>>();
+    let edits = edits(&old, &new);
+
+    // Changes close enough to share context end up in the same hunk
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for (i, edit) in edits.iter().enumerate() {
+        if let Edit::Same(_) = edit {
+            continue;
+        }
+        let start = i.saturating_sub(CONTEXT);
+        let end = (i + CONTEXT + 1).min(edits.len());
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let old_lines = |edits: &[Edit]| {
+        edits
+            .iter()
+            .filter(|e| !matches!(e, Edit::Added(_)))
+            .count()
+    };
+    let new_lines = |edits: &[Edit]| {
+        edits
+            .iter()
+            .filter(|e| !matches!(e, Edit::Removed(_)))
+            .count()
+    };
+    let range = |start: usize, len: usize| {
+        if len == 0 {
+            format!("{},0", start)
+        } else {
+            format!("{},{}", start + 1, len)
+        }
+    };
+
+    let mut lines = Vec::new();
+    for (start, end) in hunks {
+        lines.push(format!(
+            "@@ -{} +{} @@",
+            range(old_lines(&edits[..start]), old_lines(&edits[start..end])),
+            range(new_lines(&edits[..start]), new_lines(&edits[start..end])),
+        ));
+        for edit in &edits[start..end] {
+            lines.push(match edit {
+                Edit::Same(line) => format!(" {}", line),
+                Edit::Removed(line) => format!("-{}", line),
+                Edit::Added(line) => format!("+{}", line),
+            });
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_makes_unified_diffs() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh\ni\n";
+        let new = "a\nB\nc\nd\ne\nf\ng\nh\ni\nj\n";
+        assert_eq!(
+            unified_diff(old, new),
+            vec![
+                "@@ -1,5 +1,5 @@",
+                " a",
+                "-b",
+                "+B",
+                " c",
+                " d",
+                " e",
+                "@@ -7,3 +7,4 @@",
+                " g",
+                " h",
+                " i",
+                "+j",
+            ]
+        );
+        assert_eq!(unified_diff("", "a\n"), vec!["@@ -0,0 +1,1 @@", "+a"]);
+        assert!(unified_diff(old, old).is_empty());
+    }
+}
@@ -1,49 +1,20 @@
+//! The Edge API client and the models it sends and receives
+
 use std::fmt;
-use std::{env, process};
 
 use reqwest::blocking::Response;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use serde::de::DeserializeOwned;
 use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::BTreeMap;
 
-use crate::config::Config;
-
-pub fn new_client() -> EdgeClient {
-    let config = Config::load();
-
-    let url = env::var("EDGE_URL")
-        .ok()
-        .or_else(|| config.get_current_context().map(|c| c.url.clone()))
-        .unwrap_or_else(|| {
-            eprintln!("No URL provided, either via config or env var. Try:");
-            eprintln!("edgectl login");
-            process::exit(1);
-        });
-
-    if let Ok(password) = env::var("EDGE_PASSWORD") {
-        let client = EdgeClient::with_url(&url);
-        let username = env::var("EDGE_USER").unwrap_or_else(|_| "admin".to_owned());
-
-        if let Err(e) = client.login(username, password) {
-            eprintln!("Failed to authenticate: {}", e);
-            process::exit(1);
-        }
-
-        return client;
-    }
-
-    let token = env::var("EDGE_TOKEN")
-        .ok()
-        .or_else(|| config.get_current_context().map(|c| c.token.clone()))
-        .unwrap_or_else(|| {
-            eprintln!("No credentials found. Run 'edgectl login' to authenticate.");
-            process::exit(1);
-        });
-
-    EdgeClient::with_url_and_token(&url, &token)
-}
+use crate::http::{self, HttpClient};
+use crate::parallel;
+use crate::query::{Pages, Query, PAGE_SIZE};
 
+/// A blocking client for the Edge API of one installation
 pub struct EdgeClient {
-    pub client: reqwest::blocking::Client,
+    pub client: HttpClient,
     pub url: String,
 }
 
@@ -64,7 +35,7 @@ pub struct ApiTokenInit {
     pub scopes: Vec<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ApiToken {
     pub id: String,
@@ -202,6 +173,33 @@ pub struct Input {
     pub health: InputHealth,
     pub ports: Option<Vec<InputPort>>,
     pub metrics: Option<InputMetrics>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub derive_from: Option<DerivableInputSource>,
+}
+
+impl Input {
+    pub fn get_all_channels(&self) -> Vec<ChannelInfo> {
+        let Some(metrics) = self.metrics.as_ref() else {
+            return Vec::new();
+        };
+        let Some(rist_metrics) = metrics.rist_metrics.as_ref() else {
+            return Vec::new();
+        };
+
+        rist_metrics
+            .iter()
+            .filter_map(|metric| {
+                if metric.metric_type == "channel" {
+                    metric.channel_id.map(|id| ChannelInfo {
+                        channel_id: id,
+                        active: metric.state.as_deref() == Some("activated"),
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -216,6 +214,9 @@ pub struct NewInput {
     pub ports: Vec<NewInputPort>,
     pub buffer_size: u16,
     pub max_bitrate: Option<u16>,
+    // The ID of the group, defaults to the group of the user
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub derive_from: Option<DerivableInputSource>,
 }
@@ -239,7 +240,7 @@ pub struct InputAppliance {
     pub name: String,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct DerivableInputSource {
     pub parent_input: String,
@@ -247,7 +248,7 @@ pub struct DerivableInputSource {
     pub ingest_transform: Option<IngestTransform>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 #[serde(tag = "type")]
 pub enum IngestTransform {
@@ -257,15 +258,18 @@ pub enum IngestTransform {
         services: Vec<u16>,
         pid_map: Option<PidMap>,
     },
+    // Transforms edgectl does not know about yet, kept as is so they can be sent back
+    #[serde(untagged)]
+    Unsupported(serde_json::Value),
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct PidMap {
     pub rules: Vec<PIDRule>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 #[serde(tag = "action")]
 pub enum PIDRule {
@@ -277,6 +281,16 @@ pub enum PIDRule {
     SetNull { pid: u16 },
 }
 
+impl fmt::Display for PIDRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Map { pid, dest_pid } => write!(f, "map {} to {}", pid, dest_pid),
+            Self::Delete { pid } => write!(f, "delete {}", pid),
+            Self::SetNull { pid } => write!(f, "set {} to null", pid),
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Output {
@@ -297,6 +311,12 @@ pub struct Output {
     pub alarms: Option<Vec<OutputAlarm>>,
     pub appliances: Vec<LimitedAppliance>,
     pub ports: Vec<OutputPort>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    // The fields edgectl does not model, kept so that the output can be sent
+    // back unchanged when updating it
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Serialize)]
@@ -310,7 +330,8 @@ pub struct NewOutput {
     pub delay_mode: Option<OutputDelayMode>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub group: Option<String>,
-    pub input: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input: Option<String>,
     pub ports: Vec<OutputPort>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub redundancy_mode: Option<OutputRedundancyMode>,
@@ -344,7 +365,7 @@ impl Serialize for OutputAdminStatus {
     }
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Clone)]
 pub enum OutputRedundancyMode {
     None = 0,
     Failover = 1,
@@ -379,7 +400,20 @@ impl<'de> Deserialize<'de> for OutputRedundancyMode {
     }
 }
 
-#[derive(Debug, Serialize, Clone)]
+impl Serialize for OutputRedundancyMode {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::None => serializer.serialize_u8(0),
+            Self::Failover => serializer.serialize_u8(1),
+            Self::Active => serializer.serialize_u8(2),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub enum OutputDelayMode {
     BasedOnArrivalTime = 1,
     BasedOnOriginTime = 2,
@@ -408,6 +442,18 @@ impl<'de> Deserialize<'de> for OutputDelayMode {
     }
 }
 
+impl Serialize for OutputDelayMode {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::BasedOnArrivalTime => serializer.serialize_u8(1),
+            Self::BasedOnOriginTime => serializer.serialize_u8(2),
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct OutputAlarm {
@@ -505,6 +551,9 @@ pub enum OutputHealthState {
     Alarm,
 }
 
+/// A port of an output. Every port keeps its `id`, empty for ports that have
+/// not been created yet, and the fields edgectl does not model in `other`, so
+/// that outputs can be sent back unchanged when updating them.
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 #[serde(tag = "mode")]
@@ -523,18 +572,45 @@ pub enum OutputPort {
     ComprimatoNdi(ComprimatoNdiOutputPort),
 }
 
+impl OutputPort {
+    /// The ID of the physical port the output is sent from, if the mode has one
+    /// and the API returned it
+    pub fn physical_port(&self) -> Option<&str> {
+        let physical_port: Option<&str> = match self {
+            Self::Udp(port) => Some(&port.physical_port),
+            Self::Rtp(port) => Some(&port.physical_port),
+            Self::Rist(port) => Some(&port.physical_port),
+            Self::Srt(SrtOutputPort::Listener(port)) => Some(&port.physical_port),
+            Self::Srt(SrtOutputPort::Caller(port)) => Some(&port.physical_port),
+            Self::Srt(SrtOutputPort::Rendezvous(port)) => Some(&port.physical_port),
+            Self::Rtmp(port) => Some(&port.physical_port),
+            Self::Zixi(ZixiOutputPort::Pull(port)) => Some(&port.physical_port),
+            Self::Zixi(ZixiOutputPort::Push(port)) => Some(&port.physical_port),
+            Self::Sdi(port) => Some(&port.physical_port),
+            _ => None,
+        };
+        physical_port.filter(|p| !p.is_empty())
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct UdpOutputPort {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub id: String,
     pub address: String,
     pub port: u16,
     pub physical_port: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub source_address: Option<String>,
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
 }
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct RtpOutputPort {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub id: String,
     pub address: String,
     pub port: u16,
     pub physical_port: String,
@@ -546,6 +622,8 @@ pub struct RtpOutputPort {
     pub fec_cols: Option<u8>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub source_address: Option<String>,
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
 }
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -558,12 +636,16 @@ pub enum OutputPortFec {
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct RistOutputPort {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub id: String,
     pub address: String,
     pub port: u16,
     pub physical_port: String,
     pub profile: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub source_address: Option<String>,
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
 }
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -576,14 +658,20 @@ pub enum SrtOutputPort {
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct SrtListenerOutputPort {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub id: String,
     pub local_ip: String,
     pub local_port: u16,
     pub physical_port: String,
     pub latency: u16,
     pub pbkeylen: SrtKeylen,
     pub rate_limiting: SrtRateLimiting,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub passphrase: Option<String>,
     // Required on core nodes
     pub whitelist_cidr_block: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -598,6 +686,17 @@ pub enum SrtKeylen {
     None,
 }
 
+impl fmt::Display for SrtKeylen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Aes128 => write!(f, "AES-128"),
+            Self::Aes192 => write!(f, "AES-192"),
+            Self::Aes256 => write!(f, "AES-256"),
+            Self::None => write!(f, "none"),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub enum SrtRateLimiting {
@@ -612,6 +711,8 @@ pub enum SrtRateLimiting {
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct SrtCallerOutputPort {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub id: String,
     pub physical_port: String,
     pub remote_ip: String,
     pub remote_port: u16,
@@ -619,18 +720,45 @@ pub struct SrtCallerOutputPort {
     pub latency: u16,
     pub pbkeylen: SrtKeylen,
     pub rate_limiting: SrtRateLimiting,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub passphrase: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream_id: Option<String>,
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
 }
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct SrtRendezvousOutputPort {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub id: String,
+    // Not always returned by the API
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub physical_port: String,
     pub local_ip: String,
     pub remote_ip: String,
     pub remote_port: u16, // Both local and remote port
+    pub latency: u16,
+    pub pbkeylen: SrtKeylen,
+    pub rate_limiting: SrtRateLimiting,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub passphrase: Option<String>,
+    pub whitelist_cidr_block: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
 }
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct RtmpOutputPort {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub id: String,
+    // Not always returned by the API
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub physical_port: String,
+    // The URL including the stream key, e.g. rtmp://198.51.100.12/live/key
     pub rtmp_destination_address: String,
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
 }
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -642,14 +770,29 @@ pub enum ZixiOutputPort {
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ZixiPullOutputPort {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub id: String,
+    // Not always returned by the API
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub physical_port: String,
     pub stream_id: String,
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
 }
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ZixiPushOutputPort {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub id: String,
+    // Not always returned by the API
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub physical_port: String,
     pub stream_id: String,
     pub link_set_1: Vec<ZixiLink>, // ZixiLinkSet: length 1, 2 or 3
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub link_set_2: Option<Vec<ZixiLink>>, // ZixiLinkSet: length 1, 2 or 3
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
 }
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -660,24 +803,53 @@ pub struct ZixiLink {
 }
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
-pub struct UnixOutputPort {}
+pub struct UnixOutputPort {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub id: String,
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
+}
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct SdiOutputPort {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub id: String,
     pub physical_port: String,
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
 }
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
-pub struct AsiOutputPort {}
+pub struct AsiOutputPort {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub id: String,
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
+}
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
-pub struct MatroxSdiOutputPort {}
+pub struct MatroxSdiOutputPort {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub id: String,
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
+}
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
-pub struct ComprimatoSdiOutputPort {}
+pub struct ComprimatoSdiOutputPort {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub id: String,
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
+}
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
-pub struct ComprimatoNdiOutputPort {}
+pub struct ComprimatoNdiOutputPort {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub id: String,
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
+}
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -775,6 +947,17 @@ pub enum AppliancePortType {
     Ndi,
 }
 
+impl fmt::Display for AppliancePortType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ip => f.pad("IP"),
+            Self::Coax => f.pad("Coax"),
+            Self::Videon => f.pad("Videon"),
+            Self::Ndi => f.pad("Ndi"),
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct PhysicalPortAddress {
@@ -791,7 +974,7 @@ pub struct ApplianceNetwork {
     // pub enabled: bool,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ApplianceInput {
     // pub appliance_id: String,
@@ -801,7 +984,7 @@ pub struct ApplianceInput {
     pub input_admin_status: InputAdminStatus,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ApplianceOutput {
     // pub appliance_id: String,
@@ -818,6 +1001,7 @@ pub struct ApplianceOutput {
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct InputPort {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub id: String,
     pub copies: u8,
     // "internalStreamId": 20485,
@@ -825,13 +1009,13 @@ pub struct InputPort {
     // "appliance": "a2253ca5-78a4-45a5-ae96-d8d4091b49ea",
     // "priority": 0,
     pub mode: String,
-    // "resolution": "1920x1080",
-    // "scanMode": "progressive",
-    // "frameRate": "30",
-    // "timestampResolution": "seconds"
+    // The mode specific settings, e.g. address and port, kept as is so that
+    // the port can be sent back unchanged when updating the input
+    #[serde(flatten)]
+    pub settings: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 #[serde(tag = "mode")]
 pub enum NewInputPort {
@@ -843,20 +1027,21 @@ pub enum NewInputPort {
     Generator(GeneratorInputPort),
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RtpInputPort {
     pub copies: u8,
     pub physical_port: String,
     pub address: String,
     pub port: u16,
+    #[serde(default)]
     pub fec: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub multicast_address: Option<String>,
     pub whitelist_cidr_block: Option<Vec<String>>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UdpInputPort {
     pub copies: u8,
@@ -877,7 +1062,14 @@ pub enum SrtInputPort {
         remote_ip: String,
         remote_port: u16,
         latency: u16,
+        pbkeylen: SrtKeylen,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        passphrase: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        stream_id: Option<String>,
+        #[serde(default)]
         reduced_bitrate_detection: bool,
+        #[serde(default)]
         unrecovered_packets_detection: bool,
     },
     #[serde(rename_all = "camelCase")]
@@ -886,14 +1078,34 @@ pub enum SrtInputPort {
         latency: u16,
         local_ip: String,
         local_port: u16,
+        pbkeylen: SrtKeylen,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        passphrase: Option<String>,
+        #[serde(default)]
         reduced_bitrate_detection: bool,
+        #[serde(default)]
+        unrecovered_packets_detection: bool,
+        whitelist_cidr_block: Option<Vec<String>>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Rendezvous {
+        physical_port: String,
+        latency: u16,
+        local_ip: String,
+        remote_ip: String,
+        remote_port: u16, // Both local and remote port
+        pbkeylen: SrtKeylen,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        passphrase: Option<String>,
+        #[serde(default)]
+        reduced_bitrate_detection: bool,
+        #[serde(default)]
         unrecovered_packets_detection: bool,
         whitelist_cidr_block: Option<Vec<String>>,
     },
-    Rendezvous,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RistInputPort {
     pub physical_port: String,
@@ -903,7 +1115,7 @@ pub struct RistInputPort {
     pub whitelist_cidr_block: Option<Vec<String>>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SdiInputPort {
     pub copies: u8,
@@ -911,7 +1123,7 @@ pub struct SdiInputPort {
     pub encoder_settings: SdiEncoderSettings,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SdiEncoderSettings {
     pub video_codec: String,
@@ -920,7 +1132,7 @@ pub struct SdiEncoderSettings {
     pub audio_streams: Vec<SdiEncoderAudioStream>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SdiEncoderAudioStream {
     pub codec: String,
@@ -930,7 +1142,7 @@ pub struct SdiEncoderAudioStream {
     pub kind: String, // enum: stereo | mono
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GeneratorInputPort {
     pub copies: u8,
@@ -938,7 +1150,7 @@ pub struct GeneratorInputPort {
     pub bitrate: GeneratorBitrate,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 #[serde(tag = "type")]
 pub enum GeneratorBitrate {
@@ -946,7 +1158,7 @@ pub enum GeneratorBitrate {
     Cbr(GeneratorBitrateCBR),
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GeneratorBitrateCBR {
     pub bitrate: u64,
@@ -959,6 +1171,9 @@ pub struct OutputRecipientList {
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    // The ID of the owning group
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -978,6 +1193,9 @@ pub struct GroupRecipientList {
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    // The ID of the owning group
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -1003,6 +1221,7 @@ pub struct Group {
 pub struct Port {
     pub id: String,
     pub name: String,
+    pub appliance: LimitedAppliance,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -1022,6 +1241,30 @@ pub struct NewRegion {
     pub external: ExternalRegionMode,
 }
 
+/// A request for a port on one of the appliances of a region
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewPortAllocation {
+    // "output" for the ports of regional outputs
+    pub purpose: String,
+    pub region_id: String,
+    pub input_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AllocatedPort {
+    pub id: String,
+    pub physical_port: AllocatedPhysicalPort,
+    pub port_number: u16,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AllocatedPhysicalPort {
+    pub id: String,
+}
+
 #[derive(Debug, Clone)]
 pub enum ExternalRegionMode {
     Core = 0,
@@ -1029,6 +1272,16 @@ pub enum ExternalRegionMode {
     External = 2,
 }
 
+impl fmt::Display for ExternalRegionMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Core => f.pad("core"),
+            Self::ExternalK8s => f.pad("external kubernetes"),
+            Self::External => f.pad("external"),
+        }
+    }
+}
+
 impl<'de> Deserialize<'de> for ExternalRegionMode {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -1191,6 +1444,15 @@ pub enum Product {
     ConnectIt,
 }
 
+impl fmt::Display for Product {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NimbraEdge => f.pad("Nimbra Edge"),
+            Self::ConnectIt => f.pad("Connect iT"),
+        }
+    }
+}
+
 impl<'de> Deserialize<'de> for Product {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -1300,16 +1562,28 @@ pub struct BackupConf {
     pub external_s3: Option<serde_json::Value>,
 }
 
-#[derive(Serialize)]
-struct EdgeQuery<T: Serialize> {
-    filter: T,
+/// How many ids to look up in one request, to keep the URLs short
+const IDS_PER_REQUEST: usize = 50;
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct ListResp<T> {
+    pub(crate) items: Vec<T>,
+    #[serde(default)]
+    total: Option<usize>,
 }
 
+/// The error returned by every [`EdgeClient`] request
 #[derive(Debug)]
 pub enum EdgeError {
     RequestError(reqwest::Error),
+    /// The client could not be set up, e.g. because of an unreadable CA file
+    Config(String),
     NonSuccessStatus(reqwest::StatusCode, EdgeApiError),
     ServerError(reqwest::StatusCode, EdgeApiError),
+    /// 401 or 403, the token is invalid, expired or lacks permissions
+    Unauthorized(reqwest::StatusCode, EdgeApiError),
+    NotFound(reqwest::StatusCode, EdgeApiError),
+    Conflict(reqwest::StatusCode, EdgeApiError),
     ClientError(reqwest::StatusCode, EdgeApiError),
 }
 
@@ -1321,25 +1595,27 @@ pub enum EdgeApiError {
     ParseError(String),
 }
 
+/// The body of an error response of the Edge API
 #[derive(Deserialize, Debug)]
 pub struct EdgeErrorResp {
-    title: String,
-    detail: EdgeErrorResponseDetail,
+    pub title: String,
+    pub detail: EdgeErrorResponseDetail,
+    /// A URL identifying the kind of error
     #[serde(rename = "type")]
-    kind: String,
+    pub kind: String,
 }
 
 #[derive(Deserialize, Debug)]
 #[serde(untagged)]
-enum EdgeErrorResponseDetail {
+pub enum EdgeErrorResponseDetail {
     Detail(String),
     InvalidParameters(Vec<InvalidParameter>),
 }
 
 #[derive(Deserialize, Debug)]
-struct InvalidParameter {
-    name: String,
-    reason: String,
+pub struct InvalidParameter {
+    pub name: String,
+    pub reason: String,
 }
 
 impl From<Result<EdgeErrorResp, String>> for EdgeApiError {
@@ -1397,10 +1673,14 @@ impl std::fmt::Display for EdgeError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
             Self::RequestError(e) => e.fmt(f),
+            Self::Config(e) => e.fmt(f),
             Self::NonSuccessStatus(s, res) => {
                 write!(f, "Unsuccessful HTTP status ({}): {}", s, res)
             }
             Self::ServerError(s, res) => write!(f, "HTTP server error ({}): {}", s, res),
+            Self::Unauthorized(s, res) => write!(f, "Not authorized ({}): {}", s, res),
+            Self::NotFound(s, res) => write!(f, "Not found ({}): {}", s, res),
+            Self::Conflict(s, res) => write!(f, "Conflict ({}): {}", s, res),
             Self::ClientError(s, res) => write!(f, "HTTP client error ({}): {}", s, res),
         }
     }
@@ -1423,7 +1703,13 @@ impl ResponseExt for Response {
 
             let resp = get_edge_error_detail(content_type, body);
 
-            return Err(if status_code.is_client_error() {
+            return Err(if matches!(status_code.as_u16(), 401 | 403) {
+                EdgeError::Unauthorized(status_code, resp.into())
+            } else if status_code == reqwest::StatusCode::NOT_FOUND {
+                EdgeError::NotFound(status_code, resp.into())
+            } else if status_code == reqwest::StatusCode::CONFLICT {
+                EdgeError::Conflict(status_code, resp.into())
+            } else if status_code.is_client_error() {
                 EdgeError::ClientError(status_code, resp.into())
             } else if status_code.is_server_error() {
                 EdgeError::ServerError(status_code, resp.into())
@@ -1465,37 +1751,46 @@ fn get_edge_error_detail(
 }
 
 impl EdgeClient {
-    pub fn with_url(url: &str) -> Self {
-        let client = reqwest::blocking::Client::builder()
-            .cookie_store(true)
-            .build()
-            .unwrap();
-
-        Self {
-            client,
+    /// Creates an unauthenticated client, call `login` before any other request
+    pub fn with_url(url: &str) -> Result<Self, EdgeError> {
+        Self::with_url_and_options(url, &http::Options::default())
+    }
+
+    /// Creates a client that authenticates with a session cookie from `login`
+    pub fn with_url_and_options(url: &str, options: &http::Options) -> Result<Self, EdgeError> {
+        Ok(Self {
+            client: HttpClient::new(HeaderMap::new(), true, options)?,
             url: url.to_owned(),
-        }
+        })
+    }
+
+    /// Creates a client that authenticates with an API token
+    pub fn with_url_and_token(url: &str, token: &str) -> Result<Self, EdgeError> {
+        Self::with_options(url, token, &http::Options::default())
     }
 
-    pub fn with_url_and_token(url: &str, token: &str) -> Self {
-        use reqwest::header;
-        let mut headers = header::HeaderMap::new();
+    /// Creates a client with the timeouts, retries and TLS settings of a
+    /// context
+    pub fn with_options(
+        url: &str,
+        token: &str,
+        options: &http::Options,
+    ) -> Result<Self, EdgeError> {
+        let mut headers = HeaderMap::new();
         headers.insert(
-            header::HeaderName::from_static("edge-api-token"),
-            header::HeaderValue::from_str(token).expect("Token cannot be used as a HTTP header"),
+            HeaderName::from_static("edge-api-token"),
+            HeaderValue::from_str(token).map_err(|_| {
+                EdgeError::Config("The token cannot be used as a HTTP header".into())
+            })?,
         );
 
-        let client = reqwest::blocking::Client::builder()
-            .default_headers(headers)
-            .build()
-            .unwrap();
-
-        Self {
-            client,
+        Ok(Self {
+            client: HttpClient::new(headers, false, options)?,
             url: url.to_owned(),
-        }
+        })
     }
 
+    /// Logs in with a username and password, the session is kept in a cookie
     pub fn login(&self, username: String, password: String) -> Result<LoginRespUser, EdgeError> {
         let mut login_data = BTreeMap::new();
         login_data.insert("username", username);
@@ -1517,111 +1812,44 @@ impl EdgeClient {
         Ok(res.json::<LoginResp>()?.user)
     }
 
-    pub fn list_inputs(&self) -> Result<Vec<Input>, reqwest::Error> {
-        #[derive(Debug, Deserialize)]
-        struct InputListResp {
-            items: Vec<Input>,
-            // total: u32,
-        }
+    /// Lists every input, fetching the pages concurrently
+    pub fn list_inputs(&self) -> Result<Vec<Input>, EdgeError> {
+        self.query_inputs(Query::new())
+    }
 
-        let mut all_inputs = Vec::new();
-        let pagesize = 100;
-        let mut page = 1;
-
-        loop {
-            let skip = (page - 1) * pagesize;
-            let res = self
-                .client
-                .get(format!(
-                    r#"{}/api/input/?q={{"filter":{{}},"skip":{},"limit":{}}}"#,
-                    self.url, skip, pagesize
-                ))
-                .header("content-type", "application/json")
-                .send()?;
-
-            let resp = res.json::<InputListResp>()?;
-            let items_count = resp.items.len();
-            all_inputs.extend(resp.items);
-
-            if items_count < pagesize as usize {
-                break;
-            }
-            page += 1;
-        }
+    /// Lists the inputs matching `query`, fetching the pages concurrently
+    pub fn query_inputs(&self, query: Query) -> Result<Vec<Input>, EdgeError> {
+        self.list_all("/api/input/", &query)
+    }
 
-        Ok(all_inputs)
+    /// Iterates lazily over the pages of the inputs matching `query`
+    pub fn input_pages(&self, query: Query) -> Pages<'_, Input> {
+        Pages::new(self, "/api/input/", query)
     }
 
-    pub fn get_input(&self, id: &str) -> Result<Input, reqwest::Error> {
+    /// Gets the input with `id`
+    pub fn get_input(&self, id: &str) -> Result<Input, EdgeError> {
         let res = self
             .client
             .get(format!(r#"{}/api/input/{}"#, self.url, id))
             .header("content-type", "application/json")
-            .send()?;
+            .send()?
+            .error_if_not_success()?;
 
-        res.json::<Input>()
+        Ok(res.json::<Input>()?)
     }
 
-    pub fn find_inputs(&self, name: &str) -> Result<Vec<Input>, reqwest::Error> {
-        #[derive(Serialize)]
-        #[serde(rename_all = "camelCase")]
-        struct InputFilter {
-            search_name: String,
-        }
-
-        #[derive(Debug, Deserialize)]
-        struct InputListResp {
-            items: Vec<Input>,
-            // total: u32,
-        }
-
-        let query = EdgeQuery {
-            filter: InputFilter {
-                search_name: name.to_owned(),
-            },
-        };
-        let query = serde_json::to_string(&query).expect("Failed to serialize filter as JSON");
-
-        let res = self
-            .client
-            .get(format!(r#"{}/api/input/?q={}"#, self.url, query))
-            .header("content-type", "application/json")
-            .send()?;
-
-        Ok(res.json::<InputListResp>()?.items)
+    /// Finds the inputs whose name contains `name`
+    pub fn find_inputs(&self, name: &str) -> Result<Vec<Input>, EdgeError> {
+        self.query_inputs(Query::new().search_name(name))
     }
 
-    pub fn list_inputs_by_ids(&self, ids: Vec<String>) -> Result<Vec<Input>, reqwest::Error> {
-        if ids.is_empty() {
-            return Ok(Vec::new());
-        }
-
-        #[derive(Serialize)]
-        #[serde(rename_all = "camelCase")]
-        struct InputFilterByIds {
-            ids: Vec<String>,
-        }
-
-        #[derive(Debug, Deserialize)]
-        struct InputListResp {
-            items: Vec<Input>,
-            // total: u32,
-        }
-
-        let query = EdgeQuery {
-            filter: InputFilterByIds { ids },
-        };
-        let query = serde_json::to_string(&query).expect("Failed to serialize filter as JSON");
-
-        let res = self
-            .client
-            .get(format!(r#"{}/api/input/?q={}"#, self.url, query))
-            .header("content-type", "application/json")
-            .send()?;
-
-        Ok(res.json::<InputListResp>()?.items)
+    /// Gets the inputs with the given ids, in concurrent batches
+    pub fn list_inputs_by_ids(&self, ids: Vec<String>) -> Result<Vec<Input>, EdgeError> {
+        self.list_by_ids("/api/input/", &ids)
     }
 
+    /// Creates an input
     pub fn create_input(&self, input: NewInput) -> Result<(), EdgeError> {
         self.client
             .post(format!("{}/api/input/", self.url))
@@ -1632,6 +1860,31 @@ impl EdgeClient {
             .map(|_| ())
     }
 
+    /// Replaces an existing input with `input`
+    pub fn update_input(&self, input: &Input) -> Result<(), EdgeError> {
+        self.client
+            .put(format!("{}/api/input/{}", self.url, input.id))
+            .header("content-type", "application/json")
+            .json(input)
+            .send()?
+            .error_if_not_success()
+            .map(|_| ())
+    }
+
+    /// Enables or disables an input, leaving the rest of it untouched
+    pub fn set_input_admin_status(
+        &self,
+        id: &str,
+        admin_status: InputAdminStatus,
+    ) -> Result<(), EdgeError> {
+        self.set_field(
+            format!("{}/api/input/{}", self.url, id),
+            "adminStatus",
+            admin_status,
+        )
+    }
+
+    /// Deletes the input with `id`
     pub fn delete_input(&self, id: &str) -> Result<(), EdgeError> {
         self.client
             .delete(format!("{}/api/input/{}", self.url, id))
@@ -1640,102 +1893,44 @@ impl EdgeClient {
             .map(|_| ())
     }
 
+    /// Lists every output, fetching the pages concurrently
     pub fn list_outputs(&self) -> Result<Vec<Output>, EdgeError> {
-        #[derive(Debug, Deserialize)]
-        struct OutputListResp {
-            items: Vec<Output>,
-            // total: u32,
-        }
-
-        let mut all_outputs = Vec::new();
-        let pagesize = 100;
-        let mut page = 1;
-
-        loop {
-            let skip = (page - 1) * pagesize;
-            let res = self
-                .client
-                .get(format!(
-                    r#"{}/api/output/?q={{"filter":{{}},"skip":{},"limit":{}}}"#,
-                    self.url, skip, pagesize
-                ))
-                .header("content-type", "application/json")
-                .send()?
-                .error_if_not_success()?;
-
-            let resp = res.json::<OutputListResp>()?;
-            let items_count = resp.items.len();
-            all_outputs.extend(resp.items);
-
-            if items_count < pagesize as usize {
-                break;
-            }
-            page += 1;
-        }
-
-        Ok(all_outputs)
+        self.query_outputs(Query::new())
     }
 
-    pub fn find_outputs(&self, name: &str) -> Result<Vec<Output>, reqwest::Error> {
-        #[derive(Serialize)]
-        #[serde(rename_all = "camelCase")]
-        struct OutputFilter {
-            search_name: String,
-        }
-
-        #[derive(Debug, Deserialize)]
-        struct OutputListResp {
-            items: Vec<Output>,
-            // total: u32,
-        }
+    /// Lists the outputs matching `query`, fetching the pages concurrently
+    pub fn query_outputs(&self, query: Query) -> Result<Vec<Output>, EdgeError> {
+        self.list_all("/api/output/", &query)
+    }
 
-        let query = EdgeQuery {
-            filter: OutputFilter {
-                search_name: name.to_owned(),
-            },
-        };
-        let query = serde_json::to_string(&query).expect("Failed to serialize filter as JSON");
+    /// Iterates lazily over the pages of the outputs matching `query`
+    pub fn output_pages(&self, query: Query) -> Pages<'_, Output> {
+        Pages::new(self, "/api/output/", query)
+    }
 
+    /// Gets the output with `id`
+    pub fn get_output(&self, id: &str) -> Result<Output, EdgeError> {
         let res = self
             .client
-            .get(format!(r#"{}/api/output/?q={}"#, self.url, query))
+            .get(format!(r#"{}/api/output/{}"#, self.url, id))
             .header("content-type", "application/json")
-            .send()?;
+            .send()?
+            .error_if_not_success()?;
 
-        Ok(res.json::<OutputListResp>()?.items)
+        Ok(res.json::<Output>()?)
     }
 
-    pub fn list_outputs_by_ids(&self, ids: Vec<String>) -> Result<Vec<Output>, EdgeError> {
-        if ids.is_empty() {
-            return Ok(Vec::new());
-        }
-
-        #[derive(Serialize)]
-        #[serde(rename_all = "camelCase")]
-        struct OutputFilterByIds {
-            ids: Vec<String>,
-        }
-
-        #[derive(Debug, Deserialize)]
-        struct OutputListResp {
-            items: Vec<Output>,
-            // total: u32,
-        }
-
-        let query = EdgeQuery {
-            filter: OutputFilterByIds { ids },
-        };
-        let query = serde_json::to_string(&query).expect("Failed to serialize filter as JSON");
-
-        let res = self
-            .client
-            .get(format!(r#"{}/api/output/?q={}"#, self.url, query))
-            .header("content-type", "application/json")
-            .send()?;
+    /// Finds the outputs whose name contains `name`
+    pub fn find_outputs(&self, name: &str) -> Result<Vec<Output>, EdgeError> {
+        self.query_outputs(Query::new().search_name(name))
+    }
 
-        Ok(res.json::<OutputListResp>()?.items)
+    /// Gets the outputs with the given ids, in concurrent batches
+    pub fn list_outputs_by_ids(&self, ids: Vec<String>) -> Result<Vec<Output>, EdgeError> {
+        self.list_by_ids("/api/output/", &ids)
     }
 
+    /// Creates an output
     pub fn create_output(&self, output: NewOutput) -> Result<(), EdgeError> {
         self.client
             .post(format!("{}/api/output/", self.url))
@@ -1746,45 +1941,79 @@ impl EdgeClient {
             .map(|_| ())
     }
 
-    pub fn delete_output(&self, id: &str) -> Result<(), EdgeError> {
+    /// Replaces an existing output with `output`
+    pub fn update_output(&self, output: &Output) -> Result<(), EdgeError> {
         self.client
-            .delete(format!("{}/api/output/{}", self.url, id))
+            .put(format!("{}/api/output/{}", self.url, output.id))
+            .header("content-type", "application/json")
+            .json(output)
             .send()?
             .error_if_not_success()
             .map(|_| ())
     }
 
-    pub fn find_groups(&self, name: &str) -> Result<Vec<Group>, EdgeError> {
-        #[derive(Serialize)]
-        #[serde(rename_all = "camelCase")]
-        struct GroupFilter {
-            search_name: String,
-        }
-
-        #[derive(Debug, Deserialize)]
-        struct GroupListResp {
-            items: Vec<Group>,
-            // total: u32,
-        }
+    /// Enables or disables an output, leaving the rest of it untouched
+    pub fn set_output_admin_status(
+        &self,
+        id: &str,
+        admin_status: OutputAdminStatus,
+    ) -> Result<(), EdgeError> {
+        self.set_field(
+            format!("{}/api/output/{}", self.url, id),
+            "adminStatus",
+            admin_status,
+        )
+    }
 
-        let query = EdgeQuery {
-            filter: GroupFilter {
-                search_name: name.to_owned(),
-            },
-        };
-        let query = serde_json::to_string(&query).expect("Failed to serialize filter as JSON");
+    /// Switches an output to the input with ID `input`, leaving the rest of it
+    /// untouched
+    pub fn set_output_input(&self, id: &str, input: &str) -> Result<(), EdgeError> {
+        self.set_field(format!("{}/api/output/{}", self.url, id), "input", input)
+    }
 
-        let res = self
+    // Changes only `field` of the object at `url` and sends back everything
+    // else as the server returned it, including the fields that edgectl does
+    // not model
+    fn set_field(&self, url: String, field: &str, value: impl Serialize) -> Result<(), EdgeError> {
+        let mut object = self
             .client
-            .get(format!(r#"{}/api/group/?q={}"#, self.url, query))
+            .get(&url)
             .header("content-type", "application/json")
             .send()?
-            .error_if_not_success()?;
+            .error_if_not_success()?
+            .json::<serde_json::Value>()?;
+        if let serde_json::Value::Object(fields) = &mut object {
+            fields.insert(
+                field.to_owned(),
+                serde_json::to_value(value).expect("Failed to serialize field"),
+            );
+        }
 
-        Ok(res.json::<GroupListResp>()?.items)
+        self.client
+            .put(&url)
+            .header("content-type", "application/json")
+            .json(&object)
+            .send()?
+            .error_if_not_success()
+            .map(|_| ())
+    }
+
+    /// Deletes the output with `id`
+    pub fn delete_output(&self, id: &str) -> Result<(), EdgeError> {
+        self.client
+            .delete(format!("{}/api/output/{}", self.url, id))
+            .send()?
+            .error_if_not_success()
+            .map(|_| ())
+    }
+
+    /// Finds the groups whose name contains `name`
+    pub fn find_groups(&self, name: &str) -> Result<Vec<Group>, EdgeError> {
+        self.find("/api/group/", name)
     }
 
-    pub fn list_groups(&self) -> Result<Vec<Group>, reqwest::Error> {
+    /// Lists all groups
+    pub fn list_groups(&self) -> Result<Vec<Group>, EdgeError> {
         #[derive(Debug, Deserialize)]
         struct GroupListResp {
             items: Vec<Group>,
@@ -1795,27 +2024,32 @@ impl EdgeClient {
             .client
             .get(format!(r#"{}/api/group/"#, self.url,))
             .header("content-type", "application/json")
-            .send()?;
+            .send()?
+            .error_if_not_success()?;
 
         Ok(res.json::<GroupListResp>()?.items)
     }
 
-    pub fn get_group(&self, id: &str) -> Result<Group, reqwest::Error> {
+    /// Gets the group with `id`
+    pub fn get_group(&self, id: &str) -> Result<Group, EdgeError> {
         let res = self
             .client
             .get(format!(r#"{}/api/group/{}"#, self.url, id))
             .header("content-type", "application/json")
-            .send()?;
+            .send()?
+            .error_if_not_success()?;
 
-        res.json::<Group>()
+        Ok(res.json::<Group>()?)
     }
 
-    pub fn get_group_core_secret(&self, id: &str) -> Result<String, reqwest::Error> {
+    /// Gets the secret used by the appliances of a group to register
+    pub fn get_group_core_secret(&self, id: &str) -> Result<String, EdgeError> {
         let res = self
             .client
             .get(format!(r#"{}/api/group/{}/core-secret"#, self.url, id))
             .header("content-type", "application/json")
-            .send()?;
+            .send()?
+            .error_if_not_success()?;
 
         #[derive(Deserialize)]
         struct SecretResp {
@@ -1825,6 +2059,7 @@ impl EdgeClient {
         Ok(res.json::<SecretResp>()?.secret)
     }
 
+    /// Creates a group
     pub fn create_group(&self, group: NewGroup) -> Result<Group, EdgeError> {
         let res = self
             .client
@@ -1837,6 +2072,7 @@ impl EdgeClient {
         Ok(res.json::<Group>()?)
     }
 
+    /// Deletes the group with `id`
     pub fn delete_group(&self, id: &str) -> Result<(), EdgeError> {
         self.client
             .delete(format!("{}/api/group/{}", self.url, id))
@@ -1845,47 +2081,25 @@ impl EdgeClient {
             .map(|_| ())
     }
 
-    pub fn get_port(&self, id: &str) -> Result<Port, reqwest::Error> {
+    /// Gets the appliance port with `id`
+    pub fn get_port(&self, id: &str) -> Result<Port, EdgeError> {
         let res = self
             .client
             .get(format!(r#"{}/api/port/{}"#, self.url, id))
             .header("content-type", "application/json")
-            .send()?;
+            .send()?
+            .error_if_not_success()?;
 
-        res.json::<Port>()
+        Ok(res.json::<Port>()?)
     }
 
-    pub fn list_ports_by_ids(&self, ids: Vec<String>) -> Result<Vec<Port>, reqwest::Error> {
-        if ids.is_empty() {
-            return Ok(Vec::new());
-        }
-
-        #[derive(Serialize)]
-        #[serde(rename_all = "camelCase")]
-        struct PortFilterByIds {
-            ids: Vec<String>,
-        }
-
-        #[derive(Debug, Deserialize)]
-        struct PortListResp {
-            items: Vec<Port>,
-        }
-
-        let query = EdgeQuery {
-            filter: PortFilterByIds { ids },
-        };
-        let query = serde_json::to_string(&query).expect("Failed to serialize filter as JSON");
-
-        let res = self
-            .client
-            .get(format!(r#"{}/api/port/?q={}"#, self.url, query))
-            .header("content-type", "application/json")
-            .send()?;
-
-        Ok(res.json::<PortListResp>()?.items)
+    /// Gets the appliance ports with the given ids, in concurrent batches
+    pub fn list_ports_by_ids(&self, ids: Vec<String>) -> Result<Vec<Port>, EdgeError> {
+        self.list_by_ids("/api/port/", &ids)
     }
 
-    pub fn list_appliances(&self) -> Result<Vec<Appliance>, reqwest::Error> {
+    /// Lists all appliances
+    pub fn list_appliances(&self) -> Result<Vec<Appliance>, EdgeError> {
         #[derive(Debug, Deserialize)]
         struct ApplianceListResponse {
             items: Vec<Appliance>,
@@ -1896,40 +2110,18 @@ impl EdgeClient {
             .client
             .get(format!(r#"{}/api/appliance/"#, self.url,))
             .header("content-type", "application/json")
-            .send()?;
+            .send()?
+            .error_if_not_success()?;
 
         Ok(res.json::<ApplianceListResponse>()?.items)
     }
 
-    pub fn find_appliances(&self, name: &str) -> Result<Vec<Appliance>, reqwest::Error> {
-        #[derive(Serialize)]
-        #[serde(rename_all = "camelCase")]
-        struct ApplianceFilter {
-            search_name: String,
-        }
-
-        #[derive(Debug, Deserialize)]
-        struct ApplianceListResp {
-            items: Vec<Appliance>,
-            // total: u32,
-        }
-
-        let query = EdgeQuery {
-            filter: ApplianceFilter {
-                search_name: name.to_owned(),
-            },
-        };
-        let query = serde_json::to_string(&query).expect("Failed to serialize filter as JSON");
-
-        let res = self
-            .client
-            .get(format!(r#"{}/api/appliance/?q={}"#, self.url, query))
-            .header("content-type", "application/json")
-            .send()?;
-
-        Ok(res.json::<ApplianceListResp>()?.items)
+    /// Finds the appliances whose name contains `name`
+    pub fn find_appliances(&self, name: &str) -> Result<Vec<Appliance>, EdgeError> {
+        self.find("/api/appliance/", name)
     }
 
+    /// Deletes the appliance with `id`
     pub fn delete_appliance(&self, id: &str) -> Result<(), EdgeError> {
         self.client
             .delete(format!("{}/api/appliance/{}", self.url, id))
@@ -1938,6 +2130,7 @@ impl EdgeClient {
             .map(|_| ())
     }
 
+    /// Lists the inputs running on an appliance
     pub fn get_appliance_inputs(&self, id: &str) -> Result<Vec<ApplianceInput>, EdgeError> {
         #[derive(Debug, Deserialize)]
         struct InputListResp {
@@ -1949,11 +2142,13 @@ impl EdgeClient {
             .client
             .get(format!(r#"{}/api/appliance/{}/inputs"#, self.url, id))
             .header("content-type", "application/json")
-            .send()?;
+            .send()?
+            .error_if_not_success()?;
 
         Ok(res.json::<InputListResp>()?.items)
     }
 
+    /// Lists the outputs running on an appliance
     pub fn get_appliance_outputs(&self, id: &str) -> Result<Vec<ApplianceOutput>, EdgeError> {
         #[derive(Debug, Deserialize)]
         struct OutputListResp {
@@ -1965,21 +2160,25 @@ impl EdgeClient {
             .client
             .get(format!(r#"{}/api/appliance/{}/outputs"#, self.url, id))
             .header("content-type", "application/json")
-            .send()?;
+            .send()?
+            .error_if_not_success()?;
 
         Ok(res.json::<OutputListResp>()?.items)
     }
 
+    /// Gets the configuration the appliance has been given, as raw JSON
     pub fn get_appliance_config(&self, id: &str) -> Result<serde_json::Value, EdgeError> {
         let res = self
             .client
             .get(format!(r#"{}/api/appliance/{}/config"#, self.url, id))
             .header("content-type", "application/json")
-            .send()?;
+            .send()?
+            .error_if_not_success()?;
 
         Ok(res.json()?)
     }
 
+    /// Restarts the appliance with `id`
     pub fn restart_appliance(&self, id: &str) -> Result<(), EdgeError> {
         self.client
             .post(format!("{}/api/appliance/{}/restart", self.url, id))
@@ -1988,6 +2187,7 @@ impl EdgeClient {
             .map(|_| ())
     }
 
+    /// Lists all regions
     pub fn list_regions(&self) -> Result<Vec<Region>, EdgeError> {
         #[derive(Debug, Deserialize)]
         struct RegionListResp {
@@ -1999,40 +2199,18 @@ impl EdgeClient {
             .client
             .get(format!(r#"{}/api/region/"#, self.url))
             .header("content-type", "application/json")
-            .send()?;
+            .send()?
+            .error_if_not_success()?;
 
         Ok(res.json::<RegionListResp>()?.items)
     }
 
-    pub fn find_region(&self, name: &str) -> Result<Vec<Region>, reqwest::Error> {
-        #[derive(Serialize)]
-        #[serde(rename_all = "camelCase")]
-        struct RegionFilter {
-            search_name: String,
-        }
-
-        #[derive(Debug, Deserialize)]
-        struct RegionListResp {
-            items: Vec<Region>,
-            // total: u32,
-        }
-
-        let query = EdgeQuery {
-            filter: RegionFilter {
-                search_name: name.to_owned(),
-            },
-        };
-        let query = serde_json::to_string(&query).expect("Failed to serialize filter as JSON");
-
-        let res = self
-            .client
-            .get(format!(r#"{}/api/region/?q={}"#, self.url, query))
-            .header("content-type", "application/json")
-            .send()?;
-
-        Ok(res.json::<RegionListResp>()?.items)
+    /// Finds the regions whose name contains `name`
+    pub fn find_region(&self, name: &str) -> Result<Vec<Region>, EdgeError> {
+        self.find("/api/region/", name)
     }
 
+    /// Creates a region
     pub fn create_region(&self, region: NewRegion) -> Result<(), EdgeError> {
         self.client
             .post(format!("{}/api/region/", self.url))
@@ -2043,6 +2221,20 @@ impl EdgeClient {
             .map(|_| ())
     }
 
+    /// Allocates a port in a region, for a regional output
+    pub fn allocate_port(&self, allocation: NewPortAllocation) -> Result<AllocatedPort, EdgeError> {
+        let res = self
+            .client
+            .post(format!("{}/api/allocatePort", self.url))
+            .header("content-type", "application/json")
+            .json(&allocation)
+            .send()?
+            .error_if_not_success()?;
+
+        Ok(res.json::<AllocatedPort>()?)
+    }
+
+    /// Deletes the region with `id`
     pub fn delete_region(&self, id: &str) -> Result<(), EdgeError> {
         self.client
             .delete(format!("{}/api/region/{}", self.url, id))
@@ -2051,6 +2243,7 @@ impl EdgeClient {
             .map(|_| ())
     }
 
+    /// Lists the kubernetes nodes of the installation
     pub fn list_kubernetes_nodes(&self) -> Result<Vec<KubernetesNode>, EdgeError> {
         #[derive(Debug, Deserialize)]
         struct NodeListResp {
@@ -2062,11 +2255,13 @@ impl EdgeClient {
             .client
             .get(format!(r#"{}/api/k8s/node/"#, self.url))
             .header("content-type", "application/json")
-            .send()?;
+            .send()?
+            .error_if_not_success()?;
 
         Ok(res.json::<NodeListResp>()?.items)
     }
 
+    /// Lists all tunnels
     pub fn list_tunnels(&self) -> Result<Vec<Tunnel>, EdgeError> {
         #[derive(Debug, Deserialize)]
         struct TunnelListResp {
@@ -2078,31 +2273,37 @@ impl EdgeClient {
             .client
             .get(format!(r#"{}/api/tunnel/"#, self.url))
             .header("content-type", "application/json")
-            .send()?;
+            .send()?
+            .error_if_not_success()?;
 
         Ok(res.json::<TunnelListResp>()?.items)
     }
 
+    /// Gets the version and build of the installation
     pub fn get_build_info(&self) -> Result<BuildInfo, EdgeError> {
         let res = self
             .client
             .get(format!(r#"{}/api/build-info"#, self.url))
             .header("content-type", "application/json")
-            .send()?;
+            .send()?
+            .error_if_not_success()?;
 
         Ok(res.json::<BuildInfo>()?)
     }
 
+    /// Gets the settings of the installation
     pub fn global_settings(&self) -> Result<GlobalSettings, EdgeError> {
         let res = self
             .client
             .get(format!(r#"{}/api/globalsettings"#, self.url))
             .header("content-type", "application/json")
-            .send()?;
+            .send()?
+            .error_if_not_success()?;
 
         Ok(res.json::<GlobalSettings>()?)
     }
 
+    /// Replaces the settings of the installation
     pub fn set_global_settings(&self, settings: GlobalSettings) -> Result<(), EdgeError> {
         self.client
             .put(format!("{}/api/globalsettings", self.url))
@@ -2113,6 +2314,7 @@ impl EdgeClient {
             .map(|_| ())
     }
 
+    /// Lists all output recipient lists
     pub fn list_output_recipient_lists(&self) -> Result<Vec<OutputRecipientList>, EdgeError> {
         #[derive(Debug, Deserialize)]
         struct OutputRecipientListResp {
@@ -2129,6 +2331,7 @@ impl EdgeClient {
         Ok(res.json::<OutputRecipientListResp>()?.items)
     }
 
+    /// Creates an output recipient list
     pub fn create_output_recipient_list(
         &self,
         list: NewOutputRecipientList,
@@ -2144,6 +2347,7 @@ impl EdgeClient {
         Ok(res.json::<OutputRecipientList>()?)
     }
 
+    /// Deletes the output recipient list with `id`
     pub fn delete_output_recipient_list(&self, id: &str) -> Result<(), EdgeError> {
         self.client
             .delete(format!("{}/api/outputRecipientList/{}", self.url, id))
@@ -2152,6 +2356,7 @@ impl EdgeClient {
             .map(|_| ())
     }
 
+    /// Lists the outputs in an output recipient list
     pub fn get_output_list_members(&self, id: &str) -> Result<Vec<Output>, EdgeError> {
         #[derive(Debug, Deserialize)]
         struct OutputListMembersResp {
@@ -2171,6 +2376,7 @@ impl EdgeClient {
         Ok(res.json::<OutputListMembersResp>()?.items)
     }
 
+    /// Adds outputs to a recipient list
     pub fn add_output_to_list(
         &self,
         list_id: &str,
@@ -2198,6 +2404,7 @@ impl EdgeClient {
             .map(|_| ())
     }
 
+    /// Removes outputs from a recipient list
     pub fn remove_output_from_list(
         &self,
         list_id: &str,
@@ -2225,41 +2432,15 @@ impl EdgeClient {
             .map(|_| ())
     }
 
+    /// Finds the output recipient lists whose name contains `name`
     pub fn find_output_recipient_lists(
         &self,
         name: &str,
     ) -> Result<Vec<OutputRecipientList>, EdgeError> {
-        #[derive(Serialize)]
-        #[serde(rename_all = "camelCase")]
-        struct OutputRecipientListFilter {
-            search_name: String,
-        }
-
-        #[derive(Debug, Deserialize)]
-        struct OutputRecipientListResp {
-            items: Vec<OutputRecipientList>,
-        }
-
-        let query = EdgeQuery {
-            filter: OutputRecipientListFilter {
-                search_name: name.to_owned(),
-            },
-        };
-        let query = serde_json::to_string(&query).expect("Failed to serialize filter as JSON");
-
-        let res = self
-            .client
-            .get(format!(
-                r#"{}/api/outputRecipientList/?q={}"#,
-                self.url, query
-            ))
-            .header("content-type", "application/json")
-            .send()?
-            .error_if_not_success()?;
-
-        Ok(res.json::<OutputRecipientListResp>()?.items)
+        self.find("/api/outputRecipientList/", name)
     }
 
+    /// Lists all group recipient lists
     pub fn list_group_recipient_lists(&self) -> Result<Vec<GroupRecipientList>, EdgeError> {
         #[derive(Debug, Deserialize)]
         struct GroupRecipientListResp {
@@ -2270,11 +2451,13 @@ impl EdgeClient {
             .client
             .get(format!("{}/api/groupRecipientList/", self.url))
             .header("content-type", "application/json")
-            .send()?;
+            .send()?
+            .error_if_not_success()?;
 
         Ok(res.json::<GroupRecipientListResp>()?.items)
     }
 
+    /// Creates a group recipient list
     pub fn create_group_recipient_list(
         &self,
         list: NewGroupRecipientList,
@@ -2290,6 +2473,7 @@ impl EdgeClient {
         Ok(res.json::<GroupRecipientList>()?)
     }
 
+    /// Deletes the group recipient list with `id`
     pub fn delete_group_recipient_list(&self, id: &str) -> Result<(), EdgeError> {
         self.client
             .delete(format!("{}/api/groupRecipientList/{}", self.url, id))
@@ -2298,6 +2482,7 @@ impl EdgeClient {
             .map(|_| ())
     }
 
+    /// Lists the groups in a group recipient list
     pub fn get_group_list_members(&self, id: &str) -> Result<Vec<Group>, EdgeError> {
         #[derive(Debug, Deserialize)]
         struct GroupListMembersResp {
@@ -2308,11 +2493,13 @@ impl EdgeClient {
             .client
             .get(format!("{}/api/groupRecipientList/{}/groups", self.url, id))
             .header("content-type", "application/json")
-            .send()?;
+            .send()?
+            .error_if_not_success()?;
 
         Ok(res.json::<GroupListMembersResp>()?.items)
     }
 
+    /// Adds groups to a recipient list
     pub fn add_group_to_list(
         &self,
         list_id: &str,
@@ -2340,6 +2527,7 @@ impl EdgeClient {
             .map(|_| ())
     }
 
+    /// Removes groups from a recipient list
     pub fn remove_group_from_list(
         &self,
         list_id: &str,
@@ -2367,146 +2555,52 @@ impl EdgeClient {
             .map(|_| ())
     }
 
+    /// Finds the group recipient lists whose name contains `name`
     pub fn find_group_recipient_lists(
         &self,
         name: &str,
     ) -> Result<Vec<GroupRecipientList>, EdgeError> {
-        #[derive(Serialize)]
-        #[serde(rename_all = "camelCase")]
-        struct GroupRecipientListFilter {
-            search_name: String,
-        }
-
-        #[derive(Debug, Deserialize)]
-        struct GroupRecipientListResp {
-            items: Vec<GroupRecipientList>,
-        }
-
-        let query = EdgeQuery {
-            filter: GroupRecipientListFilter {
-                search_name: name.to_owned(),
-            },
-        };
-        let query = serde_json::to_string(&query).expect("Failed to serialize filter as JSON");
-
-        let res = self
-            .client
-            .get(format!(
-                r#"{}/api/groupRecipientList/?q={}"#,
-                self.url, query
-            ))
-            .header("content-type", "application/json")
-            .send()?
-            .error_if_not_success()?;
-
-        Ok(res.json::<GroupRecipientListResp>()?.items)
+        self.find("/api/groupRecipientList/", name)
     }
 
+    /// Lists the active alarms, fetching the pages concurrently
     pub fn list_alarms(&self) -> Result<Vec<AlarmWithImpact>, EdgeError> {
-        #[derive(Debug, Deserialize)]
-        struct AlarmListResp {
-            items: Vec<AlarmWithImpact>,
-        }
+        self.list_all("/api/alarm/", &Query::new())
+    }
 
-        let mut all_alarms = Vec::new();
-        let pagesize = 100;
-        let mut page = 1;
-
-        loop {
-            let skip = (page - 1) * pagesize;
-            let res = self
-                .client
-                .get(format!(
-                    r#"{}/api/alarm/?q={{"filter":{{}},"skip":{},"limit":{}}}"#,
-                    self.url, skip, pagesize
-                ))
-                .header("content-type", "application/json")
-                .send()?
-                .error_if_not_success()?;
-
-            let resp = res.json::<AlarmListResp>()?;
-            let items_count = resp.items.len();
-            all_alarms.extend(resp.items);
-
-            if items_count < pagesize {
-                break;
-            }
-            page += 1;
-        }
+    /// Lists the active alarms matching `query`, fetching the pages concurrently
+    pub fn query_alarms(&self, query: Query) -> Result<Vec<AlarmWithImpact>, EdgeError> {
+        self.list_all("/api/alarm/", &query)
+    }
 
-        Ok(all_alarms)
+    /// Iterates lazily over the pages of the active alarms matching `query`
+    pub fn alarm_pages(&self, query: Query) -> Pages<'_, AlarmWithImpact> {
+        Pages::new(self, "/api/alarm/", query)
     }
 
+    /// Lists at most `limit` alarms from the alarm log, optionally within a date range
     pub fn list_alarm_history(
         &self,
         limit: usize,
         from_date: Option<String>,
         to_date: Option<String>,
     ) -> Result<Vec<Alarm>, EdgeError> {
-        #[derive(Debug, Deserialize)]
-        struct AlarmLogResp {
-            items: Vec<Alarm>,
-        }
-
-        #[derive(Serialize)]
-        #[serde(rename_all = "camelCase")]
-        struct AlarmLogFilter {
-            #[serde(skip_serializing_if = "Option::is_none")]
-            from_date: Option<String>,
-            #[serde(skip_serializing_if = "Option::is_none")]
-            to_date: Option<String>,
-        }
-
-        #[derive(Serialize)]
-        struct AlarmLogQuery {
-            filter: AlarmLogFilter,
-            skip: usize,
-            limit: usize,
+        let mut query = Query::new().limit(limit);
+        if let Some(from_date) = from_date {
+            query = query.from_date(from_date);
         }
-
-        let mut all_alarms = Vec::new();
-        let pagesize = 100;
-        let mut page = 1;
-
-        loop {
-            let skip = (page - 1) * pagesize;
-            let remaining = limit.saturating_sub(all_alarms.len());
-            if remaining == 0 {
-                break;
-            }
-
-            let fetch_size = pagesize.min(remaining);
-
-            let query = AlarmLogQuery {
-                filter: AlarmLogFilter {
-                    from_date: from_date.clone(),
-                    to_date: to_date.clone(),
-                },
-                skip,
-                limit: fetch_size,
-            };
-            let query_str = serde_json::to_string(&query).expect("Failed to serialize query");
-
-            let res = self
-                .client
-                .get(format!("{}/api/alarm-log/?q={}", self.url, query_str))
-                .header("content-type", "application/json")
-                .send()?
-                .error_if_not_success()?;
-
-            let resp = res.json::<AlarmLogResp>()?;
-            let items_count = resp.items.len();
-            all_alarms.extend(resp.items);
-
-            if items_count < fetch_size {
-                break;
-            }
-            page += 1;
+        if let Some(to_date) = to_date {
+            query = query.to_date(to_date);
         }
+        self.alarm_history_pages(query).items().collect()
+    }
 
-        Ok(all_alarms)
+    /// Iterates lazily over the pages of the alarm log matching `query`
+    pub fn alarm_history_pages(&self, query: Query) -> Pages<'_, Alarm> {
+        Pages::new(self, "/api/alarm-log/", query)
     }
 
+    /// Clears the alarm with `alarm_id`
     pub fn clear_alarm(&self, alarm_id: &str) -> Result<(), EdgeError> {
         #[derive(Serialize)]
         struct ClearAlarmRequest {
@@ -2526,6 +2620,7 @@ impl EdgeClient {
         Ok(())
     }
 
+    /// Creates an API token, the secret is only returned here
     pub fn create_api_token(&self, init: ApiTokenInit) -> Result<ApiToken, EdgeError> {
         let res = self
             .client
@@ -2539,6 +2634,7 @@ impl EdgeClient {
         res.json::<ApiToken>().map_err(EdgeError::RequestError)
     }
 
+    /// Lists all API tokens
     pub fn list_api_tokens(&self) -> Result<Vec<ApiToken>, EdgeError> {
         #[derive(Debug, Deserialize)]
         struct ApiTokenListResp {
@@ -2558,6 +2654,7 @@ impl EdgeClient {
             .map_err(EdgeError::RequestError)
     }
 
+    /// Deletes the API token named `name`
     pub fn delete_api_token(&self, name: &str) -> Result<(), EdgeError> {
         self.client
             .delete(format!("{}/api/api-token/{}", self.url, name))
@@ -2568,6 +2665,99 @@ impl EdgeClient {
             .map(|_| ())
     }
 
+    /// Gets one page of objects from a list endpoint such as `/api/input/`
+    pub(crate) fn list_page<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &Query,
+    ) -> Result<ListResp<T>, EdgeError> {
+        let res = self
+            .client
+            .get(format!("{}{}?{}", self.url, path, query.to_param()))
+            .header("content-type", "application/json")
+            .send()?
+            .error_if_not_success()?;
+
+        Ok(res.json::<ListResp<T>>()?)
+    }
+
+    /// Gets the objects matching `query` from a list endpoint. The first page
+    /// tells the total, the remaining pages are then fetched concurrently.
+    fn list_all<T: DeserializeOwned + Send>(
+        &self,
+        path: &str,
+        query: &Query,
+    ) -> Result<Vec<T>, EdgeError> {
+        let end = query.end();
+        let page_size = |skip: usize| end.map_or(PAGE_SIZE, |end| PAGE_SIZE.min(end - skip));
+        let mut skip = query.first();
+        if end.is_some_and(|end| end <= skip) {
+            return Ok(Vec::new());
+        }
+
+        let first = self.list_page::<T>(path, &query.page(skip, page_size(skip)))?;
+        let mut full = first.items.len() == page_size(skip);
+        let mut items = first.items;
+        skip += PAGE_SIZE;
+
+        if let (Some(total), true) = (first.total, full) {
+            let total = end.map_or(total, |end| end.min(total));
+            let skips = (skip..total).step_by(PAGE_SIZE).collect::<Vec<_>>();
+            let pages = parallel::map(&skips, |&skip| {
+                self.list_page::<T>(path, &query.page(skip, page_size(skip)))
+                    .map(|page| (page_size(skip), page.items))
+            });
+            for page in pages {
+                let (size, page) = page?;
+                full = page.len() == size;
+                items.extend(page);
+            }
+            skip += skips.len() * PAGE_SIZE;
+        }
+
+        // Objects created while listing end up past the total, and older
+        // versions of the API do not return a total at all
+        while full && end.is_none_or(|end| skip < end) {
+            let page = self.list_page::<T>(path, &query.page(skip, page_size(skip)))?;
+            full = page.items.len() == page_size(skip);
+            items.extend(page.items);
+            skip += PAGE_SIZE;
+        }
+
+        Ok(items)
+    }
+
+    /// Gets all objects whose name contains `name`. Short names match a lot
+    /// of objects, so the one with exactly that name may be on any page.
+    fn find<T: DeserializeOwned + Send>(
+        &self,
+        path: &str,
+        name: &str,
+    ) -> Result<Vec<T>, EdgeError> {
+        self.list_all(path, &Query::new().search_name(name))
+    }
+
+    /// Gets the objects with the given ids from a list endpoint, in batches
+    /// that are fetched concurrently
+    fn list_by_ids<T: DeserializeOwned + Send>(
+        &self,
+        path: &str,
+        ids: &[String],
+    ) -> Result<Vec<T>, EdgeError> {
+        let batches = ids.chunks(IDS_PER_REQUEST).collect::<Vec<_>>();
+        let pages = parallel::map(&batches, |ids| {
+            // Without a limit the server only returns its default page size
+            self.list_page::<T>(path, &Query::new().ids(ids.iter()).limit(ids.len()))
+                .map(|page| page.items)
+        });
+
+        let mut items = Vec::new();
+        for page in pages {
+            items.extend(page?);
+        }
+        Ok(items)
+    }
+
     /// Fetch thumbnail image for an input
     /// Returns JPEG bytes on success, or None if thumbnail doesn't exist
     pub fn fetch_thumbnail(&self, path: &str) -> Option<Vec<u8>> {
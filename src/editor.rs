@@ -0,0 +1,48 @@
+use std::env;
+use std::fs;
+use std::process::Command;
+
+use anyhow::{anyhow, Context};
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Opens `value` as YAML in the editor from `$VISUAL` or `$EDITOR` and parses
+/// the result. Returns `None` if the file was left unchanged.
+pub(crate) fn edit<T: Serialize + DeserializeOwned>(value: &T) -> anyhow::Result<Option<T>> {
+    let original = serde_saphyr::to_string(value).context("Failed to serialize")?;
+    let path = env::temp_dir().join(format!("edgectl-{}.yaml", uuid::Uuid::new_v4()));
+    fs::write(&path, &original).with_context(|| format!("Failed to write {}", path.display()))?;
+
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .unwrap_or_else(|_| {
+            if cfg!(windows) {
+                "notepad".to_owned()
+            } else {
+                "vi".to_owned()
+            }
+        });
+    // The editor may come with arguments, e.g. `code --wait`
+    let mut words = editor.split_whitespace();
+    let program = words.next().ok_or_else(|| anyhow!("The editor is empty"))?;
+    let status = Command::new(program)
+        .args(words)
+        .arg(&path)
+        .status()
+        .with_context(|| format!("Failed to start editor {}", editor))?;
+    if !status.success() {
+        let _ = fs::remove_file(&path);
+        return Err(anyhow!("Editor exited with {}", status));
+    }
+
+    let edited =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    if edited == original {
+        let _ = fs::remove_file(&path);
+        return Ok(None);
+    }
+    // Keep the file on errors so that the changes are not lost
+    let value = serde_saphyr::from_str(&edited)
+        .with_context(|| format!("Invalid YAML, the changes are kept in {}", path.display()))?;
+    let _ = fs::remove_file(&path);
+    Ok(Some(value))
+}
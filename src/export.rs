@@ -0,0 +1,191 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+
+use clap::{Arg, ArgMatches, Command};
+
+use crate::client::new_client;
+use crate::format::{self, Format};
+use crate::manifest::{
+    GroupListManifest, GroupManifest, InputManifest, Manifest, OutputListManifest, OutputManifest,
+    RegionManifest, Resolver,
+};
+
+pub(crate) fn subcommand() -> clap::Command {
+    Command::new("export")
+        .about("Export the configuration as manifests that can be applied again")
+        .arg(
+            Arg::new("directory")
+                .short('d')
+                .long("directory")
+                .required(true)
+                .help("The directory to write the manifests to, one file per object"),
+        )
+        .arg(
+            Arg::new("appliance-configs")
+                .long("appliance-configs")
+                .num_args(0)
+                .help("Also write the configuration of each appliance to <DIRECTORY>/appliance-configs"),
+        )
+}
+
+pub(crate) fn run(args: &ArgMatches) {
+    let dir = PathBuf::from(
+        args.get_one::<String>("directory")
+            .expect("Directory is mandatory"),
+    );
+    if let Err(e) = fs::create_dir_all(&dir) {
+        eprintln!("Failed to create directory {}: {}", dir.display(), e);
+        process::exit(1);
+    }
+
+    let client = new_client();
+    let mut resolver = Resolver::new(&client);
+    let mut writer = Writer {
+        dir: dir.clone(),
+        written: HashSet::new(),
+        failed: false,
+    };
+
+    match client.list_regions() {
+        Ok(regions) => {
+            for region in regions {
+                writer.write(&Manifest::Region(RegionManifest::from_region(&region)));
+            }
+        }
+        Err(e) => writer.fail("regions", e.into()),
+    }
+    match client.list_groups() {
+        Ok(groups) => {
+            for group in groups {
+                writer.write(&Manifest::Group(GroupManifest { name: group.name }));
+            }
+        }
+        Err(e) => writer.fail("groups", e.into()),
+    }
+    match client.list_inputs() {
+        Ok(inputs) => {
+            for input in inputs {
+                match InputManifest::from_input(&input, &mut resolver) {
+                    Ok(m) => writer.write(&Manifest::Input(m)),
+                    Err(e) => writer.fail(&format!("input/{}", input.name), e),
+                }
+            }
+        }
+        Err(e) => writer.fail("inputs", e.into()),
+    }
+    match client.list_outputs() {
+        Ok(outputs) => {
+            for output in outputs {
+                match OutputManifest::from_output(&output, &mut resolver) {
+                    Ok(m) => writer.write(&Manifest::Output(m)),
+                    Err(e) => writer.fail(&format!("output/{}", output.name), e),
+                }
+            }
+        }
+        Err(e) => writer.fail("outputs", e.into()),
+    }
+    match client.list_output_recipient_lists() {
+        Ok(lists) => {
+            for list in lists {
+                match OutputListManifest::from_list(&list, &client, &mut resolver) {
+                    Ok(m) => writer.write(&Manifest::OutputList(m)),
+                    Err(e) => writer.fail(&format!("output-list/{}", list.name), e),
+                }
+            }
+        }
+        Err(e) => writer.fail("output lists", e.into()),
+    }
+    match client.list_group_recipient_lists() {
+        Ok(lists) => {
+            for list in lists {
+                match GroupListManifest::from_list(&list, &client, &mut resolver) {
+                    Ok(m) => writer.write(&Manifest::GroupList(m)),
+                    Err(e) => writer.fail(&format!("group-list/{}", list.name), e),
+                }
+            }
+        }
+        Err(e) => writer.fail("group lists", e.into()),
+    }
+
+    if args.get_flag("appliance-configs") {
+        // Kept in a sub directory so that `apply -f <DIRECTORY>` skips them
+        let config_dir = dir.join("appliance-configs");
+        if let Err(e) = fs::create_dir_all(&config_dir) {
+            eprintln!("Failed to create directory {}: {}", config_dir.display(), e);
+            process::exit(1);
+        }
+        match client.list_appliances() {
+            Ok(appliances) => {
+                for appliance in appliances {
+                    let path = config_dir.join(format!("{}.json", file_name(&appliance.name)));
+                    let config = client
+                        .get_appliance_config(&appliance.id)
+                        .map_err(anyhow::Error::from)
+                        .and_then(|config| Ok(serde_json::to_string_pretty(&config)?))
+                        .and_then(|config| Ok(fs::write(&path, config + "\n")?));
+                    if let Err(e) = config {
+                        writer.fail(&format!("appliance config for {}", appliance.name), e);
+                    }
+                }
+            }
+            Err(e) => writer.fail("appliances", e.into()),
+        }
+    }
+
+    println!(
+        "Exported {} objects to {}",
+        writer.written.len(),
+        dir.display()
+    );
+    if writer.failed {
+        process::exit(1);
+    }
+}
+
+struct Writer {
+    dir: PathBuf,
+    written: HashSet<PathBuf>,
+    failed: bool,
+}
+
+impl Writer {
+    fn write(&mut self, manifest: &Manifest) {
+        let base = format!("{}-{}", manifest.kind(), file_name(manifest.name()));
+        // Names are not unique for every kind of object
+        let mut path = self.dir.join(format!("{}.yaml", base));
+        let mut n = 2;
+        while self.written.contains(&path) {
+            path = self.dir.join(format!("{}-{}.yaml", base, n));
+            n += 1;
+        }
+
+        let result =
+            format::to_string(Format::Yaml, manifest).and_then(|yaml| Ok(fs::write(&path, yaml)?));
+        match result {
+            Ok(()) => {
+                self.written.insert(path);
+            }
+            Err(e) => self.fail(&format!("{}/{}", manifest.kind(), manifest.name()), e),
+        }
+    }
+
+    fn fail(&mut self, what: &str, e: anyhow::Error) {
+        eprintln!("Failed to export {}: {:#}", what, e);
+        self.failed = true;
+    }
+}
+
+// Object names can contain anything, keep file names portable
+fn file_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,132 @@
+use std::process;
+use std::thread;
+
+use clap::ArgMatches;
+use serde::Serialize;
+use tabled::{builder::Builder, settings::Style};
+
+use crate::client::client_for_context;
+use crate::config::Config;
+use crate::edge::EdgeClient;
+use crate::format::{self, Format};
+
+/// Returns the contexts selected with `--all-contexts` or `--contexts`, or
+/// `None` if the command should only run against a single installation
+pub(crate) fn contexts(args: &ArgMatches) -> Option<Vec<String>> {
+    let config = Config::load();
+    let mut names = if args.get_flag("all-contexts") {
+        config.contexts.keys().cloned().collect::<Vec<_>>()
+    } else {
+        args.get_many::<String>("contexts")?.cloned().collect()
+    };
+    names.sort();
+    names.dedup();
+
+    if names.is_empty() {
+        eprintln!("No contexts configured. Run 'edgectl login' to add one.");
+        process::exit(1);
+    }
+    for name in &names {
+        if !config.contexts.contains_key(name) {
+            eprintln!("Context '{}' does not exist", name);
+            process::exit(1);
+        }
+    }
+    Some(names)
+}
+
+/// Runs `f` against every context in parallel. The results are in the same
+/// order as `contexts`.
+pub(crate) fn run<T, F>(contexts: &[String], f: F) -> Vec<(String, anyhow::Result<T>)>
+where
+    T: Send,
+    F: Fn(&EdgeClient) -> anyhow::Result<T> + Sync,
+{
+    thread::scope(|s| {
+        let handles = contexts
+            .iter()
+            .map(|context| {
+                let f = &f;
+                s.spawn(move || f(&client_for_context(context)?))
+            })
+            .collect::<Vec<_>>();
+        contexts
+            .iter()
+            .zip(handles)
+            .map(|(context, handle)| {
+                let result = handle
+                    .join()
+                    .unwrap_or_else(|_| Err(anyhow::anyhow!("Request thread panicked")));
+                (context.to_owned(), result)
+            })
+            .collect()
+    })
+}
+
+/// Prints the failed contexts on stderr and returns the successful results.
+/// The second value is true if any context failed.
+pub(crate) fn partition<T>(results: Vec<(String, anyhow::Result<T>)>) -> (Vec<(String, T)>, bool) {
+    let mut ok = Vec::new();
+    let mut failed = false;
+    for (context, result) in results {
+        match result {
+            Ok(value) => ok.push((context, value)),
+            Err(e) => {
+                eprintln!("{}: {:#}", context, e);
+                failed = true;
+            }
+        }
+    }
+    (ok, failed)
+}
+
+/// Prints the records of every context as one table with a leading CONTEXT
+/// column. Returns false if any context failed.
+pub(crate) fn print_table(
+    header: &[&str],
+    results: Vec<(String, anyhow::Result<Vec<Vec<String>>>)>,
+) -> bool {
+    let (results, failed) = partition(results);
+
+    let mut builder = Builder::default();
+    builder.push_record(std::iter::once("Context").chain(header.iter().copied()));
+    for (context, records) in results {
+        for record in records {
+            builder.push_record(std::iter::once(context.to_owned()).chain(record));
+        }
+    }
+    let mut table = builder.build();
+    table.with(Style::empty());
+    println!("{}", table);
+
+    !failed
+}
+
+/// Prints the objects of every context as one list, with a `context` field
+/// added to each object. Returns false if any context failed.
+pub(crate) fn print_structured<T: Serialize>(
+    format: Format,
+    results: Vec<(String, anyhow::Result<Vec<T>>)>,
+) -> bool {
+    let (results, failed) = partition(results);
+
+    let mut items = Vec::new();
+    for (context, values) in results {
+        for value in values {
+            let mut value = match serde_json::to_value(value) {
+                Ok(value) => value,
+                Err(e) => {
+                    eprintln!("Failed to serialize output: {}", e);
+                    process::exit(1);
+                }
+            };
+            if let Some(object) = value.as_object_mut() {
+                object.insert("context".to_owned(), context.to_owned().into());
+            }
+            items.push(value);
+        }
+    }
+    format::print(format, &items);
+
+    !failed
+}
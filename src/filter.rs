@@ -0,0 +1,48 @@
+use anyhow::{anyhow, Context};
+use clap::{builder::PossibleValuesParser, Arg, ArgAction, ArgMatches};
+
+use crate::edge::EdgeClient;
+use crate::manifest;
+use crate::query::Query;
+
+/// The arguments of `input list` and `output list` that filter on the server
+pub(crate) fn args() -> [Arg; 3] {
+    [
+        Arg::new("admin-status")
+            .long("admin-status")
+            .value_parser(PossibleValuesParser::new(["on", "off"]))
+            .help("Only list enabled or disabled objects"),
+        Arg::new("group")
+            .long("group")
+            .action(ArgAction::Append)
+            .help("Only list objects owned by this group, can be repeated"),
+        Arg::new("appliance")
+            .long("appliance")
+            .action(ArgAction::Append)
+            .help("Only list objects running on this appliance, can be repeated"),
+    ]
+}
+
+/// The query for the filters in `args`, with the groups and appliances
+/// looked up by name
+pub(crate) fn query(client: &EdgeClient, args: &ArgMatches) -> anyhow::Result<Query> {
+    let mut query = Query::new();
+    if let Some(status) = args.get_one::<String>("admin-status") {
+        query = query.admin_status(status == "on");
+    }
+    for name in args.get_many::<String>("group").unwrap_or_default() {
+        let group = manifest::find_group(client, name)?
+            .ok_or_else(|| anyhow!("Group {} not found", name))?;
+        query = query.group(group.id);
+    }
+    for name in args.get_many::<String>("appliance").unwrap_or_default() {
+        let appliance = client
+            .find_appliances(name)
+            .context("Failed to find appliances")?
+            .into_iter()
+            .find(|a| &a.name == name)
+            .ok_or_else(|| anyhow!("Appliance {} not found", name))?;
+        query = query.appliance(appliance.id);
+    }
+    Ok(query)
+}
@@ -0,0 +1,131 @@
+use std::io::{self, Write};
+use std::process;
+
+use clap::{builder::PossibleValuesParser, Arg, ArgMatches};
+use serde::Serialize;
+
+/// Machine readable output formats, available on every list and show command
+/// next to the command specific table formats. The field names are the ones
+/// used by the Edge API so they stay stable between releases.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Format {
+    Json,
+    Yaml,
+}
+
+/// The `-o/--output` argument. The first of `formats` is the default.
+pub(crate) fn arg(formats: &[&'static str]) -> Arg {
+    let mut values = formats.to_vec();
+    values.extend(["json", "yaml"]);
+    Arg::new("output")
+        .long("output")
+        .short('o')
+        .value_parser(PossibleValuesParser::new(values))
+        .default_value(formats[0])
+        .help("Change the output format")
+}
+
+/// Returns the structured format requested with `-o`, or `None` if a table
+/// format was requested
+pub(crate) fn structured(args: &ArgMatches) -> Option<Format> {
+    match args.get_one::<String>("output").map(|s| s.as_str()) {
+        Some("json") => Some(Format::Json),
+        Some("yaml") => Some(Format::Yaml),
+        _ => None,
+    }
+}
+
+pub(crate) fn to_string<T: Serialize>(format: Format, value: &T) -> anyhow::Result<String> {
+    Ok(match format {
+        Format::Json => serde_json::to_string_pretty(value)?,
+        Format::Yaml => serde_saphyr::to_string(value)?,
+    })
+}
+
+pub(crate) fn print<T: Serialize>(format: Format, value: &T) {
+    match to_string(format, value) {
+        Ok(s) => println!("{}", s.trim_end()),
+        Err(e) => {
+            eprintln!("Failed to serialize output: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+/// Prints the objects of a list as they arrive, the same way `print` prints
+/// the whole list, so that long lists show up page by page
+pub(crate) fn print_items<T, E>(
+    format: Format,
+    items: impl IntoIterator<Item = Result<T, E>>,
+) -> anyhow::Result<()>
+where
+    T: Serialize,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    write_items(&mut io::stdout().lock(), format, items)
+}
+
+fn write_items<T, E>(
+    out: &mut impl Write,
+    format: Format,
+    items: impl IntoIterator<Item = Result<T, E>>,
+) -> anyhow::Result<()>
+where
+    T: Serialize,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let mut empty = true;
+    for item in items {
+        let item = item?;
+        match format {
+            // Indented like the elements of a pretty printed array
+            Format::Json => {
+                let json = serde_json::to_string_pretty(&item)?;
+                writeln!(out, "{}", if empty { "[" } else { "," })?;
+                for (i, line) in json.lines().enumerate() {
+                    if i > 0 {
+                        writeln!(out)?;
+                    }
+                    write!(out, "  {}", line)?;
+                }
+            }
+            Format::Yaml => write!(out, "{}", serde_saphyr::to_string(&[item])?)?,
+        }
+        out.flush()?;
+        empty = false;
+    }
+    match (format, empty) {
+        (_, true) => writeln!(out, "{}", to_string(format, &Vec::<T>::new())?.trim_end())?,
+        (Format::Json, false) => writeln!(out, "\n]")?,
+        (Format::Yaml, false) => {}
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn streamed(format: Format, items: &[serde_json::Value]) -> String {
+        let mut out = Vec::new();
+        write_items(&mut out, format, items.iter().map(Ok::<_, std::fmt::Error>)).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn it_streams_lists_like_it_prints_them() {
+        let items = [
+            json!({ "id": "a", "ports": [{ "port": 4000 }] }),
+            json!({ "id": "b", "tags": [] }),
+        ];
+        for format in [Format::Json, Format::Yaml] {
+            for items in [&items[..], &items[..1], &[]] {
+                assert_eq!(
+                    streamed(format, items).trim_end(),
+                    to_string(format, &items).unwrap().trim_end()
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use crate::query::Query;
+
+/// Returns true if `name` contains glob wildcards
+pub(crate) fn is_pattern(name: &str) -> bool {
+    name.contains(['*', '?'])
+}
+
+/// A query for the names that can match `pattern`, searching on the text
+/// before the first wildcard
+pub(crate) fn query(pattern: &str) -> Query {
+    match pattern.split(['*', '?']).next() {
+        Some(prefix) if !prefix.is_empty() => Query::new().search_name(prefix),
+        _ => Query::new(),
+    }
+}
+
+/// Matches `text` against a shell style pattern, where `*` matches any number
+/// of characters and `?` matches exactly one character
+pub(crate) fn matches(pattern: &str, text: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let text = text.chars().collect::<Vec<_>>();
+    let (mut p, mut t) = (0, 0);
+    // Where to resume if the text after the last `*` stops matching
+    let mut backtrack = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star, star_t)) => {
+                    p = star + 1;
+                    t = star_t + 1;
+                    backtrack = Some((star, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_matches_globs() {
+        assert!(matches("studio-*", "studio-1"));
+        assert!(matches("*-backup", "studio-1-backup"));
+        assert!(matches("s?udio*", "studio"));
+        assert!(matches("*", ""));
+        assert!(matches("a*b*c", "aXbYbZc"));
+        assert!(!matches("studio-*", "Studio-1"));
+        assert!(!matches("a*b", "aXbY"));
+        assert!(!matches("?", ""));
+    }
+
+    #[test]
+    fn it_searches_on_the_text_before_the_first_wildcard() {
+        let filter = |pattern| serde_json::to_value(query(pattern)).unwrap()["filter"].clone();
+        assert_eq!(
+            filter("studio-*"),
+            serde_json::json!({ "searchName": "studio-" })
+        );
+        assert_eq!(filter("st?dio*"), serde_json::json!({ "searchName": "st" }));
+        assert_eq!(filter("*-backup"), serde_json::json!({}));
+    }
+}
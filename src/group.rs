@@ -3,19 +3,24 @@ use std::process;
 use clap::{Arg, ArgMatches, Command};
 use tabled::{builder::Builder, settings::Style};
 
-use crate::edge::{new_client, EdgeClient, Group, NewGroup};
+use crate::client::new_client;
+use crate::edge::{EdgeClient, Group, NewGroup};
+use crate::format::{self, Format};
+use crate::OrExit;
 
 pub(crate) fn subcommand() -> clap::Command {
     Command::new("group")
         .about("Manage groups")
         .subcommand_required(true)
-        .subcommand(Command::new("list"))
+        .subcommand(Command::new("list").arg(format::arg(&["short"])))
         .subcommand(
-            Command::new("show").arg(
-                Arg::new("name")
-                    .required(true)
-                    .help("The group name to show details for"),
-            ),
+            Command::new("show")
+                .arg(
+                    Arg::new("name")
+                        .required(true)
+                        .help("The group name to show details for"),
+                )
+                .arg(format::arg(&["text"])),
         )
         .subcommand(
             Command::new("create").arg(Arg::new("name").required(true).help("The group name")),
@@ -30,9 +35,9 @@ pub(crate) fn subcommand() -> clap::Command {
 
 pub(crate) fn run(subcmd: &ArgMatches) {
     match subcmd.subcommand() {
-        Some(("list", _)) | None => {
+        Some(("list", args)) => {
             let client = new_client();
-            list(client)
+            list(client, format::structured(args))
         }
         Some(("show", args)) => {
             let client = new_client();
@@ -40,7 +45,7 @@ pub(crate) fn run(subcmd: &ArgMatches) {
                 .get_one::<String>("name")
                 .map(|s| s.as_str())
                 .expect("Group name is mandatory");
-            show(client, name)
+            show(client, name, format::structured(args))
         }
         Some(("core-secret", args)) => {
             let client = new_client();
@@ -70,8 +75,13 @@ pub(crate) fn run(subcmd: &ArgMatches) {
     }
 }
 
-fn list(client: EdgeClient) {
-    let groups = client.list_groups().expect("Failed to fetch group list");
+fn list(client: EdgeClient, format: Option<Format>) {
+    let groups = client.list_groups().or_exit("Failed to fetch group list");
+
+    if let Some(format) = format {
+        format::print(format, &groups);
+        return;
+    }
 
     let mut builder = Builder::default();
 
@@ -85,12 +95,16 @@ fn list(client: EdgeClient) {
     println!("{}", table)
 }
 
-fn show(client: EdgeClient, name: &str) {
-    let groups = client.find_groups(name).expect("Failed to find groups");
+fn show(client: EdgeClient, name: &str, format: Option<Format>) {
+    let groups = client.find_groups(name).or_exit("Failed to find groups");
     if groups.is_empty() {
         println!("No such group: {}", name);
         process::exit(1);
     }
+    if let Some(format) = format {
+        format::print(format, &groups);
+        return;
+    }
     for group in groups {
         println!("Name:                 {}", group.name);
         println!("ID:                   {}", group.id);
@@ -117,7 +131,7 @@ pub(crate) fn core_secret(client: EdgeClient, name: &str) {
     for group in groups {
         let secret = client
             .get_group_core_secret(&group.id)
-            .expect("Failed to get group secret");
+            .or_exit("Failed to get group secret");
         println!("{}", secret)
     }
 }
@@ -1,13 +1,21 @@
 use clap::{parser::ValuesRef, Arg, ArgMatches, Command};
+use serde::Serialize;
 use tabled::{builder::Builder, settings::Style};
 
-use crate::edge::{new_client, NewGroupRecipientList};
+use crate::client::new_client;
+use crate::edge::{Group, GroupRecipientList, NewGroupRecipientList};
+use crate::format::{self, Format};
+use crate::OrExit;
 
 pub(crate) fn subcommand() -> clap::Command {
     Command::new("group-list")
         .about("Manage group lists")
         .subcommand_required(true)
-        .subcommand(Command::new("list").about("List all group lists"))
+        .subcommand(
+            Command::new("list")
+                .about("List all group lists")
+                .arg(format::arg(&["short"])),
+        )
         .subcommand(
             Command::new("show")
                 .about("Show details of a group list")
@@ -15,7 +23,8 @@ pub(crate) fn subcommand() -> clap::Command {
                     Arg::new("name")
                         .required(true)
                         .help("The name of the group list"),
-                ),
+                )
+                .arg(format::arg(&["text"])),
         )
         .subcommand(
             Command::new("create")
@@ -86,7 +95,7 @@ pub(crate) fn subcommand() -> clap::Command {
 
 pub(crate) fn run(args: &ArgMatches) {
     match args.subcommand() {
-        Some(("list", _)) => list(),
+        Some(("list", sub_args)) => list(format::structured(sub_args)),
         Some(("show", sub_args)) => show(sub_args),
         Some(("create", sub_args)) => create(sub_args),
         Some(("delete", sub_args)) => delete(sub_args),
@@ -99,11 +108,16 @@ pub(crate) fn run(args: &ArgMatches) {
     }
 }
 
-fn list() {
+fn list(format: Option<Format>) {
     let client = new_client();
     let lists = client
         .list_group_recipient_lists()
-        .expect("Failed to list group lists");
+        .or_exit("Failed to list group lists");
+
+    if let Some(format) = format {
+        format::print(format, &lists);
+        return;
+    }
 
     if lists.is_empty() {
         println!("No group lists found");
@@ -129,7 +143,7 @@ fn show(args: &ArgMatches) {
 
     let lists = client
         .find_group_recipient_lists(name)
-        .expect("Failed to find group list");
+        .or_exit("Failed to find group list");
 
     if lists.is_empty() {
         eprintln!("Group list '{}' not found", name);
@@ -140,7 +154,18 @@ fn show(args: &ArgMatches) {
 
     let groups = client
         .get_group_list_members(&list.id)
-        .expect("Failed to get groups in list");
+        .or_exit("Failed to get groups in list");
+
+    if let Some(format) = format::structured(args) {
+        #[derive(Serialize)]
+        struct GroupListDetails<'a> {
+            #[serde(flatten)]
+            list: &'a GroupRecipientList,
+            groups: Vec<Group>,
+        }
+        format::print(format, &GroupListDetails { list, groups });
+        return;
+    }
 
     println!("ID:          {}", list.id);
     println!("Name:        {}", list.name);
@@ -183,7 +208,7 @@ fn create(args: &ArgMatches) {
 
     let groups = client
         .find_groups(&group_name)
-        .expect("Failed to find group");
+        .or_exit("Failed to find group");
 
     if groups.is_empty() {
         eprintln!("Group '{}' not found", group_name);
@@ -201,7 +226,7 @@ fn create(args: &ArgMatches) {
 
     client
         .create_group_recipient_list(new_list)
-        .expect("Failed to create group list");
+        .or_exit("Failed to create group list");
 }
 
 fn delete(args: &ArgMatches) {
@@ -227,7 +252,7 @@ fn delete(args: &ArgMatches) {
 
         client
             .delete_group_recipient_list(&list.id)
-            .expect("Failed to delete group list");
+            .or_exit("Failed to delete group list");
 
         println!("Deleted group list '{}'", list.name);
     }
@@ -240,7 +265,7 @@ fn add_group(args: &ArgMatches) {
 
     let lists = client
         .find_group_recipient_lists(list_name)
-        .expect("Failed to find group list");
+        .or_exit("Failed to find group list");
     if lists.is_empty() {
         eprintln!("Group list '{}' not found", list_name);
         std::process::exit(1);
@@ -251,7 +276,7 @@ fn add_group(args: &ArgMatches) {
 
     client
         .add_group_to_list(&list.id, &list.name, groups)
-        .expect("Failed to add group to list");
+        .or_exit("Failed to add group to list");
 }
 
 fn remove_group(args: &ArgMatches) {
@@ -261,7 +286,7 @@ fn remove_group(args: &ArgMatches) {
 
     let lists = client
         .find_group_recipient_lists(list_name)
-        .expect("Failed to find group list");
+        .or_exit("Failed to find group list");
     if lists.is_empty() {
         eprintln!("Group list '{}' not found", list_name);
         std::process::exit(1);
@@ -272,7 +297,7 @@ fn remove_group(args: &ArgMatches) {
 
     client
         .remove_group_from_list(&list.id, &list.name, groups)
-        .expect("Failed to remove group from list");
+        .or_exit("Failed to remove group from list");
 }
 
 fn get_group_ids_by_names(
@@ -1,9 +1,14 @@
 use std::process;
 
+use anyhow::Context;
 use clap::{ArgMatches, Command};
 use tabled::{builder::Builder, settings::Style};
 
-use crate::edge::{new_client, EdgeClient, OutputAdminStatus, OutputHealthState};
+use crate::client::new_client;
+use crate::edge::{EdgeClient, OutputAdminStatus, OutputHealthState};
+use crate::fanout;
+use crate::input;
+use crate::output;
 
 pub(crate) fn subcommand() -> clap::Command {
     Command::new("health").about("Check health status of inputs and outputs")
@@ -15,85 +20,124 @@ pub(crate) fn run(_subcmd: &ArgMatches) {
     process::exit(exit_code);
 }
 
-fn check_health(client: EdgeClient) -> i32 {
-    let mut exit_code = 0;
+/// `health` against several contexts at once
+pub(crate) fn run_contexts(contexts: &[String]) {
+    let (results, mut failed) = fanout::partition(fanout::run(contexts, unhealthy));
+
+    let mut builder = Builder::default();
+    builder.push_record(["Context", "Kind", "Name", "Health"]);
+    for (context, unhealthy) in &results {
+        for (name, status) in &unhealthy.inputs {
+            builder.push_record([context, "input", name, status]);
+        }
+        for (name, status) in &unhealthy.outputs {
+            builder.push_record([context, "output", name, status]);
+        }
+        failed |= !unhealthy.is_empty();
+    }
+
+    if results.iter().all(|(_, unhealthy)| unhealthy.is_empty()) {
+        if !results.is_empty() {
+            println!("All OK");
+        }
+    } else {
+        let mut table = builder.build();
+        table.with(Style::empty());
+        println!("{}", table);
+        println!("Found inputs/outputs with non-ok status");
+    }
+    process::exit(if failed { 1 } else { 0 });
+}
+
+/// The unhealthy inputs and outputs, as name and health
+pub(crate) struct Unhealthy {
+    pub inputs: Vec<(String, String)>,
+    pub outputs: Vec<(String, String)>,
+}
+
+impl Unhealthy {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.inputs.is_empty() && self.outputs.is_empty()
+    }
+}
+
+pub(crate) fn unhealthy(client: &EdgeClient) -> anyhow::Result<Unhealthy> {
     let mut unhealthy_inputs = Vec::new();
     let mut unhealthy_outputs = Vec::new();
 
     // Check input health
-    match client.list_inputs() {
-        Ok(inputs) => {
-            for input in inputs {
-                if input.health.state != "allOk" {
-                    unhealthy_inputs.push((input.name, format!("{}", input.health)));
-                    exit_code = 1;
-                }
-            }
-        }
-        Err(e) => {
-            eprintln!("Failed to list inputs: {}", e);
-            return 1;
+    let inputs = client.list_inputs().context("Failed to list inputs")?;
+    for input in inputs {
+        if input.health.state != "allOk" {
+            unhealthy_inputs.push((input.name, input::health_fmt(&input.health)));
         }
     }
 
     // Check output health
-    match client.list_outputs() {
-        Ok(outputs) => {
-            for output in outputs {
-                match output.admin_status {
-                    OutputAdminStatus::On => {
-                        if let Some(health) = &output.health {
-                            if !matches!(health.state, OutputHealthState::AllOk) {
-                                let health_msg = if health.title.is_empty() {
-                                    health.state.to_string()
-                                } else {
-                                    format!("{} ({})", health.state, health.title)
-                                };
-                                unhealthy_outputs.push((output.name, health_msg));
-                                exit_code = 1;
-                            }
-                        }
-                    }
-                    OutputAdminStatus::Off => {
-                        // Skip disabled outputs - they're not considered unhealthy
+    let outputs = client.list_outputs().context("Failed to list outputs")?;
+    for output in outputs {
+        match output.admin_status {
+            OutputAdminStatus::On => {
+                if let Some(health) = &output.health {
+                    if !matches!(health.state, OutputHealthState::AllOk) {
+                        let health_msg = if health.title.is_empty() {
+                            output::health_state(&health.state)
+                        } else {
+                            format!("{} ({})", output::health_state(&health.state), health.title)
+                        };
+                        unhealthy_outputs.push((output.name, health_msg));
                     }
                 }
             }
+            OutputAdminStatus::Off => {
+                // Skip disabled outputs - they're not considered unhealthy
+            }
         }
+    }
+
+    Ok(Unhealthy {
+        inputs: unhealthy_inputs,
+        outputs: unhealthy_outputs,
+    })
+}
+
+fn check_health(client: EdgeClient) -> i32 {
+    let unhealthy = match unhealthy(&client) {
+        Ok(unhealthy) => unhealthy,
         Err(e) => {
-            eprintln!("Failed to list outputs: {}", e);
+            eprintln!("{:#}", e);
             return 1;
         }
-    }
+    };
 
     // Display results
-    if !unhealthy_inputs.is_empty() {
+    if !unhealthy.inputs.is_empty() {
         println!("Found unhealthy inputs:");
         let mut builder = Builder::default();
-        for (name, status) in unhealthy_inputs {
-            builder.push_record([format!("  {}", name), status]);
+        for (name, status) in &unhealthy.inputs {
+            builder.push_record([format!("  {}", name), status.to_owned()]);
         }
         let mut table = builder.build();
         table.with(Style::empty());
         println!("{}", table);
     }
 
-    if !unhealthy_outputs.is_empty() {
+    if !unhealthy.outputs.is_empty() {
         println!("Found unhealthy outputs:");
         let mut builder = Builder::default();
-        for (name, status) in unhealthy_outputs {
-            builder.push_record([format!("  {}", name), status]);
+        for (name, status) in &unhealthy.outputs {
+            builder.push_record([format!("  {}", name), status.to_owned()]);
         }
         let mut table = builder.build();
         table.with(Style::empty());
         println!("{}", table);
     }
 
-    if exit_code == 0 {
+    if unhealthy.is_empty() {
         println!("All OK");
+        0
     } else {
         println!("Found inputs/outputs with non-ok status");
+        1
     }
-
-    exit_code
 }
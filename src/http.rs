@@ -0,0 +1,241 @@
+//! The HTTP transport of [`EdgeClient`](crate::EdgeClient), with timeouts,
+//! retries and TLS settings
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Context};
+use reqwest::blocking::{Client, Request, Response};
+use reqwest::header::{HeaderMap, HeaderValue};
+use reqwest::{Certificate, Identity, Method, Proxy};
+use serde::Serialize;
+
+use crate::edge::EdgeError;
+
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+const DEFAULT_RETRIES: u32 = 3;
+const MAX_BACKOFF: Duration = Duration::from_secs(5);
+
+// Set once from the global command line arguments
+static FLAGS: OnceLock<Options> = OnceLock::new();
+static VERBOSE: AtomicBool = AtomicBool::new(false);
+
+/// Connection settings, unset values fall back to the context and then to the
+/// defaults
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    pub connect_timeout: Option<Duration>,
+    pub request_timeout: Option<Duration>,
+    pub retries: Option<u32>,
+    /// Extra CA certificates to trust, in PEM
+    pub ca_file: Option<PathBuf>,
+    /// A client certificate and its PKCS #8 key, in PEM
+    pub client_cert: Option<PathBuf>,
+    pub client_key: Option<PathBuf>,
+    pub insecure_skip_verify: bool,
+    pub proxy: Option<String>,
+}
+
+impl Options {
+    fn or(&self, other: &Options) -> Options {
+        Options {
+            connect_timeout: self.connect_timeout.or(other.connect_timeout),
+            request_timeout: self.request_timeout.or(other.request_timeout),
+            retries: self.retries.or(other.retries),
+            ca_file: self.ca_file.clone().or(other.ca_file.clone()),
+            client_cert: self.client_cert.clone().or(other.client_cert.clone()),
+            client_key: self.client_key.clone().or(other.client_key.clone()),
+            insecure_skip_verify: self.insecure_skip_verify || other.insecure_skip_verify,
+            proxy: self.proxy.clone().or(other.proxy.clone()),
+        }
+    }
+}
+
+/// Makes every client use `options` over the settings of the context
+pub fn set_flags(options: Options) {
+    let _ = FLAGS.set(options);
+}
+
+/// Logs every request on stderr
+pub fn set_verbose(verbose: bool) {
+    VERBOSE.store(verbose, Ordering::Relaxed);
+}
+
+/// A blocking HTTP client that retries failed requests and optionally logs
+/// them
+pub struct HttpClient {
+    client: Client,
+    headers: HeaderMap,
+    retries: u32,
+}
+
+impl HttpClient {
+    pub fn new(
+        headers: HeaderMap,
+        cookie_store: bool,
+        context: &Options,
+    ) -> Result<Self, EdgeError> {
+        let options = FLAGS.get().cloned().unwrap_or_default().or(context);
+        let client = build(&options, cookie_store)
+            .map_err(|e| EdgeError::Config(format!("Failed to create HTTP client: {:#}", e)))?;
+
+        Ok(Self {
+            client,
+            headers,
+            retries: options.retries.unwrap_or(DEFAULT_RETRIES),
+        })
+    }
+
+    pub fn get(&self, url: impl AsRef<str>) -> RequestBuilder<'_> {
+        self.request(Method::GET, url)
+    }
+
+    pub fn post(&self, url: impl AsRef<str>) -> RequestBuilder<'_> {
+        self.request(Method::POST, url)
+    }
+
+    pub fn put(&self, url: impl AsRef<str>) -> RequestBuilder<'_> {
+        self.request(Method::PUT, url)
+    }
+
+    pub fn delete(&self, url: impl AsRef<str>) -> RequestBuilder<'_> {
+        self.request(Method::DELETE, url)
+    }
+
+    fn execute(&self, request: Request) -> reqwest::Result<Response> {
+        if !VERBOSE.load(Ordering::Relaxed) {
+            return self.client.execute(request);
+        }
+
+        let method = request.method().clone();
+        let url = request.url().clone();
+        eprintln!("> {} {}", method, url);
+        for (name, value) in request.headers() {
+            eprintln!("> {}: {}", name, redact(name.as_str(), value));
+        }
+
+        let start = Instant::now();
+        let result = self.client.execute(request);
+        let elapsed = start.elapsed().as_millis();
+        match &result {
+            Ok(res) => eprintln!("< {} {} {} in {} ms", method, url, res.status(), elapsed),
+            Err(e) => eprintln!("< {} {} failed in {} ms: {}", method, url, elapsed, e),
+        }
+        result
+    }
+
+    fn request(&self, method: Method, url: impl AsRef<str>) -> RequestBuilder<'_> {
+        RequestBuilder {
+            client: self,
+            builder: self
+                .client
+                .request(method, url.as_ref())
+                .headers(self.headers.clone()),
+        }
+    }
+}
+
+pub struct RequestBuilder<'a> {
+    client: &'a HttpClient,
+    builder: reqwest::blocking::RequestBuilder,
+}
+
+impl RequestBuilder<'_> {
+    pub fn header(self, key: &'static str, value: &str) -> Self {
+        Self {
+            builder: self.builder.header(key, value),
+            ..self
+        }
+    }
+
+    pub fn json<T: Serialize + ?Sized>(self, json: &T) -> Self {
+        Self {
+            builder: self.builder.json(json),
+            ..self
+        }
+    }
+
+    /// Sends the request. Connection errors are retried for every request,
+    /// server errors and timeouts only for idempotent ones.
+    pub fn send(self) -> reqwest::Result<Response> {
+        let request = self.builder.build()?;
+        let idempotent = matches!(
+            *request.method(),
+            Method::GET | Method::HEAD | Method::PUT | Method::DELETE
+        );
+
+        let mut attempt = 0;
+        loop {
+            // Bodies are kept in memory, only streamed ones cannot be cloned
+            let Some(next) = request.try_clone() else {
+                return self.client.execute(request);
+            };
+            let result = self.client.execute(next);
+            let retryable = match &result {
+                Ok(res) => idempotent && res.status().is_server_error(),
+                Err(e) => e.is_connect() || (idempotent && e.is_timeout()),
+            };
+            if !retryable || attempt >= self.client.retries {
+                return result;
+            }
+
+            let backoff = Duration::from_millis(200 * 2u64.pow(attempt)).min(MAX_BACKOFF);
+            if VERBOSE.load(Ordering::Relaxed) {
+                eprintln!("Retrying in {} ms", backoff.as_millis());
+            }
+            thread::sleep(backoff);
+            attempt += 1;
+        }
+    }
+}
+
+fn build(options: &Options, cookie_store: bool) -> anyhow::Result<Client> {
+    let mut builder = Client::builder()
+        .cookie_store(cookie_store)
+        .connect_timeout(options.connect_timeout.unwrap_or(DEFAULT_CONNECT_TIMEOUT))
+        .timeout(options.request_timeout.unwrap_or(DEFAULT_REQUEST_TIMEOUT))
+        .danger_accept_invalid_certs(options.insecure_skip_verify);
+
+    if let Some(path) = &options.ca_file {
+        let pem = fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        for cert in Certificate::from_pem_bundle(&pem)
+            .with_context(|| format!("Invalid CA certificates in {}", path.display()))?
+        {
+            builder = builder.add_root_certificate(cert);
+        }
+    }
+    match (&options.client_cert, &options.client_key) {
+        (Some(cert), Some(key)) => {
+            let cert =
+                fs::read(cert).with_context(|| format!("Failed to read {}", cert.display()))?;
+            let key = fs::read(key).with_context(|| format!("Failed to read {}", key.display()))?;
+            builder = builder.identity(
+                Identity::from_pkcs8_pem(&cert, &key).context("Invalid client certificate")?,
+            );
+        }
+        (None, None) => {}
+        _ => {
+            return Err(anyhow!(
+                "A client certificate needs both a certificate and a key"
+            ))
+        }
+    }
+    if let Some(proxy) = &options.proxy {
+        builder =
+            builder.proxy(Proxy::all(proxy).with_context(|| format!("Invalid proxy {}", proxy))?);
+    }
+
+    Ok(builder.build()?)
+}
+
+fn redact(name: &str, value: &HeaderValue) -> String {
+    match name {
+        "edge-api-token" | "cookie" | "authorization" => "<redacted>".to_owned(),
+        _ => value.to_str().unwrap_or("<binary>").to_owned(),
+    }
+}
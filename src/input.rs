@@ -1,26 +1,42 @@
 use clap::{Arg, ArgAction, ArgMatches, Command};
-use std::collections::BTreeMap;
-use std::fmt;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::process;
 
 use anyhow::{anyhow, Context};
+use clap_complete::ArgValueCompleter;
 use tabled::{builder::Builder, settings::Style};
 
+use crate::client::{new_client, new_client_for_context};
+use crate::context::context_name_completer;
 use crate::edge::{
-    new_client, AppliancePhysicalPort, DerivableInputSource, EdgeClient, GeneratorBitrate,
-    GeneratorBitrateCBR, GeneratorInputPort, IngestTransform, InputAdminStatus, NewInputPort,
-    PidMap, RistInputPort, RtpInputPort, SdiEncoderAudioStream, SdiEncoderSettings, SdiInputPort,
+    AppliancePhysicalPort, DerivableInputSource, EdgeClient, GeneratorBitrate, GeneratorBitrateCBR,
+    GeneratorInputPort, IngestTransform, InputAdminStatus, InputHealth, NewInputPort, PidMap,
+    RistInputPort, RtpInputPort, SdiEncoderAudioStream, SdiEncoderSettings, SdiInputPort,
     SrtInputPort, UdpInputPort,
 };
+use crate::editor;
+use crate::fanout;
+use crate::filter;
+use crate::format::{self, Format};
+use crate::glob;
+use crate::legs::{self, PortSpec};
+use crate::manifest::{self, InputManifest, Resolver};
+use crate::output::parse_destination;
+use crate::query::Query;
+use crate::srt;
+use crate::{exit_with, OrExit};
 use crate::{green, red};
 
-impl fmt::Display for crate::edge::InputHealth {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if self.state == "allOk" {
-            write!(f, "{}", green!("✓"))
-        } else {
-            write!(f, "{} {}", red!("✗"), self.title)
-        }
+// PIDs are 13 bits, the highest one is for null packets
+const MAX_PID: u16 = 0x1fff;
+// The PIDs below are reserved for the PSI and SI tables
+const FIRST_ELEMENTARY_PID: u16 = 0x20;
+
+pub(crate) fn health_fmt(health: &InputHealth) -> String {
+    if health.state == "allOk" {
+        green!("✓")
+    } else {
+        format!("{} {}", red!("✗"), health.title)
     }
 }
 
@@ -30,21 +46,17 @@ pub(crate) fn subcommand() -> clap::Command {
         .subcommand_required(true)
         .subcommand(
             Command::new("list")
-                .arg(
-                    Arg::new("output")
-                        .long("output")
-                        .short('o')
-                        .value_parser(["short", "wide"])
-                        .default_value("short")
-                        .help("Change the output format"),
-                ),
+                .arg(format::arg(&["short", "wide"]))
+                .args(filter::args()),
         )
         .subcommand(
-            Command::new("show").arg(
-                Arg::new("name")
-                    .required(true)
-                    .help("The input name to show details for"),
-            ),
+            Command::new("show")
+                .arg(
+                    Arg::new("name")
+                        .required(true)
+                        .help("The input name to show details for"),
+                )
+                .arg(format::arg(&["text"])),
         )
         .subcommand(
             Command::new("create")
@@ -81,13 +93,6 @@ pub(crate) fn subcommand() -> clap::Command {
                         .short('i')
                         .long("interface")
                         .required(false)
-                        .required_if_eq_any([
-                            ("mode", "rtp"),
-                            ("mode", "udp"),
-                            ("mode", "srt"),
-                            ("mode", "rist"),
-                            ("mode", "sdi"),
-                        ])
                         .help("The interface on the appliance to create the input on"),
                 )
                 .arg(
@@ -106,12 +111,6 @@ pub(crate) fn subcommand() -> clap::Command {
                         .value_parser(clap::value_parser!(u16).range(1..))
                         .action(clap::ArgAction::Set)
                         .required(false)
-                        .required_if_eq_any([
-                            ("mode", "rtp"),
-                            ("mode", "udp"),
-                            ("mode", "rist"),
-                            ("listener", "true"),
-                        ])
                         .help("The TCP or UDP port to listen to"),
                 )
                 .arg(
@@ -123,6 +122,7 @@ pub(crate) fn subcommand() -> clap::Command {
                 .arg(
                     Arg::new("multicast")
                         .long("multicast")
+                        .conflicts_with("port-spec")
                         .help("Specify source multicast address for RTP and UDP inputs"),
                 )
                 .arg(
@@ -149,23 +149,30 @@ pub(crate) fn subcommand() -> clap::Command {
                         .long("map")
                         .num_args(2)
                         .action(ArgAction::Append)
-                        .value_parser(clap::value_parser!(u16).range(1..))
-                        .help("Map PIDs in the stream (derived streams only)"),
+                        .value_parser(clap::value_parser!(u16).range(1..MAX_PID as i64))
+                        .help("Map PIDs in the stream (derived streams only). The PIDs of the parent are not known here, so mapping onto a PID the parent still carries is not caught"),
                 )
                 .arg(
                     Arg::new("set-null")
                         .long("set-null")
                         .action(ArgAction::Append)
-                        .value_parser(clap::value_parser!(u16).range(1..))
+                        .value_parser(clap::value_parser!(u16).range(1..MAX_PID as i64))
                         .help("Replace PID with null packets (derived streams only)"),
                 )
                 .arg(
                     Arg::new("delete")
                         .long("delete")
                         .action(ArgAction::Append)
-                        .value_parser(clap::value_parser!(u16).range(1..))
+                        .value_parser(clap::value_parser!(u16).range(1..MAX_PID as i64))
                         .help("Delete PID from stream (derived streams only)"),
                 )
+                .arg(
+                    Arg::new("service")
+                        .long("service")
+                        .action(ArgAction::Append)
+                        .value_parser(clap::value_parser!(u16).range(1..))
+                        .help("The program number of a service to demux from an MPTS parent, can be repeated (derived streams only) [default: 1]"),
+                )
                 .arg(
                     Arg::new("caller")
                         .long("caller")
@@ -187,13 +194,122 @@ pub(crate) fn subcommand() -> clap::Command {
                 .arg(Arg::new("destination")
                     .long("dest")
                     .required(false)
-                    .required_if_eq("caller", "true")
-                    .help("The destination to for SRT callers format ip:port, e.g. 198.51.100.12:4000"),
+                    .required_if_eq_any([("caller", "true"), ("rendezvous", "true")])
+                    .help("The destination to for SRT callers and rendezvous in format ip:port, e.g. 198.51.100.12:4000"),
                 )
                 .group(
                     clap::ArgGroup::new("srt_mode")
                         .args(["caller", "listener", "rendezvous"])
                         .required(false)
+                )
+                .args(srt::args())
+                .arg(legs::arg())
+                .arg(
+                    Arg::new("stream-id")
+                        .long("stream-id")
+                        .requires("caller")
+                        .help("The stream ID to send when calling. Only applicable for SRT callers"),
+                ),
+        )
+        .subcommand(
+            Command::new("update")
+                .about("Change an existing input")
+                .arg(
+                    Arg::new("name")
+                        .required(true)
+                        .help("The name of the input to update"),
+                )
+                .arg(
+                    Arg::new("rename")
+                        .long("rename")
+                        .help("Give the input a new name"),
+                )
+                .arg(
+                    Arg::new("enable")
+                        .long("enable")
+                        .num_args(0)
+                        .conflicts_with("disable")
+                        .help("Enable the input"),
+                )
+                .arg(
+                    Arg::new("disable")
+                        .long("disable")
+                        .num_args(0)
+                        .help("Disable the input"),
+                )
+                .arg(
+                    Arg::new("thumbnail")
+                        .long("thumbnail")
+                        .value_parser(clap::builder::PossibleValuesParser::new([
+                            "core", "edge", "none",
+                        ]))
+                        .help("Set thumbnailing mode"),
+                )
+                .arg(
+                    Arg::new("buffer-size")
+                        .long("buffer-size")
+                        .value_parser(clap::value_parser!(u32))
+                        .help("Set the buffer size in milliseconds"),
+                )
+                .arg(
+                    Arg::new("tr101290")
+                        .long("tr101290")
+                        .value_parser(clap::value_parser!(bool))
+                        .help("Enable or disable TR 101 290 analysis"),
+                )
+                .arg(
+                    Arg::new("appliance")
+                        .short('a')
+                        .long("appliance")
+                        .requires("interface")
+                        .help("Move the port to this appliance. Requires --interface"),
+                )
+                .arg(
+                    Arg::new("interface")
+                        .short('i')
+                        .long("interface")
+                        .help("Move the port to this interface"),
+                )
+                .arg(
+                    Arg::new("port")
+                        .short('p')
+                        .long("port")
+                        .value_parser(clap::value_parser!(u16).range(1..))
+                        .help("Change the TCP or UDP port to listen to"),
+                )
+                .arg(
+                    Arg::new("multicast")
+                        .long("multicast")
+                        .help("Change the source multicast address for RTP and UDP inputs"),
+                ),
+        )
+        .subcommand(
+            Command::new("edit")
+                .about("Edit an input in $EDITOR")
+                .arg(
+                    Arg::new("name")
+                        .required(true)
+                        .help("The name of the input to edit"),
+                ),
+        )
+        .subcommand(
+            Command::new("enable")
+                .about("Enable inputs")
+                .arg(
+                    Arg::new("name")
+                        .required(true)
+                        .num_args(1..)
+                        .help("The names of the inputs to enable, may contain * and ? wildcards"),
+                ),
+        )
+        .subcommand(
+            Command::new("disable")
+                .about("Disable inputs")
+                .arg(
+                    Arg::new("name")
+                        .required(true)
+                        .num_args(1..)
+                        .help("The names of the inputs to disable, may contain * and ? wildcards"),
                 ),
         )
         .subcommand(
@@ -204,18 +320,60 @@ pub(crate) fn subcommand() -> clap::Command {
                     .help("The name of the inputs to remove"),
             ),
         )
+        .subcommand(
+            Command::new("copy")
+                .about("Copy an input to another installation")
+                .arg(
+                    Arg::new("name")
+                        .required(true)
+                        .help("The name of the input to copy"),
+                )
+                .arg(
+                    Arg::new("to-context")
+                        .long("to-context")
+                        .required(true)
+                        .help("The context of the installation to copy the input to")
+                        .add(ArgValueCompleter::new(context_name_completer)),
+                ),
+        )
+        .subcommand(
+            Command::new("move")
+                .about("Move an input to another installation")
+                .arg(
+                    Arg::new("name")
+                        .required(true)
+                        .help("The name of the input to move"),
+                )
+                .arg(
+                    Arg::new("to-context")
+                        .long("to-context")
+                        .required(true)
+                        .help("The context of the installation to move the input to")
+                        .add(ArgValueCompleter::new(context_name_completer)),
+                )
+                .arg(
+                    Arg::new("force")
+                        .long("force")
+                        .num_args(0)
+                        .help("Move the input even if outputs use it, leaving them without it"),
+                ),
+        )
 }
 
 pub(crate) fn run(subcmd: &ArgMatches) {
     match subcmd.subcommand() {
         Some(("list", args)) => {
             let client = new_client();
-            if let Err(e) = match args.get_one::<String>("output").map(|s| s.as_str()) {
-                Some("wide") => list_wide(client),
-                _ => list(client),
+            let query = filter::query(&client, args).unwrap_or_else(|e| exit_with(e));
+            if let Err(e) = match (
+                format::structured(args),
+                args.get_one::<String>("output").map(|s| s.as_str()),
+            ) {
+                (Some(format), _) => list_structured(client, format, query),
+                (None, Some("wide")) => list_wide(client, query),
+                _ => list(client, query),
             } {
-                eprintln!("Failed to list inputs: {:?}", e);
-                process::exit(1);
+                exit_with(e);
             }
         }
         Some(("show", args)) => {
@@ -225,7 +383,10 @@ pub(crate) fn run(subcmd: &ArgMatches) {
                 .map(|s| s.as_str())
                 .expect("input name should not be None");
 
-            show(client, name);
+            match format::structured(args) {
+                Some(format) => show_structured(client, name, format),
+                None => show(client, name),
+            }
         }
         Some(("create", args)) => {
             let client = new_client();
@@ -261,71 +422,41 @@ pub(crate) fn run(subcmd: &ArgMatches) {
                 process::exit(1);
             }
 
-            let mode = match mode {
-                "rtp" => {
-                    let port = match port {
-                        Some(p) => p,
-                        None => {
-                            eprintln!("Port is required for RTP inputs");
-                            process::exit(1);
-                        }
-                    };
-                    NewInputMode::Rtp(NewRtpInputMode {
-                        appliance: args
-                            .get_one::<String>("appliance")
-                            .cloned()
-                            .expect("appliance is required"),
-                        interface: args
-                            .get_one::<String>("interface")
-                            .cloned()
-                            .expect("interface is required"),
-                        port: *port,
-                        fec: args.get_flag("fec"),
-                        multicast_address: multicast.map(|s| s.to_owned()),
-                    })
-                }
-                "udp" => {
-                    let port = match port {
-                        Some(p) => p,
-                        None => {
-                            eprintln!("Port is required for UDP inputs");
-                            process::exit(1);
-                        }
-                    };
-                    NewInputMode::Udp(NewUdpInputMode {
-                        appliance: args
-                            .get_one::<String>("appliance")
-                            .cloned()
-                            .expect("appliance is required"),
-                        interface: args
-                            .get_one::<String>("interface")
-                            .cloned()
-                            .expect("interface is required"),
-                        port: *port,
-                        multicast_address: multicast.map(|s| s.to_owned()),
-                    })
+            for arg in ["parent", "map", "set-null", "delete", "service"] {
+                if args.contains_id(arg) && mode != "derived" {
+                    eprintln!("--{} is only supported for derived inputs", arg);
+                    process::exit(1);
                 }
-                "srt" => {
-                    if args.get_flag("rendezvous") {
-                        eprintln!("--rendezvous is not yet implemented");
+            }
+
+            // Redundant inputs give the appliance and interface of every leg
+            // in --port-spec instead
+            if !args.contains_id("port-spec")
+                && matches!(mode, "rtp" | "udp" | "srt" | "rist" | "sdi")
+            {
+                for arg in ["appliance", "interface"] {
+                    if !args.contains_id(arg) {
+                        eprintln!("--{} is required for {} inputs", arg, mode);
                         process::exit(1);
-                    } else if args.get_flag("caller") {
-                        let dest = match args.get_one::<String>("destination") {
-                            Some(d) => d,
+                    }
+                }
+            }
+
+            let modes = match args.get_many::<PortSpec>("port-spec") {
+                Some(specs) => specs
+                    .map(|spec| leg_mode(mode, spec, args))
+                    .collect::<anyhow::Result<Vec<_>>>()
+                    .unwrap_or_else(|e| exit_with(e)),
+                None => vec![match mode {
+                    "rtp" => {
+                        let port = match port {
+                            Some(p) => p,
                             None => {
-                                eprintln!("Dest is required for SRT caller inputs");
+                                eprintln!("Port is required for RTP inputs");
                                 process::exit(1);
                             }
                         };
-                        let address = dest.split(':').next().expect("dest address is missing");
-                        let port = dest
-                            .split(':')
-                            .next_back()
-                            .expect("Port number is required for --dest")
-                            .parse::<u16>()
-                            .expect("port needs to be a number between 0 and 65535");
-
-                        NewInputMode::Srt(NewSrtInputMode::Caller {
+                        NewInputMode::Rtp(NewRtpInputMode {
                             appliance: args
                                 .get_one::<String>("appliance")
                                 .cloned()
@@ -334,18 +465,20 @@ pub(crate) fn run(subcmd: &ArgMatches) {
                                 .get_one::<String>("interface")
                                 .cloned()
                                 .expect("interface is required"),
-                            address: address.to_owned(),
-                            port,
+                            port: *port,
+                            fec: args.get_flag("fec"),
+                            multicast_address: multicast.map(|s| s.to_owned()),
                         })
-                    } else if args.get_flag("listener") {
-                        let port = match args.get_one::<u16>("port") {
-                            Some(port) => port,
+                    }
+                    "udp" => {
+                        let port = match port {
+                            Some(p) => p,
                             None => {
-                                eprintln!("--port is required for srt listener outputs");
+                                eprintln!("Port is required for UDP inputs");
                                 process::exit(1);
                             }
                         };
-                        NewInputMode::Srt(NewSrtInputMode::Listener {
+                        NewInputMode::Udp(NewUdpInputMode {
                             appliance: args
                                 .get_one::<String>("appliance")
                                 .cloned()
@@ -355,21 +488,106 @@ pub(crate) fn run(subcmd: &ArgMatches) {
                                 .cloned()
                                 .expect("interface is required"),
                             port: *port,
+                            multicast_address: multicast.map(|s| s.to_owned()),
                         })
-                    } else {
-                        eprintln!("Missing either --listener, --caller or --rendezvous flag for creating SRT input");
-                        process::exit(1);
                     }
-                }
-                "rist" => {
-                    let port = match args.get_one::<u16>("port") {
-                        Some(port) => port,
-                        None => {
-                            eprintln!("--port is required for RIST outputs");
+                    "srt" => {
+                        let settings = srt::settings(args).unwrap_or_else(|e| exit_with(e));
+                        if args.get_flag("rendezvous") {
+                            let dest = args
+                                .get_one::<String>("destination")
+                                .expect("dest is required for SRT rendezvous inputs");
+                            let (address, port) =
+                                parse_destination(dest).unwrap_or_else(|e| exit_with(e));
+                            NewInputMode::Srt(NewSrtInputMode::Rendezvous {
+                                appliance: args
+                                    .get_one::<String>("appliance")
+                                    .cloned()
+                                    .expect("appliance is required"),
+                                interface: args
+                                    .get_one::<String>("interface")
+                                    .cloned()
+                                    .expect("interface is required"),
+                                address,
+                                port,
+                                settings,
+                            })
+                        } else if args.get_flag("caller") {
+                            let dest = match args.get_one::<String>("destination") {
+                                Some(d) => d,
+                                None => {
+                                    eprintln!("Dest is required for SRT caller inputs");
+                                    process::exit(1);
+                                }
+                            };
+                            let address = dest.split(':').next().expect("dest address is missing");
+                            let port = dest
+                                .split(':')
+                                .next_back()
+                                .expect("Port number is required for --dest")
+                                .parse::<u16>()
+                                .expect("port needs to be a number between 0 and 65535");
+
+                            NewInputMode::Srt(NewSrtInputMode::Caller {
+                                appliance: args
+                                    .get_one::<String>("appliance")
+                                    .cloned()
+                                    .expect("appliance is required"),
+                                interface: args
+                                    .get_one::<String>("interface")
+                                    .cloned()
+                                    .expect("interface is required"),
+                                address: address.to_owned(),
+                                port,
+                                stream_id: args.get_one::<String>("stream-id").cloned(),
+                                settings,
+                            })
+                        } else if args.get_flag("listener") {
+                            let port = match args.get_one::<u16>("port") {
+                                Some(port) => port,
+                                None => {
+                                    eprintln!("--port is required for srt listener outputs");
+                                    process::exit(1);
+                                }
+                            };
+                            NewInputMode::Srt(NewSrtInputMode::Listener {
+                                appliance: args
+                                    .get_one::<String>("appliance")
+                                    .cloned()
+                                    .expect("appliance is required"),
+                                interface: args
+                                    .get_one::<String>("interface")
+                                    .cloned()
+                                    .expect("interface is required"),
+                                port: *port,
+                                settings,
+                            })
+                        } else {
+                            eprintln!("Missing either --listener, --caller or --rendezvous flag for creating SRT input");
                             process::exit(1);
                         }
-                    };
-                    NewInputMode::Rist(NewRistInputMode {
+                    }
+                    "rist" => {
+                        let port = match args.get_one::<u16>("port") {
+                            Some(port) => port,
+                            None => {
+                                eprintln!("--port is required for RIST outputs");
+                                process::exit(1);
+                            }
+                        };
+                        NewInputMode::Rist(NewRistInputMode {
+                            appliance: args
+                                .get_one::<String>("appliance")
+                                .cloned()
+                                .expect("appliance is required"),
+                            interface: args
+                                .get_one::<String>("interface")
+                                .cloned()
+                                .expect("interface is required"),
+                            port: *port,
+                        })
+                    }
+                    "sdi" => NewInputMode::Sdi(NewSdiInputMode {
                         appliance: args
                             .get_one::<String>("appliance")
                             .cloned()
@@ -378,66 +596,59 @@ pub(crate) fn run(subcmd: &ArgMatches) {
                             .get_one::<String>("interface")
                             .cloned()
                             .expect("interface is required"),
-                        port: *port,
-                    })
-                }
-                "sdi" => NewInputMode::Sdi(NewSdiInputMode {
-                    appliance: args
-                        .get_one::<String>("appliance")
-                        .cloned()
-                        .expect("appliance is required"),
-                    interface: args
-                        .get_one::<String>("interface")
-                        .cloned()
-                        .expect("interface is required"),
-                }),
-                "generator" => {
-                    if args.contains_id("interface") {
-                        eprintln!("Cannot specify interface for generator input");
-                        process::exit(1)
+                    }),
+                    "generator" => {
+                        if args.contains_id("interface") {
+                            eprintln!("Cannot specify interface for generator input");
+                            process::exit(1)
+                        }
+
+                        NewInputMode::Generator(NewGeneratorInputMode {
+                            appliance: args
+                                .get_one::<String>("appliance")
+                                .cloned()
+                                .expect("appliance is required"),
+                            bitrate: bitrate.unwrap_or(&Bitrate::Vbr).clone(),
+                        })
                     }
+                    "derived" => {
+                        let mut rules: Vec<PIDRule> = Vec::new();
+                        let maps = args
+                            .get_occurrences::<u16>("map")
+                            .unwrap_or_default()
+                            .map(Iterator::collect)
+                            .map(|m: Vec<&u16>| PIDRule::Map(*m[0], *m[1]));
+                        let deletes = args
+                            .get_many::<u16>("delete")
+                            .unwrap_or_default()
+                            .map(|d| PIDRule::Delete(*d));
 
-                    NewInputMode::Generator(NewGeneratorInputMode {
-                        appliance: args
-                            .get_one::<String>("appliance")
-                            .cloned()
-                            .expect("appliance is required"),
-                        bitrate: bitrate.unwrap_or(&Bitrate::Vbr).clone(),
-                    })
-                }
-                "derived" => {
-                    let mut rules: Vec<PIDRule> = Vec::new();
-                    let maps = args
-                        .get_occurrences::<u16>("map")
-                        .unwrap_or_default()
-                        .map(Iterator::collect)
-                        .map(|m: Vec<&u16>| PIDRule::Map(*m[0], *m[1]));
-                    let deletes = args
-                        .get_many::<u16>("delete")
-                        .unwrap_or_default()
-                        .map(|d| PIDRule::Delete(*d));
-
-                    let nulls = args
-                        .get_many::<u16>("set-null")
-                        .unwrap_or_default()
-                        .map(|d| PIDRule::SetNull(*d));
-
-                    rules.extend(maps);
-                    rules.extend(deletes);
-                    rules.extend(nulls);
-
-                    NewInputMode::Derived(NewDerivedInputMode {
-                        parent: args
-                            .get_one::<String>("parent")
-                            .expect("parent is required for derived inputs")
-                            .to_owned(),
-                        pid_rules: rules,
-                    })
-                }
-                e => {
-                    eprintln!("Invalid mode: {}", e);
-                    process::exit(1);
-                }
+                        let nulls = args
+                            .get_many::<u16>("set-null")
+                            .unwrap_or_default()
+                            .map(|d| PIDRule::SetNull(*d));
+
+                        rules.extend(maps);
+                        rules.extend(deletes);
+                        rules.extend(nulls);
+
+                        NewInputMode::Derived(NewDerivedInputMode {
+                            parent: args
+                                .get_one::<String>("parent")
+                                .expect("parent is required for derived inputs")
+                                .to_owned(),
+                            services: args
+                                .get_many::<u16>("service")
+                                .map(|services| services.copied().collect())
+                                .unwrap_or_else(|| vec![1]),
+                            pid_rules: rules,
+                        })
+                    }
+                    e => {
+                        eprintln!("Invalid mode: {}", e);
+                        process::exit(1);
+                    }
+                }],
             };
 
             let thumbnail_mode = match args.get_one::<String>("thumbnail").map(|s| s.as_str()) {
@@ -452,10 +663,62 @@ pub(crate) fn run(subcmd: &ArgMatches) {
                 NewInput {
                     name: name.to_owned(),
                     thumbnails: thumbnail_mode,
-                    mode,
+                    modes,
                 },
             )
         }
+        Some(("update", args)) => {
+            let client = new_client();
+            let name = args
+                .get_one::<String>("name")
+                .expect("Input name is mandatory");
+            if let Err(e) = update(&client, name, args) {
+                eprintln!("Failed to update input {}: {:#}", name, e);
+                process::exit(1);
+            }
+        }
+        Some(("edit", args)) => {
+            let client = new_client();
+            let name = args
+                .get_one::<String>("name")
+                .expect("Input name is mandatory");
+            if let Err(e) = edit(&client, name) {
+                eprintln!("Failed to edit input {}: {:#}", name, e);
+                process::exit(1);
+            }
+        }
+        Some(("enable", args)) => {
+            let client = new_client();
+            let mut failed = false;
+            for name in args
+                .get_many::<String>("name")
+                .expect("Input name is mandatory")
+            {
+                if let Err(e) = set_admin_status(&client, name, InputAdminStatus::On) {
+                    eprintln!("Failed to enable input {}: {:#}", name, e);
+                    failed = true;
+                }
+            }
+            if failed {
+                process::exit(1);
+            }
+        }
+        Some(("disable", args)) => {
+            let client = new_client();
+            let mut failed = false;
+            for name in args
+                .get_many::<String>("name")
+                .expect("Input name is mandatory")
+            {
+                if let Err(e) = set_admin_status(&client, name, InputAdminStatus::Off) {
+                    eprintln!("Failed to disable input {}: {:#}", name, e);
+                    failed = true;
+                }
+            }
+            if failed {
+                process::exit(1);
+            }
+        }
         Some(("delete", args)) => {
             let client = new_client();
             let mut failed = false;
@@ -472,6 +735,32 @@ pub(crate) fn run(subcmd: &ArgMatches) {
                 process::exit(1);
             }
         }
+        Some(("copy", args)) => {
+            let client = new_client();
+            let name = args
+                .get_one::<String>("name")
+                .expect("Input name is mandatory");
+            let to_context = args
+                .get_one::<String>("to-context")
+                .expect("Destination context is mandatory");
+            if let Err(e) = transfer(&client, name, to_context, false, false) {
+                eprintln!("Failed to copy input {}: {:#}", name, e);
+                process::exit(1);
+            }
+        }
+        Some(("move", args)) => {
+            let client = new_client();
+            let name = args
+                .get_one::<String>("name")
+                .expect("Input name is mandatory");
+            let to_context = args
+                .get_one::<String>("to-context")
+                .expect("Destination context is mandatory");
+            if let Err(e) = transfer(&client, name, to_context, true, args.get_flag("force")) {
+                eprintln!("Failed to move input {}: {:#}", name, e);
+                process::exit(1);
+            }
+        }
         Some((cmd, _)) => {
             eprintln!("Command input {cmd} is not yet implemented");
             process::exit(1);
@@ -497,6 +786,19 @@ fn parse_bitrate(val: &str) -> Result<u64, String> {
     }
 }
 
+fn list_structured(client: EdgeClient, format: Format, query: Query) -> anyhow::Result<()> {
+    format::print_items(format, client.input_pages(query).items()).context("Failed to list inputs")
+}
+
+fn show_structured(client: EdgeClient, name: &str, format: Format) {
+    match client.find_inputs(name) {
+        Ok(inputs) => format::print(format, &inputs),
+        Err(e) => {
+            exit_with(anyhow::Error::from(e).context("Failed to find inputs"));
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -527,23 +829,57 @@ mod tests {
         test_bitrate("1 Kbps", Err("Invalid bitrate: 1 Kbps".to_owned()));
         test_bitrate("1Kbps", Err("Invalid bitrate: 1Kbps".to_owned()));
     }
+
+    #[test]
+    fn it_rejects_colliding_pid_rules() {
+        use crate::edge::PIDRule::{Delete, Map, SetNull};
+
+        let map = |pid, dest_pid| Map { pid, dest_pid };
+        assert!(check_pid_rules(&[map(256, 300), map(300, 256), Delete { pid: 400 }]).is_ok());
+        assert!(check_pid_rules(&[map(256, 300), map(257, 300)]).is_err());
+        assert!(check_pid_rules(&[map(256, 300), SetNull { pid: 256 }]).is_err());
+        assert!(check_pid_rules(&[map(256, 17)]).is_err());
+        assert!(check_pid_rules(&[map(256, 0x1fff)]).is_err());
+    }
 }
 
-fn list(client: EdgeClient) -> anyhow::Result<()> {
-    let inputs = client.list_inputs().context("Failed to list edge inputs")?;
-    let mut builder = Builder::default();
-    builder.push_record(["ID", "Name", "Health"]);
+/// `input list` against several contexts at once
+pub(crate) fn list_contexts(args: &ArgMatches, contexts: &[String]) {
+    let ok = match (
+        format::structured(args),
+        args.get_one::<String>("output").map(|s| s.as_str()),
+    ) {
+        (Some(format), _) => fanout::print_structured(
+            format,
+            fanout::run(contexts, |client| {
+                client
+                    .query_inputs(filter::query(client, args)?)
+                    .context("Failed to list inputs")
+            }),
+        ),
+        (None, Some("wide")) => fanout::print_table(
+            &LIST_WIDE_HEADER,
+            fanout::run(contexts, |client| {
+                list_wide_records(client, filter::query(client, args)?)
+            }),
+        ),
+        _ => fanout::print_table(
+            &LIST_HEADER,
+            fanout::run(contexts, |client| {
+                list_records(client, filter::query(client, args)?)
+            }),
+        ),
+    };
+    if !ok {
+        process::exit(1);
+    }
+}
 
-    for input in inputs {
-        builder.push_record([
-            input.id,
-            input.name,
-            if input.health.state == "allOk" {
-                green!("✓")
-            } else {
-                format!("{} {}", red!("✗"), input.health.title)
-            },
-        ]);
+fn list(client: EdgeClient, query: Query) -> anyhow::Result<()> {
+    let mut builder = Builder::default();
+    builder.push_record(LIST_HEADER);
+    for record in list_records(&client, query)? {
+        builder.push_record(record);
     }
 
     let mut table = builder.build();
@@ -553,60 +889,11 @@ fn list(client: EdgeClient) -> anyhow::Result<()> {
     Ok(())
 }
 
-fn list_wide(client: EdgeClient) -> anyhow::Result<()> {
-    let inputs = client.list_inputs().context("Failed to list inputs")?;
-    let mut groups = BTreeMap::new();
-    let mut group_list = client.list_groups().context("Failed to list groups")?;
-    while let Some(group) = group_list.pop() {
-        groups.insert(group.id.to_owned(), group);
-    }
-
+fn list_wide(client: EdgeClient, query: Query) -> anyhow::Result<()> {
     let mut builder = Builder::default();
-    builder.push_record([
-        "ID",
-        "Name",
-        "Group",
-        "Enabled",
-        "Buffer",
-        "Preview",
-        "Thumbnails",
-        "TR 101 290",
-        "Appliances",
-        "Health",
-    ]);
-
-    for input in inputs {
-        builder.push_record([
-            input.id,
-            input.name,
-            groups
-                .get(&input.owner)
-                .map(|g| g.name.to_owned())
-                .unwrap_or("?".to_owned()),
-            input.admin_status.to_string(),
-            input.buffer_size.to_string(),
-            input
-                .preview_settings
-                .map(|p| p.mode)
-                .unwrap_or("unknown".to_owned()),
-            input.thumbnail_mode.to_string(),
-            if input.tr101290_enabled {
-                "on".to_owned()
-            } else {
-                "off".to_owned()
-            },
-            input
-                .appliances
-                .into_iter()
-                .map(|a| a.name)
-                .collect::<Vec<String>>()
-                .join(", "),
-            if input.health.state == "allOk" {
-                green!("✓")
-            } else {
-                format!("{} {}", red!("✗"), input.health.title)
-            },
-        ]);
+    builder.push_record(LIST_WIDE_HEADER);
+    for record in list_wide_records(&client, query)? {
+        builder.push_record(record);
     }
 
     let mut table = builder.build();
@@ -616,6 +903,94 @@ fn list_wide(client: EdgeClient) -> anyhow::Result<()> {
     Ok(())
 }
 
+pub(crate) const LIST_HEADER: [&str; 3] = ["ID", "Name", "Health"];
+
+pub(crate) const LIST_WIDE_HEADER: [&str; 10] = [
+    "ID",
+    "Name",
+    "Group",
+    "Enabled",
+    "Buffer",
+    "Preview",
+    "Thumbnails",
+    "TR 101 290",
+    "Appliances",
+    "Health",
+];
+
+/// The rows of `input list`
+pub(crate) fn list_records(client: &EdgeClient, query: Query) -> anyhow::Result<Vec<Vec<String>>> {
+    let inputs = client
+        .query_inputs(query)
+        .context("Failed to list edge inputs")?;
+    Ok(inputs
+        .into_iter()
+        .map(|input| {
+            vec![
+                input.id,
+                input.name,
+                if input.health.state == "allOk" {
+                    green!("✓")
+                } else {
+                    format!("{} {}", red!("✗"), input.health.title)
+                },
+            ]
+        })
+        .collect())
+}
+
+/// The rows of `input list -o wide`
+pub(crate) fn list_wide_records(
+    client: &EdgeClient,
+    query: Query,
+) -> anyhow::Result<Vec<Vec<String>>> {
+    let inputs = client
+        .query_inputs(query)
+        .context("Failed to list inputs")?;
+    let mut groups = BTreeMap::new();
+    let mut group_list = client.list_groups().context("Failed to list groups")?;
+    while let Some(group) = group_list.pop() {
+        groups.insert(group.id.to_owned(), group);
+    }
+
+    Ok(inputs
+        .into_iter()
+        .map(|input| {
+            vec![
+                input.id,
+                input.name,
+                groups
+                    .get(&input.owner)
+                    .map(|g| g.name.to_owned())
+                    .unwrap_or("?".to_owned()),
+                input.admin_status.to_string(),
+                input.buffer_size.to_string(),
+                input
+                    .preview_settings
+                    .map(|p| p.mode)
+                    .unwrap_or("unknown".to_owned()),
+                input.thumbnail_mode.to_string(),
+                if input.tr101290_enabled {
+                    "on".to_owned()
+                } else {
+                    "off".to_owned()
+                },
+                input
+                    .appliances
+                    .into_iter()
+                    .map(|a| a.name)
+                    .collect::<Vec<String>>()
+                    .join(", "),
+                if input.health.state == "allOk" {
+                    green!("✓")
+                } else {
+                    format!("{} {}", red!("✗"), input.health.title)
+                },
+            ]
+        })
+        .collect())
+}
+
 fn show(client: EdgeClient, name: &str) {
     let inputs = client.find_inputs(name);
     let inputs = match inputs {
@@ -626,6 +1001,34 @@ fn show(client: EdgeClient, name: &str) {
         }
     };
 
+    // The state of every leg comes from the alarms on its physical port
+    let with_ports = inputs
+        .iter()
+        .filter(|i| i.ports.as_ref().is_some_and(|p| !p.is_empty()))
+        .collect::<Vec<_>>();
+    let (alarms, ports_by_id) = if with_ports.is_empty() {
+        (Vec::new(), HashMap::new())
+    } else {
+        let query = with_ports
+            .iter()
+            .fold(Query::new(), |query, input| query.input(&input.id));
+        let physical_ports = with_ports
+            .iter()
+            .flat_map(|i| i.ports.iter().flatten())
+            .map(|p| p.physical_port.to_owned())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let alarms = client.query_alarms(query).or_exit("Failed to list alarms");
+        let ports = client
+            .list_ports_by_ids(physical_ports)
+            .or_exit("Failed to list ports")
+            .into_iter()
+            .map(|port| (port.id.to_owned(), port))
+            .collect::<HashMap<_, _>>();
+        (alarms, ports)
+    };
+
     for input in inputs {
         let group = client.get_group(&input.owner);
         let group_name = group.map(|g| g.name).unwrap_or("unknown".to_owned());
@@ -654,25 +1057,72 @@ fn show(client: EdgeClient, name: &str) {
                 .join(", ")
         );
         if let Some(ports) = input.ports {
+            let alarms = alarms
+                .iter()
+                .filter(|a| a.input_id.as_deref() == Some(input.id.as_str()))
+                .cloned()
+                .collect::<Vec<_>>();
             println!("Ports:");
             for port in ports {
-                let port_details = client.get_port(&port.physical_port);
-                let name = port_details.map(|p| p.name).unwrap_or("unknown".to_owned());
+                let (name, appliance) = match ports_by_id.get(&port.physical_port) {
+                    Some(p) => (p.name.as_str(), p.appliance.name.as_str()),
+                    None => ("unknown", "unknown"),
+                };
                 println!("  - Mode:                   {}", port.mode);
+                println!("    Appliance:              {}", appliance);
                 println!("    Source interface:       {}", name);
                 println!("    Copies:                 {}", port.copies);
+                println!(
+                    "    State:                  {}",
+                    legs::state(Some(&alarms), &port.physical_port)
+                );
+            }
+        }
+        if let Some(derive_from) = input.derive_from {
+            let parent = client
+                .get_input(&derive_from.parent_input)
+                .map(|parent| parent.name)
+                .unwrap_or(derive_from.parent_input);
+            println!("Derived from:   {}", parent);
+            println!("Delay:          {}ms", derive_from.delay);
+            match derive_from.ingest_transform {
+                Some(IngestTransform::MptsDemuxTransform { services, pid_map }) => {
+                    println!(
+                        "Services:       {}",
+                        services
+                            .iter()
+                            .map(|s| s.to_string())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    );
+                    match pid_map.filter(|map| !map.rules.is_empty()) {
+                        Some(map) => {
+                            println!("PID rules:");
+                            for rule in map.rules {
+                                println!("  - {}", rule);
+                            }
+                        }
+                        None => println!("PID rules:      none"),
+                    }
+                }
+                Some(IngestTransform::Unsupported(transform)) => println!(
+                    "Transform:      {}",
+                    transform["type"].as_str().unwrap_or("unsupported")
+                ),
+                None => {}
             }
         }
         println!("Created:        {}", input.created_at);
         println!("Updated:        {}", input.updated_at);
-        println!("Health:         {}", input.health);
+        println!("Health:         {}", health_fmt(&input.health));
     }
 }
 
 struct NewInput {
     pub name: String,
     pub thumbnails: ThumbnailMode,
-    pub mode: NewInputMode,
+    // One mode per leg of redundant inputs
+    pub modes: Vec<NewInputMode>,
 }
 
 enum ThumbnailMode {
@@ -718,11 +1168,21 @@ enum NewSrtInputMode {
         interface: String,
         address: String,
         port: u16,
+        stream_id: Option<String>,
+        settings: srt::Settings,
     },
     Listener {
         appliance: String,
         interface: String,
         port: u16,
+        settings: srt::Settings,
+    },
+    Rendezvous {
+        appliance: String,
+        interface: String,
+        address: String,
+        port: u16,
+        settings: srt::Settings,
     },
 }
 
@@ -738,6 +1198,8 @@ struct NewGeneratorInputMode {
 
 struct NewDerivedInputMode {
     pub parent: String,
+    // The program numbers of the services to demux
+    pub services: Vec<u16>,
     pub pid_rules: Vec<PIDRule>,
 }
 
@@ -754,10 +1216,209 @@ enum Bitrate {
     Cbr(u64),
 }
 
+/// Checks that the PID rules of a derived input leave every PID of the
+/// stream with a single source. Only the rules are checked, a PID mapped onto
+/// a PID that the parent carries and no rule moves away still collides.
+pub(crate) fn check_pid_rules(rules: &[crate::edge::PIDRule]) -> anyhow::Result<()> {
+    let mut sources = HashSet::new();
+    let mut mapped = HashMap::new();
+    for rule in rules {
+        let pid = match rule {
+            crate::edge::PIDRule::Map { pid, .. }
+            | crate::edge::PIDRule::Delete { pid }
+            | crate::edge::PIDRule::SetNull { pid } => *pid,
+        };
+        if !sources.insert(pid) {
+            return Err(anyhow!("PID {} is in more than one rule", pid));
+        }
+        if let crate::edge::PIDRule::Map { dest_pid, .. } = rule {
+            if *dest_pid < FIRST_ELEMENTARY_PID || *dest_pid >= MAX_PID {
+                return Err(anyhow!(
+                    "PID {} cannot be mapped to {}, PIDs below {} are reserved for tables and {} for null packets",
+                    pid,
+                    dest_pid,
+                    FIRST_ELEMENTARY_PID,
+                    MAX_PID
+                ));
+            }
+            if let Some(other) = mapped.insert(*dest_pid, pid) {
+                return Err(anyhow!(
+                    "PIDs {} and {} are both mapped to {}",
+                    other,
+                    pid,
+                    dest_pid
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
 fn create(client: EdgeClient, new_input: NewInput) {
-    let ports = match new_input.mode {
+    // Every leg of a redundant input is a port of its own
+    let ports = new_input
+        .modes
+        .iter()
+        .flat_map(|mode| ports(&client, mode))
+        .collect();
+    let derived = new_input.modes.iter().find_map(|mode| match mode {
+        NewInputMode::Derived(derived) => Some(derived),
+        _ => None,
+    });
+
+    let derive_from = if let Some(derived) = derived {
+        let pid_map = PidMap {
+            rules: derived
+                .pid_rules
+                .iter()
+                .map(|r| match r {
+                    PIDRule::Map(from, to) => crate::edge::PIDRule::Map {
+                        pid: *from,
+                        dest_pid: *to,
+                    },
+                    PIDRule::Delete(pid) => crate::edge::PIDRule::Delete { pid: *pid },
+                    PIDRule::SetNull(pid) => crate::edge::PIDRule::SetNull { pid: *pid },
+                })
+                .collect(),
+        };
+        if let Err(e) = check_pid_rules(&pid_map.rules) {
+            exit_with(e);
+        }
+        let parent = client
+            .find_inputs(&derived.parent)
+            .or_exit("Failed to list inputs")
+            .into_iter()
+            .find(|i| i.name == derived.parent)
+            .unwrap_or_else(|| {
+                exit_with(anyhow!("Could not find parent input {}", derived.parent))
+            });
+        Some(DerivableInputSource {
+            parent_input: parent.id,
+            delay: 1000,
+            ingest_transform: Some(IngestTransform::MptsDemuxTransform {
+                services: derived.services.clone(),
+                pid_map: Some(pid_map),
+            }),
+        })
+    } else {
+        None
+    };
+
+    if let Err(e) = client.create_input(crate::edge::NewInput {
+        name: new_input.name,
+        tr101290_enabled: true,
+        broadcast_standard: "dvb".to_owned(),
+        thumbnail_mode: match new_input.thumbnails {
+            ThumbnailMode::Core => crate::edge::ThumbnailMode::Core,
+            ThumbnailMode::Edge => crate::edge::ThumbnailMode::Edge,
+            ThumbnailMode::None => crate::edge::ThumbnailMode::None,
+        },
+        video_preview_mode: if let ThumbnailMode::Core = new_input.thumbnails {
+            "on demand".to_owned()
+        } else {
+            "off".to_owned()
+        },
+        admin_status: InputAdminStatus::On,
+        ports,
+        buffer_size: 6_000,
+        max_bitrate: None,
+        owner: None,
+        derive_from,
+    }) {
+        exit_with(anyhow::Error::from(e).context("Failed to create input"));
+    }
+}
+
+// The mode of one leg of a redundant input
+fn leg_mode(mode: &str, spec: &PortSpec, args: &ArgMatches) -> anyhow::Result<NewInputMode> {
+    let PortSpec {
+        appliance,
+        interface,
+        address,
+        port,
+    } = spec.clone();
+    let no_address = || match &address {
+        Some(address) => Err(anyhow!(
+            "The address {} in --port-spec is not supported for {} inputs",
+            address,
+            mode
+        )),
+        None => Ok(()),
+    };
+    let with_address = || {
+        address
+            .clone()
+            .ok_or_else(|| anyhow!("--port-spec needs an address for {} inputs", mode))
+    };
+
+    Ok(match mode {
+        "rtp" => NewInputMode::Rtp(NewRtpInputMode {
+            appliance,
+            interface,
+            port,
+            fec: args.get_flag("fec"),
+            multicast_address: address,
+        }),
+        "udp" => NewInputMode::Udp(NewUdpInputMode {
+            appliance,
+            interface,
+            port,
+            multicast_address: address,
+        }),
+        "rist" => {
+            no_address()?;
+            NewInputMode::Rist(NewRistInputMode {
+                appliance,
+                interface,
+                port,
+            })
+        }
+        "srt" => {
+            let settings = srt::settings(args)?;
+            if args.get_flag("caller") {
+                NewInputMode::Srt(NewSrtInputMode::Caller {
+                    address: with_address()?,
+                    appliance,
+                    interface,
+                    port,
+                    stream_id: args.get_one::<String>("stream-id").cloned(),
+                    settings,
+                })
+            } else if args.get_flag("rendezvous") {
+                NewInputMode::Srt(NewSrtInputMode::Rendezvous {
+                    address: with_address()?,
+                    appliance,
+                    interface,
+                    port,
+                    settings,
+                })
+            } else if args.get_flag("listener") {
+                no_address()?;
+                NewInputMode::Srt(NewSrtInputMode::Listener {
+                    appliance,
+                    interface,
+                    port,
+                    settings,
+                })
+            } else {
+                return Err(anyhow!(
+                    "Missing either --listener, --caller or --rendezvous flag for creating SRT input"
+                ));
+            }
+        }
+        mode => {
+            return Err(anyhow!(
+                "--port-spec is only supported for RTP, UDP, RIST and SRT inputs, not {}",
+                mode
+            ))
+        }
+    })
+}
+
+fn ports(client: &EdgeClient, mode: &NewInputMode) -> Vec<NewInputPort> {
+    match *mode {
         NewInputMode::Rtp(ref rtp) => {
-            let interface = get_physical_port(&client, &rtp.appliance, &rtp.interface);
+            let interface = get_physical_port(client, &rtp.appliance, &rtp.interface);
             vec![NewInputPort::Rtp(RtpInputPort {
                 copies: 1,
                 physical_port: interface.id.to_owned(),
@@ -774,7 +1435,7 @@ fn create(client: EdgeClient, new_input: NewInput) {
             })]
         }
         NewInputMode::Udp(ref udp) => {
-            let interface = get_physical_port(&client, &udp.appliance, &udp.interface);
+            let interface = get_physical_port(client, &udp.appliance, &udp.interface);
             vec![NewInputPort::Udp(UdpInputPort {
                 copies: 1,
                 physical_port: interface.id.to_owned(),
@@ -793,13 +1454,18 @@ fn create(client: EdgeClient, new_input: NewInput) {
             ref interface,
             ref address,
             port,
+            ref stream_id,
+            ref settings,
         }) => {
-            let interface = get_physical_port(&client, appliance, interface);
+            let interface = get_physical_port(client, appliance, interface);
             vec![NewInputPort::Srt(SrtInputPort::Caller {
                 physical_port: interface.id.to_owned(),
                 remote_ip: address.to_owned(),
                 remote_port: port,
-                latency: 120,
+                latency: settings.latency,
+                pbkeylen: settings.keylen.clone(),
+                passphrase: settings.passphrase.clone(),
+                stream_id: stream_id.clone(),
                 reduced_bitrate_detection: false,
                 unrecovered_packets_detection: false,
             })]
@@ -808,17 +1474,42 @@ fn create(client: EdgeClient, new_input: NewInput) {
             ref appliance,
             ref interface,
             port,
+            ref settings,
         }) => {
-            let interface = get_physical_port(&client, appliance, interface);
+            let interface = get_physical_port(client, appliance, interface);
             vec![NewInputPort::Srt(SrtInputPort::Listener {
                 physical_port: interface.id.to_owned(),
                 local_ip: interface.addresses[0].address.to_owned(),
                 local_port: port,
 
-                latency: 120,
+                latency: settings.latency,
+                pbkeylen: settings.keylen.clone(),
+                passphrase: settings.passphrase.clone(),
                 reduced_bitrate_detection: false,
                 unrecovered_packets_detection: false,
-                whitelist_cidr_block: Some(vec!["0.0.0.0/0".to_owned()]),
+                whitelist_cidr_block: Some(settings.whitelist.clone()),
+            })]
+        }
+        NewInputMode::Srt(NewSrtInputMode::Rendezvous {
+            ref appliance,
+            ref interface,
+            ref address,
+            port,
+            ref settings,
+        }) => {
+            let interface = get_physical_port(client, appliance, interface);
+            vec![NewInputPort::Srt(SrtInputPort::Rendezvous {
+                physical_port: interface.id.to_owned(),
+                local_ip: interface.addresses[0].address.to_owned(),
+                remote_ip: address.to_owned(),
+                remote_port: port,
+
+                latency: settings.latency,
+                pbkeylen: settings.keylen.clone(),
+                passphrase: settings.passphrase.clone(),
+                reduced_bitrate_detection: false,
+                unrecovered_packets_detection: false,
+                whitelist_cidr_block: Some(settings.whitelist.clone()),
             })]
         }
         NewInputMode::Rist(NewRistInputMode {
@@ -826,7 +1517,7 @@ fn create(client: EdgeClient, new_input: NewInput) {
             ref interface,
             port,
         }) => {
-            let interface = get_physical_port(&client, appliance, interface);
+            let interface = get_physical_port(client, appliance, interface);
             vec![NewInputPort::Rist(RistInputPort {
                 physical_port: interface.id.to_owned(),
                 address: interface
@@ -841,7 +1532,7 @@ fn create(client: EdgeClient, new_input: NewInput) {
             })]
         }
         NewInputMode::Sdi(ref sdi) => {
-            let interface = get_physical_port(&client, &sdi.appliance, &sdi.interface);
+            let interface = get_physical_port(client, &sdi.appliance, &sdi.interface);
             vec![NewInputPort::Sdi(SdiInputPort {
                 copies: 1,
                 physical_port: interface.id.to_owned(),
@@ -859,7 +1550,7 @@ fn create(client: EdgeClient, new_input: NewInput) {
             })]
         }
         NewInputMode::Generator(ref generator) => {
-            let interface = get_physical_port(&client, &generator.appliance, "lo");
+            let interface = get_physical_port(client, &generator.appliance, "lo");
             vec![NewInputPort::Generator(GeneratorInputPort {
                 copies: 1,
                 physical_port: interface.id.to_owned(),
@@ -870,62 +1561,6 @@ fn create(client: EdgeClient, new_input: NewInput) {
             })]
         }
         NewInputMode::Derived(_) => Vec::new(),
-    };
-
-    let derive_from = if let NewInputMode::Derived(derived) = new_input.mode {
-        let parent = client
-            .find_inputs(&derived.parent)
-            .expect("Failed to list inputs")
-            .into_iter()
-            .find(|i| i.name == derived.parent)
-            .expect("Could not find parent input");
-        Some(DerivableInputSource {
-            parent_input: parent.id,
-            delay: 1000,
-            ingest_transform: Some(IngestTransform::MptsDemuxTransform {
-                services: vec![1], // TODO
-                pid_map: Some(PidMap {
-                    rules: derived
-                        .pid_rules
-                        .iter()
-                        .map(|r| match r {
-                            PIDRule::Map(from, to) => crate::edge::PIDRule::Map {
-                                pid: *from,
-                                dest_pid: *to,
-                            },
-                            PIDRule::Delete(pid) => crate::edge::PIDRule::Delete { pid: *pid },
-                            PIDRule::SetNull(pid) => crate::edge::PIDRule::SetNull { pid: *pid },
-                        })
-                        .collect(),
-                }),
-            }),
-        })
-    } else {
-        None
-    };
-
-    if let Err(e) = client.create_input(crate::edge::NewInput {
-        name: new_input.name,
-        tr101290_enabled: true,
-        broadcast_standard: "dvb".to_owned(),
-        thumbnail_mode: match new_input.thumbnails {
-            ThumbnailMode::Core => crate::edge::ThumbnailMode::Core,
-            ThumbnailMode::Edge => crate::edge::ThumbnailMode::Edge,
-            ThumbnailMode::None => crate::edge::ThumbnailMode::None,
-        },
-        video_preview_mode: if let ThumbnailMode::Core = new_input.thumbnails {
-            "on demand".to_owned()
-        } else {
-            "off".to_owned()
-        },
-        admin_status: InputAdminStatus::On,
-        ports,
-        buffer_size: 6_000,
-        max_bitrate: None,
-        derive_from,
-    }) {
-        eprintln!("Failed to create input: {}", e);
-        process::exit(1);
     }
 }
 
@@ -934,13 +1569,26 @@ fn get_physical_port(
     appliance: &str,
     interface: &str,
 ) -> AppliancePhysicalPort {
+    match find_physical_port(client, appliance, interface) {
+        Ok(port) => port,
+        Err(e) => {
+            println!("{:#}", e);
+            process::exit(1);
+        }
+    }
+}
+
+pub(crate) fn find_physical_port(
+    client: &EdgeClient,
+    appliance: &str,
+    interface: &str,
+) -> anyhow::Result<AppliancePhysicalPort> {
     let appl = match client.find_appliances(appliance) {
         Ok(appls) if appls.is_empty() => {
-            println!("Could not find appliance {}", appliance);
-            process::exit(1);
+            return Err(anyhow!("Could not find appliance {}", appliance));
         }
         Ok(appls) if appls.len() > 1 => {
-            println!(
+            return Err(anyhow!(
                 "Found more than one appliance matching {}: {}",
                 appliance,
                 appls
@@ -948,29 +1596,163 @@ fn get_physical_port(
                     .map(|a| a.name)
                     .collect::<Vec<_>>()
                     .join(", ")
-            );
-            process::exit(1);
+            ));
         }
         Ok(mut appls) => appls.pop().unwrap(),
         Err(e) => {
-            println!("Failed to find appliance {}: {}", appliance, e);
-            process::exit(1);
+            return Err(anyhow!("Failed to find appliance {}: {}", appliance, e));
         }
     };
-    match appl
-        .physical_ports
+    appl.physical_ports
         .into_iter()
         .find(|p| p.name == interface)
-    {
-        Some(interface) => interface,
-        None => {
-            println!(
+        .ok_or_else(|| {
+            anyhow!(
                 "Failed to find interface {} on appliance {}",
-                interface, appl.name
-            );
-            process::exit(1);
+                interface,
+                appl.name
+            )
+        })
+}
+
+fn update(client: &EdgeClient, name: &str, args: &ArgMatches) -> anyhow::Result<()> {
+    let mut input =
+        manifest::find_input(client, name)?.ok_or_else(|| anyhow!("Input not found"))?;
+
+    if let Some(new_name) = args.get_one::<String>("rename") {
+        input.name = new_name.to_owned();
+    }
+    if args.get_flag("enable") {
+        input.admin_status = InputAdminStatus::On;
+    }
+    if args.get_flag("disable") {
+        input.admin_status = InputAdminStatus::Off;
+    }
+    match args.get_one::<String>("thumbnail").map(|s| s.as_str()) {
+        Some("edge") => input.thumbnail_mode = crate::edge::ThumbnailMode::Edge,
+        Some("core") => input.thumbnail_mode = crate::edge::ThumbnailMode::Core,
+        Some("none") => input.thumbnail_mode = crate::edge::ThumbnailMode::None,
+        _ => {}
+    }
+    if let Some(buffer_size) = args.get_one::<u32>("buffer-size") {
+        input.buffer_size = *buffer_size;
+    }
+    if let Some(enabled) = args.get_one::<bool>("tr101290") {
+        input.tr101290_enabled = *enabled;
+    }
+
+    let physical_port = match (
+        args.get_one::<String>("appliance"),
+        args.get_one::<String>("interface"),
+    ) {
+        (Some(appliance), Some(interface)) => {
+            Some(find_physical_port(client, appliance, interface)?.id)
+        }
+        (None, Some(interface)) => {
+            // Stay on the same appliance
+            let appliance = match input.appliances.as_slice() {
+                [appliance] => appliance.name.to_owned(),
+                _ => return Err(anyhow!("--appliance is required for this input")),
+            };
+            Some(find_physical_port(client, &appliance, interface)?.id)
+        }
+        _ => None,
+    };
+    let port = args.get_one::<u16>("port");
+    let multicast = args.get_one::<String>("multicast");
+    if physical_port.is_some() || port.is_some() || multicast.is_some() {
+        let ports = input.ports.get_or_insert_with(Vec::new);
+        let [input_port] = ports.as_mut_slice() else {
+            return Err(anyhow!(
+                "Only inputs with a single port can be changed with flags, use `edgectl input edit`"
+            ));
+        };
+        if let Some(physical_port) = physical_port {
+            input_port.physical_port = physical_port;
+        }
+        if let Some(port) = port {
+            let key = match input_port.mode.as_str() {
+                "rtp" | "udp" | "rist" => "port",
+                "srt" if input_port.settings.contains_key("localPort") => "localPort",
+                "srt" => "remotePort",
+                mode => return Err(anyhow!("Cannot change the port of {} inputs", mode)),
+            };
+            input_port.settings.insert(key.to_owned(), (*port).into());
+        }
+        if let Some(multicast) = multicast {
+            if !matches!(input_port.mode.as_str(), "rtp" | "udp") {
+                return Err(anyhow!(
+                    "The multicast flag is not supported with mode {}",
+                    input_port.mode
+                ));
+            }
+            input_port
+                .settings
+                .insert("multicastAddress".to_owned(), multicast.as_str().into());
+        }
+    }
+
+    client
+        .update_input(&input)
+        .context("Failed to update input")?;
+    println!("Updated input {}", input.name);
+    Ok(())
+}
+
+fn edit(client: &EdgeClient, name: &str) -> anyhow::Result<()> {
+    let input = manifest::find_input(client, name)?.ok_or_else(|| anyhow!("Input not found"))?;
+    let Some(mut edited) = editor::edit(&input)? else {
+        println!("Input {} unchanged", name);
+        return Ok(());
+    };
+    // The ID identifies the input to update and cannot be changed
+    edited.id = input.id;
+    client
+        .update_input(&edited)
+        .context("Failed to update input")?;
+    println!("Updated input {}", edited.name);
+    Ok(())
+}
+
+fn set_admin_status(
+    client: &EdgeClient,
+    name: &str,
+    admin_status: InputAdminStatus,
+) -> anyhow::Result<()> {
+    let inputs = if glob::is_pattern(name) {
+        client
+            .query_inputs(glob::query(name))
+            .context("Failed to list inputs")?
+            .into_iter()
+            .filter(|i| glob::matches(name, &i.name))
+            .collect::<Vec<_>>()
+    } else {
+        manifest::find_input(client, name)?.into_iter().collect()
+    };
+    if inputs.is_empty() {
+        return Err(anyhow!("Input not found"));
+    }
+    // Keep going when a pattern matches several inputs and one of them fails
+    let mut failed = 0;
+    for input in &inputs {
+        if let Err(e) = client.set_input_admin_status(&input.id, admin_status.clone()) {
+            eprintln!("Failed to update input {}: {}", input.name, e);
+            failed += 1;
+            continue;
+        }
+        match admin_status {
+            InputAdminStatus::On => println!("Enabled input {}", input.name),
+            InputAdminStatus::Off => println!("Disabled input {}", input.name),
         }
     }
+    if failed > 0 {
+        return Err(anyhow!(
+            "{} of {} inputs could not be updated",
+            failed,
+            inputs.len()
+        ));
+    }
+    Ok(())
 }
 
 fn delete(client: &EdgeClient, name: &str) -> anyhow::Result<()> {
@@ -987,3 +1769,50 @@ fn delete(client: &EdgeClient, name: &str) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+// Copies an input to the installation of another context by way of its
+// manifest, so that groups and physical ports are mapped by name. Unless
+// `force` is set, an input that outputs use is not removed from under them.
+fn transfer(
+    client: &EdgeClient,
+    name: &str,
+    to_context: &str,
+    remove_source: bool,
+    force: bool,
+) -> anyhow::Result<()> {
+    let input = manifest::find_input(client, name)?.ok_or_else(|| anyhow!("Input not found"))?;
+    if remove_source && !force {
+        let outputs = client
+            .list_outputs()
+            .context("Failed to list outputs")?
+            .into_iter()
+            .filter(|o| o.input.as_deref() == Some(input.id.as_str()))
+            .map(|o| o.name)
+            .collect::<Vec<_>>();
+        if !outputs.is_empty() {
+            return Err(anyhow!(
+                "Input is used by outputs {}, use --force to move it anyway",
+                outputs.join(", ")
+            ));
+        }
+    }
+    let manifest = InputManifest::from_input(&input, &mut Resolver::new(client))?;
+
+    let destination = new_client_for_context(to_context);
+    if manifest::find_input(&destination, name)?.is_some() {
+        return Err(anyhow!("Input already exists in context {}", to_context));
+    }
+    let new = manifest.to_new_input(&mut Resolver::new(&destination))?;
+    destination
+        .create_input(new)
+        .with_context(|| format!("Failed to create input in context {}", to_context))?;
+    println!("Created input {} in context {}", name, to_context);
+
+    if remove_source {
+        client
+            .delete_input(&input.id)
+            .context("Failed to delete the source input")?;
+        println!("Deleted input {}", name);
+    }
+    Ok(())
+}
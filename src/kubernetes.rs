@@ -1,11 +1,18 @@
 use tabled::{builder::Builder, settings::Style};
 
 use crate::edge::EdgeClient;
+use crate::format::{self, Format};
+use crate::OrExit;
 
-pub fn list_nodes(client: EdgeClient) {
+pub fn list_nodes(client: EdgeClient, format: Option<Format>) {
     let nodes = client
         .list_kubernetes_nodes()
-        .expect("Failed to fetch tunnel list");
+        .or_exit("Failed to fetch tunnel list");
+
+    if let Some(format) = format {
+        format::print(format, &nodes);
+        return;
+    }
 
     let mut builder = Builder::default();
 
@@ -0,0 +1,117 @@
+use std::net::Ipv6Addr;
+
+use clap::{Arg, ArgAction};
+
+use crate::edge::AlarmWithImpact;
+use crate::{green, red};
+
+/// A leg of a redundant input or output, one port on an appliance
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PortSpec {
+    pub appliance: String,
+    pub interface: String,
+    // The destination, the caller target or the multicast source of the leg
+    pub address: Option<String>,
+    pub port: u16,
+}
+
+/// The `--port-spec` argument of `input create` and `output create`
+pub(crate) fn arg() -> Arg {
+    Arg::new("port-spec")
+        .long("port-spec")
+        .action(ArgAction::Append)
+        .value_parser(parse)
+        .conflicts_with_all(["appliance", "interface", "port", "destination"])
+        .help("A leg of a redundant input or output in format appliance:interface:port, or appliance:interface:address:port with the destination or multicast address of the leg, IPv6 addresses in brackets as in [ff15::1]:4000. Repeat for every leg")
+}
+
+fn parse(spec: &str) -> Result<PortSpec, String> {
+    const FORMAT: &str = "expected appliance:interface:port or appliance:interface:address:port";
+    let mut parts = spec.splitn(3, ':');
+    let (Some(appliance), Some(interface), Some(rest)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(FORMAT.to_owned());
+    };
+    let (address, port) = match rest.rsplit_once(':') {
+        Some((address, port)) => (Some(address), port),
+        None => (None, rest),
+    };
+    // IPv6 addresses contain colons themselves, so they go in brackets
+    let address = match address {
+        Some(a) if a.starts_with('[') => Some(
+            a.strip_prefix('[')
+                .and_then(|a| a.strip_suffix(']'))
+                .filter(|a| a.parse::<Ipv6Addr>().is_ok())
+                .ok_or_else(|| format!("{} is not an IPv6 address in brackets", a))?,
+        ),
+        Some(a) if a.contains(':') => {
+            return Err(format!("{}, with IPv6 addresses in brackets", FORMAT))
+        }
+        a => a,
+    };
+    if appliance.is_empty() || interface.is_empty() || address.is_some_and(|a| a.is_empty()) {
+        return Err("the appliance, interface and address cannot be empty".to_owned());
+    }
+    let port = port
+        .parse::<u16>()
+        .ok()
+        .filter(|port| *port > 0)
+        .ok_or_else(|| format!("{} is not a port between 1 and 65535", port))?;
+
+    Ok(PortSpec {
+        appliance: appliance.to_string(),
+        interface: interface.to_string(),
+        address: address.map(|a| a.to_string()),
+        port,
+    })
+}
+
+/// The state of the leg on `physical_port` from the active alarms of its
+/// input or output, unknown if the alarms could not be listed
+pub(crate) fn state(alarms: Option<&[AlarmWithImpact]>, physical_port: &str) -> String {
+    let Some(alarms) = alarms else {
+        return "unknown".to_owned();
+    };
+    let causes = alarms
+        .iter()
+        .filter(|a| a.physical_port_id.as_deref() == Some(physical_port))
+        .map(|a| a.alarm_cause.as_str())
+        .collect::<Vec<_>>();
+    if causes.is_empty() {
+        green!("✓")
+    } else {
+        format!("{} {}", red!("✗"), causes.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_port_specs() {
+        assert_eq!(
+            parse("edge-1:eth0:4000"),
+            Ok(PortSpec {
+                appliance: "edge-1".to_owned(),
+                interface: "eth0".to_owned(),
+                address: None,
+                port: 4000,
+            })
+        );
+        assert_eq!(
+            parse("edge-2:eth1:239.0.0.2:4000").map(|s| s.address),
+            Ok(Some("239.0.0.2".to_owned()))
+        );
+        assert_eq!(
+            parse("edge-3:eth0:[ff15::1]:4000").map(|s| (s.address, s.port)),
+            Ok((Some("ff15::1".to_owned()), 4000))
+        );
+        assert!(parse("edge-3:eth0:ff15::1:4000").is_err());
+        assert!(parse("edge-3:eth0:[ff15::1:4000").is_err());
+        assert!(parse("edge-3:eth0:[239.0.0.2]:4000").is_err());
+        assert!(parse("edge-1:eth0").is_err());
+        assert!(parse("edge-1:eth0:0").is_err());
+        assert!(parse(":eth0:4000").is_err());
+    }
+}
@@ -0,0 +1,26 @@
+//! A client for the REST API of [Nimbra Edge](https://netinsight.net/nimbra-edge/).
+//!
+//! This is the library behind `edgectl`. Build it with
+//! `default-features = false` to leave out the `cli` and `tui` features and
+//! their dependencies.
+//!
+//! ```no_run
+//! use edge_api::EdgeClient;
+//!
+//! let client = EdgeClient::with_url_and_token("https://edge.example.com", "token")?;
+//! for input in client.list_inputs()? {
+//!     println!("{} {}", input.name, input.admin_status);
+//! }
+//! # Ok::<(), edge_api::EdgeError>(())
+//! ```
+//!
+//! Every request returns an [`EdgeError`] on failure, which tells connection
+//! problems apart from the HTTP status returned by the API.
+
+pub mod edge;
+pub mod http;
+mod parallel;
+pub mod query;
+
+pub use edge::*;
+pub use query::{Pages, Query, SortOrder};
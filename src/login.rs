@@ -1,5 +1,7 @@
 use crate::config::{Config, ContextConfig};
 use crate::edge::{ApiTokenInit, EdgeClient};
+use crate::token;
+use crate::{exit_with, OrExit};
 use chrono::{Duration, Utc};
 use clap::{Arg, ArgMatches, Command};
 use std::env;
@@ -14,9 +16,32 @@ pub fn subcommand() -> Command {
                 .help("The URL of the edge installation"),
         )
         .arg(
-            Arg::new("context")
-                .long("context")
-                .help("Custom name for the context (defaults to hostname from URL)"),
+            Arg::new("ca-file")
+                .long("ca-file")
+                .help("Trust the CA certificates in this PEM file"),
+        )
+        .arg(
+            Arg::new("client-cert")
+                .long("client-cert")
+                .requires("client-key")
+                .help("Authenticate with the client certificate in this PEM file"),
+        )
+        .arg(
+            Arg::new("client-key")
+                .long("client-key")
+                .requires("client-cert")
+                .help("The PKCS #8 key of the client certificate, in PEM"),
+        )
+        .arg(
+            Arg::new("insecure-skip-verify")
+                .long("insecure-skip-verify")
+                .num_args(0)
+                .help("Do not verify the server certificate, only meant for labs"),
+        )
+        .arg(
+            Arg::new("proxy")
+                .long("proxy")
+                .help("Connect through this HTTP(S) or SOCKS proxy"),
         )
         .subcommand(Command::new("status").about("Show current user information"))
 }
@@ -26,7 +51,12 @@ pub fn run(args: &ArgMatches) {
         Some(("status", _)) => status(),
         None => {
             let url = args.get_one::<String>("url").cloned();
-            let context_name = args.get_one::<String>("context").cloned();
+            // The global --context names the context to create or update,
+            // defaulting to the hostname from the URL
+            let context_name = args
+                .get_one::<String>("context")
+                .cloned()
+                .or_else(|| env::var("EDGE_CONTEXT").ok());
 
             let (url, username, password, context_name) = if io::stdin().is_terminal() {
                 let conf = Config::load();
@@ -91,10 +121,13 @@ pub fn run(args: &ArgMatches) {
                     std::process::exit(1);
                 });
 
-                (url, username, password, "default".to_owned())
+                let context_name = context_name.unwrap_or_else(|| "default".to_owned());
+
+                (url, username, password, context_name)
             };
 
-            login(&url, &username, &password, &context_name)
+            let settings = settings(args, &context_name);
+            login(&url, &username, &password, &context_name, settings)
         }
         _ => {
             eprintln!("Unknown subcommand");
@@ -124,23 +157,52 @@ fn prompt_or_env(prompt: &str, default: Option<String>) -> String {
     input.to_string()
 }
 
-fn login(url: &str, username: &str, password: &str, context_name: &str) {
-    let client = EdgeClient::with_url(url);
+/// The connection settings of the context, from the flags or from the
+/// existing context when logging in again
+fn settings(args: &ArgMatches, context_name: &str) -> ContextConfig {
+    let mut settings = Config::load()
+        .contexts
+        .get(context_name)
+        .cloned()
+        .unwrap_or_default();
+
+    // Saved as absolute paths so that they work from any directory
+    let path = |name: &str| {
+        args.get_one::<String>(name).map(|path| {
+            std::path::absolute(path)
+                .map(|p| p.to_string_lossy().into_owned())
+                .unwrap_or(path.to_owned())
+        })
+    };
+    if let Some(ca_file) = path("ca-file") {
+        settings.ca_file = Some(ca_file);
+    }
+    if let (Some(cert), Some(key)) = (path("client-cert"), path("client-key")) {
+        settings.client_cert = Some(cert);
+        settings.client_key = Some(key);
+    }
+    if args.get_flag("insecure-skip-verify") {
+        settings.insecure_skip_verify = true;
+    }
+    if let Some(proxy) = args.get_one::<String>("proxy") {
+        settings.proxy = Some(proxy.to_owned());
+    }
+    settings
+}
+
+fn login(url: &str, username: &str, password: &str, context_name: &str, settings: ContextConfig) {
+    let client = EdgeClient::with_url_and_options(url, &settings.http_options())
+        .or_exit("Failed to connect");
 
     let user = match client.login(username.to_owned(), password.to_owned()) {
         Ok(user) => user,
         Err(e) => {
-            eprintln!("Failed to authenticate: {}", e);
-            std::process::exit(1);
+            exit_with(anyhow::Error::from(e).context("Failed to authenticate"));
         }
     };
 
-    let hostname = env::var("HOSTNAME")
-        .ok()
-        .unwrap_or(gethostname::gethostname().to_string_lossy().to_string());
-    let timestamp = Utc::now().format("%Y%m%d%H%M%S").to_string();
-    let token_name = format!("edgectl-{}-{}", hostname, timestamp);
-    let expires_at = Utc::now() + Duration::days(90);
+    let token_name = token::new_token_name();
+    let expires_at = Utc::now() + Duration::days(token::TOKEN_LIFETIME_DAYS);
 
     let token_init = ApiTokenInit {
         name: token_name.clone(),
@@ -152,8 +214,7 @@ fn login(url: &str, username: &str, password: &str, context_name: &str) {
     let token = match client.create_api_token(token_init) {
         Ok(token) => token,
         Err(e) => {
-            eprintln!("Failed to create API token: {}", e);
-            std::process::exit(1);
+            exit_with(anyhow::Error::from(e).context("Failed to create API token"));
         }
     };
 
@@ -162,14 +223,15 @@ fn login(url: &str, username: &str, password: &str, context_name: &str) {
         token: token.token.unwrap_or_default(),
         token_name: token_name.clone(),
         username: user.username.clone(),
+        expires_at: Some(token.expires_at),
+        ..settings
     };
 
     let mut config = Config::load();
     config.add_context(context_name.to_owned(), context);
 
     if let Err(e) = config.save() {
-        eprintln!("Failed to save configuration: {}", e);
-        std::process::exit(1);
+        exit_with(e.context("Failed to save configuration"));
     }
 
     println!("Logged in as {}", username);
@@ -1,30 +1,84 @@
 mod alarm;
 mod appliance;
+mod apply;
 mod buildinfo;
 mod cli;
+mod client;
 mod colors;
 mod config;
 mod context;
-mod edge;
+mod credentials;
+mod diff;
+mod editor;
+mod export;
+mod fanout;
+mod filter;
+mod format;
+mod glob;
 mod group;
 mod group_list;
 mod health;
 mod input;
 mod kubernetes;
+mod legs;
 mod login;
+mod manifest;
 mod node;
 mod output;
 mod output_list;
 mod region;
 mod settings;
+mod srt;
 mod token;
+#[cfg(feature = "tui")]
 mod tui;
 mod tunnels;
 
+use std::time::Duration;
 use std::{env, process};
 
 use clap_complete::CompleteEnv;
-use edge::EdgeClient;
+use edge::{EdgeClient, EdgeError};
+use edge_api::{edge, http, query};
+
+// Exit codes shared by all commands, clap exits with 2 on usage errors
+const EXIT_FAILURE: i32 = 1;
+const EXIT_UNAUTHORIZED: i32 = 3;
+const EXIT_NOT_FOUND: i32 = 4;
+const EXIT_CONFLICT: i32 = 5;
+const EXIT_UNAVAILABLE: i32 = 6;
+
+/// The exit code for an error, depending on the Edge API error behind it
+pub(crate) fn exit_code(e: &anyhow::Error) -> i32 {
+    match e.chain().find_map(|e| e.downcast_ref::<EdgeError>()) {
+        Some(EdgeError::Unauthorized(..)) => EXIT_UNAUTHORIZED,
+        Some(EdgeError::NotFound(..)) => EXIT_NOT_FOUND,
+        Some(EdgeError::Conflict(..)) => EXIT_CONFLICT,
+        Some(EdgeError::RequestError(_) | EdgeError::ServerError(..)) => EXIT_UNAVAILABLE,
+        _ => EXIT_FAILURE,
+    }
+}
+
+/// Prints the error with its causes and exits with its exit code
+pub(crate) fn exit_with(e: anyhow::Error) -> ! {
+    eprintln!("{:#}", e);
+    let code = exit_code(&e);
+    if code == EXIT_UNAUTHORIZED {
+        eprintln!("Run 'edgectl login' if the token has expired");
+    }
+    process::exit(code)
+}
+
+pub(crate) trait OrExit<T> {
+    /// Returns the value, or prints `what` with the error and exits
+    fn or_exit(self, what: &str) -> T;
+}
+
+impl<T, E: Into<anyhow::Error>> OrExit<T> for Result<T, E> {
+    fn or_exit(self, what: &str) -> T {
+        self.unwrap_or_else(|e| exit_with(e.into().context(what.to_owned())))
+    }
+}
 
 fn main() {
     CompleteEnv::with_factory(cli::build)
@@ -32,6 +86,33 @@ fn main() {
         .complete();
 
     let matches = cli::build().get_matches();
+    if let Some(context) = matches.get_one::<String>("context") {
+        client::set_context(context);
+    }
+    http::set_verbose(matches.get_flag("verbose"));
+    http::set_flags(http::Options {
+        connect_timeout: matches.get_one::<Duration>("connect-timeout").copied(),
+        request_timeout: matches.get_one::<Duration>("request-timeout").copied(),
+        retries: matches.get_one::<u32>("retries").copied(),
+        ..Default::default()
+    });
+
+    if let Some(contexts) = fanout::contexts(&matches) {
+        match matches.subcommand() {
+            Some(("alarm", subcmd)) if subcmd.subcommand_name() == Some("list") => {
+                alarm::list_contexts(subcmd.subcommand_matches("list").unwrap(), &contexts)
+            }
+            Some(("input", subcmd)) if subcmd.subcommand_name() == Some("list") => {
+                input::list_contexts(subcmd.subcommand_matches("list").unwrap(), &contexts)
+            }
+            Some(("health", _)) => health::run_contexts(&contexts),
+            _ => {
+                eprintln!("--all-contexts and --contexts only work with alarm list, health and input list");
+                process::exit(1);
+            }
+        }
+        return;
+    }
 
     match matches.subcommand() {
         Some(("alarm", subcmd)) => alarm::run(subcmd),
@@ -39,6 +120,9 @@ fn main() {
         Some(("output", subcmd)) => output::run(subcmd),
         Some(("output-list", subcmd)) => output_list::run(subcmd),
         Some(("appliance", subcmd)) => appliance::run(subcmd),
+        Some(("apply", subcmd)) => apply::run(subcmd),
+        Some(("diff", subcmd)) => diff::run(subcmd),
+        Some(("export", subcmd)) => export::run(subcmd),
         Some(("group", subcmd)) => group::run(subcmd),
         Some(("group-list", subcmd)) => group_list::run(subcmd),
         Some(("region", subcmd)) => region::run(subcmd),
@@ -49,6 +133,7 @@ fn main() {
         Some(("login", subcmd)) => login::run(subcmd),
         Some(("token", subcmd)) => token::run(subcmd),
         Some(("context", subcmd)) => context::run(subcmd),
+        #[cfg(feature = "tui")]
         Some(("tui", _)) => {
             if let Err(e) = tui::run() {
                 eprintln!("Error running TUI: {}", e);
@@ -60,7 +145,8 @@ fn main() {
                 env::var("EDGE_URL")
                     .expect("missing environment variable: EDGE_URL")
                     .as_ref(),
-            );
+            )
+            .or_exit("Failed to connect");
             buildinfo::show(client)
         }
         Some((cmd, _)) => {
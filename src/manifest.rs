@@ -0,0 +1,941 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+
+use anyhow::{anyhow, Context};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::edge::{
+    AppliancePhysicalPort, DerivableInputSource, EdgeClient, ExternalRegionMode, Group,
+    GroupRecipientList, IngestTransform, Input, InputAdminStatus, InputPort, NewInput,
+    NewInputPort, NewOutput, Output, OutputAdminStatus, OutputDelayMode, OutputPort,
+    OutputRecipientList, OutputRedundancyMode, Region, ThumbnailMode,
+};
+use crate::input::{check_pid_rules, find_physical_port};
+
+/// A declarative description of an Edge object. The field names and values are
+/// the ones used by the Edge API, except that other objects are referenced by
+/// name instead of by ID so that manifests can be shared between installations.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(tag = "kind")]
+pub(crate) enum Manifest {
+    Region(RegionManifest),
+    Group(GroupManifest),
+    Input(InputManifest),
+    Output(OutputManifest),
+    OutputList(OutputListManifest),
+    GroupList(GroupListManifest),
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RegionManifest {
+    pub name: String,
+    #[serde(default = "default_region_mode")]
+    pub external: ExternalRegionMode,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GroupManifest {
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct InputManifest {
+    pub name: String,
+    // The owning group, defaults to the group of the user
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    #[serde(default = "default_input_admin_status")]
+    pub admin_status: InputAdminStatus,
+    #[serde(default = "default_buffer_size")]
+    pub buffer_size: u32,
+    #[serde(default = "default_thumbnail_mode")]
+    pub thumbnail_mode: ThumbnailMode,
+    #[serde(default = "default_true")]
+    pub tr101290_enabled: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ports: Vec<PortManifest>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub derive_from: Option<DeriveFromManifest>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DeriveFromManifest {
+    // The name of the parent input
+    pub parent_input: String,
+    #[serde(default = "default_derive_delay")]
+    pub delay: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ingest_transform: Option<IngestTransform>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct OutputManifest {
+    pub name: String,
+    // The owning group, defaults to the group of the user
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    // The name of the input
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input: Option<String>,
+    #[serde(default = "default_output_admin_status")]
+    pub admin_status: OutputAdminStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redundancy_mode: Option<OutputRedundancyMode>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delay: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delay_mode: Option<OutputDelayMode>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    pub ports: Vec<PortManifest>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct OutputListManifest {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    // The owning group, defaults to the system group
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    // The names of the outputs in the list
+    #[serde(default)]
+    pub outputs: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GroupListManifest {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    // The owning group, defaults to the system group
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    // The names of the groups in the list
+    #[serde(default)]
+    pub groups: Vec<String>,
+}
+
+/// An input or output port. The physical port is given as appliance and
+/// interface name, everything else is passed to the Edge API as is.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PortManifest {
+    pub appliance: String,
+    pub interface: String,
+    #[serde(flatten)]
+    pub settings: Map<String, Value>,
+}
+
+// Port fields managed by the server that should never end up in a manifest
+const SERVER_PORT_FIELDS: [&str; 4] = ["id", "physicalPort", "appliance", "internalStreamId"];
+
+fn default_region_mode() -> ExternalRegionMode {
+    ExternalRegionMode::External
+}
+
+fn default_input_admin_status() -> InputAdminStatus {
+    InputAdminStatus::On
+}
+
+fn default_output_admin_status() -> OutputAdminStatus {
+    OutputAdminStatus::On
+}
+
+fn default_buffer_size() -> u32 {
+    6_000
+}
+
+fn default_thumbnail_mode() -> ThumbnailMode {
+    ThumbnailMode::Edge
+}
+
+fn default_derive_delay() -> u32 {
+    1000
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Manifest {
+    pub(crate) fn kind(&self) -> &'static str {
+        match self {
+            Self::Region(_) => "region",
+            Self::Group(_) => "group",
+            Self::Input(_) => "input",
+            Self::Output(_) => "output",
+            Self::OutputList(_) => "output-list",
+            Self::GroupList(_) => "group-list",
+        }
+    }
+
+    pub(crate) fn name(&self) -> &str {
+        match self {
+            Self::Region(r) => &r.name,
+            Self::Group(g) => &g.name,
+            Self::Input(i) => &i.name,
+            Self::Output(o) => &o.name,
+            Self::OutputList(l) => &l.name,
+            Self::GroupList(l) => &l.name,
+        }
+    }
+
+    // Objects have to be created after the objects they reference
+    fn order(&self) -> u8 {
+        match self {
+            Self::Region(_) => 0,
+            Self::Group(_) => 1,
+            Self::Input(InputManifest {
+                derive_from: None, ..
+            }) => 2,
+            Self::Input(_) => 3,
+            Self::Output(_) => 4,
+            Self::OutputList(_) | Self::GroupList(_) => 5,
+        }
+    }
+
+    /// Removes everything that `desired` leaves up to the server from a
+    /// manifest of a live object, so that the two can be compared
+    pub(crate) fn normalize(&mut self, desired: &Manifest) {
+        match (self, desired) {
+            (Self::Input(live), Self::Input(desired)) => {
+                if desired.group.is_none() {
+                    live.group = None;
+                }
+                if let (Some(live), Some(desired)) = (&mut live.derive_from, &desired.derive_from) {
+                    if desired.ingest_transform.is_none() {
+                        live.ingest_transform = None;
+                    }
+                }
+                normalize_ports(&mut live.ports, &desired.ports);
+            }
+            (Self::Output(live), Self::Output(desired)) => {
+                if desired.group.is_none() {
+                    live.group = None;
+                }
+                if desired.redundancy_mode.is_none() {
+                    live.redundancy_mode = None;
+                }
+                if desired.delay.is_none() {
+                    live.delay = None;
+                }
+                if desired.delay_mode.is_none() {
+                    live.delay_mode = None;
+                }
+                normalize_ports(&mut live.ports, &desired.ports);
+            }
+            (Self::OutputList(live), Self::OutputList(desired)) => {
+                if desired.group.is_none() {
+                    live.group = None;
+                }
+                if desired.description.is_none() {
+                    live.description = None;
+                }
+                normalize_members(&mut live.outputs, &desired.outputs);
+            }
+            (Self::GroupList(live), Self::GroupList(desired)) => {
+                if desired.group.is_none() {
+                    live.group = None;
+                }
+                if desired.description.is_none() {
+                    live.description = None;
+                }
+                normalize_members(&mut live.groups, &desired.groups);
+            }
+            _ => {}
+        }
+    }
+
+    pub(crate) fn same_as(&self, other: &Manifest) -> bool {
+        serde_json::to_value(self).ok() == serde_json::to_value(other).ok()
+    }
+}
+
+// List members are unordered, so follow the order of the manifest
+fn normalize_members(live: &mut [String], desired: &[String]) {
+    live.sort_by_key(|m| desired.iter().position(|d| d == m).unwrap_or(usize::MAX));
+}
+
+fn normalize_ports(live: &mut [PortManifest], desired: &[PortManifest]) {
+    for (live, desired) in live.iter_mut().zip(desired) {
+        live.settings
+            .retain(|key, _| desired.settings.contains_key(key));
+    }
+}
+
+/// Loads all manifests from a file, a directory of `.yaml`, `.yml` and `.json`
+/// files or, if `path` is `-`, from stdin. A file can hold several YAML
+/// documents.
+pub(crate) fn load(path: &str) -> anyhow::Result<Vec<Manifest>> {
+    if path == "-" {
+        let mut content = String::new();
+        io::stdin()
+            .read_to_string(&mut content)
+            .context("Failed to read manifests from stdin")?;
+        return parse(&content).context("Failed to parse manifests from stdin");
+    }
+
+    let path = Path::new(path);
+    if !path.is_dir() {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        return parse(&content).with_context(|| format!("Failed to parse {}", path.display()));
+    }
+
+    let mut files = fs::read_dir(path)
+        .with_context(|| format!("Failed to read directory {}", path.display()))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| {
+            p.is_file()
+                && matches!(
+                    p.extension().and_then(|e| e.to_str()),
+                    Some("yaml" | "yml" | "json")
+                )
+        })
+        .collect::<Vec<_>>();
+    files.sort();
+
+    let mut manifests = Vec::new();
+    for file in files {
+        manifests
+            .extend(load(file.to_str().ok_or_else(|| {
+                anyhow!("Invalid file name {}", file.display())
+            })?)?);
+    }
+    Ok(manifests)
+}
+
+fn parse(content: &str) -> anyhow::Result<Vec<Manifest>> {
+    Ok(serde_saphyr::from_multiple::<Manifest>(content)?)
+}
+
+/// Sorts manifests so that referenced objects are handled first, keeping the
+/// order of the manifests otherwise
+pub(crate) fn sort(manifests: &mut [Manifest]) {
+    manifests.sort_by_key(|m| m.order());
+}
+
+pub(crate) fn find_input(client: &EdgeClient, name: &str) -> anyhow::Result<Option<Input>> {
+    Ok(client
+        .find_inputs(name)
+        .context("Failed to find inputs")?
+        .into_iter()
+        .find(|i| i.name == name))
+}
+
+pub(crate) fn find_output(client: &EdgeClient, name: &str) -> anyhow::Result<Option<Output>> {
+    Ok(client
+        .find_outputs(name)
+        .context("Failed to find outputs")?
+        .into_iter()
+        .find(|o| o.name == name))
+}
+
+pub(crate) fn find_group(client: &EdgeClient, name: &str) -> anyhow::Result<Option<Group>> {
+    Ok(client
+        .find_groups(name)
+        .context("Failed to find groups")?
+        .into_iter()
+        .find(|g| g.name == name))
+}
+
+pub(crate) fn find_output_list(
+    client: &EdgeClient,
+    name: &str,
+) -> anyhow::Result<Option<OutputRecipientList>> {
+    Ok(client
+        .find_output_recipient_lists(name)
+        .context("Failed to find output lists")?
+        .into_iter()
+        .find(|l| l.name == name))
+}
+
+pub(crate) fn find_group_list(
+    client: &EdgeClient,
+    name: &str,
+) -> anyhow::Result<Option<GroupRecipientList>> {
+    Ok(client
+        .find_group_recipient_lists(name)
+        .context("Failed to find group lists")?
+        .into_iter()
+        .find(|l| l.name == name))
+}
+
+pub(crate) fn find_region(client: &EdgeClient, name: &str) -> anyhow::Result<Option<Region>> {
+    Ok(client
+        .find_region(name)
+        .context("Failed to find regions")?
+        .into_iter()
+        .find(|r| r.name == name))
+}
+
+/// Fetches the object described by `desired` as a manifest normalized for
+/// comparison with it, or `None` if there is no such object
+pub(crate) fn live(
+    client: &EdgeClient,
+    resolver: &mut Resolver,
+    desired: &Manifest,
+) -> anyhow::Result<Option<Manifest>> {
+    let mut live = match desired {
+        Manifest::Region(region) => find_region(client, &region.name)?
+            .map(|r| Manifest::Region(RegionManifest::from_region(&r))),
+        Manifest::Group(group) => find_group(client, &group.name)?
+            .map(|g| Manifest::Group(GroupManifest { name: g.name })),
+        Manifest::Input(input) => match find_input(client, &input.name)? {
+            Some(i) => Some(Manifest::Input(InputManifest::from_input(&i, resolver)?)),
+            None => None,
+        },
+        Manifest::Output(output) => match find_output(client, &output.name)? {
+            Some(o) => Some(Manifest::Output(OutputManifest::from_output(&o, resolver)?)),
+            None => None,
+        },
+        Manifest::OutputList(list) => match find_output_list(client, &list.name)? {
+            Some(l) => Some(Manifest::OutputList(OutputListManifest::from_list(
+                &l, client, resolver,
+            )?)),
+            None => None,
+        },
+        Manifest::GroupList(list) => match find_group_list(client, &list.name)? {
+            Some(l) => Some(Manifest::GroupList(GroupListManifest::from_list(
+                &l, client, resolver,
+            )?)),
+            None => None,
+        },
+    };
+    if let Some(live) = &mut live {
+        live.normalize(desired);
+    }
+    Ok(live)
+}
+
+/// Translates between the names used in manifests and the IDs used by the
+/// Edge API. Successful lookups are cached as manifests tend to reference the
+/// same appliances and groups over and over.
+pub(crate) struct Resolver<'a> {
+    client: &'a EdgeClient,
+    physical_ports: HashMap<(String, String), AppliancePhysicalPort>,
+    physical_port_names: HashMap<String, (String, String)>,
+    groups: HashMap<String, String>,
+    group_names: HashMap<String, String>,
+    input_names: HashMap<String, String>,
+}
+
+impl<'a> Resolver<'a> {
+    pub(crate) fn new(client: &'a EdgeClient) -> Self {
+        Self {
+            client,
+            physical_ports: HashMap::new(),
+            physical_port_names: HashMap::new(),
+            groups: HashMap::new(),
+            group_names: HashMap::new(),
+            input_names: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn physical_port(
+        &mut self,
+        appliance: &str,
+        interface: &str,
+    ) -> anyhow::Result<AppliancePhysicalPort> {
+        let key = (appliance.to_owned(), interface.to_owned());
+        if let Some(port) = self.physical_ports.get(&key) {
+            return Ok(port.clone());
+        }
+        let port = find_physical_port(self.client, appliance, interface)?;
+        self.physical_ports.insert(key, port.clone());
+        Ok(port)
+    }
+
+    pub(crate) fn physical_port_name(&mut self, id: &str) -> anyhow::Result<(String, String)> {
+        if let Some(name) = self.physical_port_names.get(id) {
+            return Ok(name.to_owned());
+        }
+        let port = self
+            .client
+            .get_port(id)
+            .with_context(|| format!("Failed to get physical port {}", id))?;
+        let name = (port.appliance.name, port.name);
+        self.physical_port_names
+            .insert(id.to_owned(), name.to_owned());
+        Ok(name)
+    }
+
+    pub(crate) fn group_id(&mut self, name: &str) -> anyhow::Result<String> {
+        if let Some(id) = self.groups.get(name) {
+            return Ok(id.to_owned());
+        }
+        let group =
+            find_group(self.client, name)?.ok_or_else(|| anyhow!("Group not found: {}", name))?;
+        self.groups.insert(name.to_owned(), group.id.to_owned());
+        Ok(group.id)
+    }
+
+    pub(crate) fn group_name(&mut self, id: &str) -> anyhow::Result<String> {
+        if let Some(name) = self.group_names.get(id) {
+            return Ok(name.to_owned());
+        }
+        let group = self
+            .client
+            .get_group(id)
+            .with_context(|| format!("Failed to get group {}", id))?;
+        self.group_names
+            .insert(id.to_owned(), group.name.to_owned());
+        Ok(group.name)
+    }
+
+    // Inputs may be created while resolving, so only the name lookups are cached
+    pub(crate) fn input_id(&mut self, name: &str) -> anyhow::Result<String> {
+        find_input(self.client, name)?
+            .map(|i| i.id)
+            .ok_or_else(|| anyhow!("Input not found: {}", name))
+    }
+
+    pub(crate) fn input_name(&mut self, id: &str) -> anyhow::Result<String> {
+        if let Some(name) = self.input_names.get(id) {
+            return Ok(name.to_owned());
+        }
+        let input = self
+            .client
+            .get_input(id)
+            .with_context(|| format!("Failed to get input {}", id))?;
+        self.input_names
+            .insert(id.to_owned(), input.name.to_owned());
+        Ok(input.name)
+    }
+}
+
+impl PortManifest {
+    fn from_api(port: Value, resolver: &mut Resolver) -> anyhow::Result<Self> {
+        let Value::Object(mut settings) = port else {
+            return Err(anyhow!("Unexpected port format"));
+        };
+        let physical_port = settings
+            .get("physicalPort")
+            .and_then(|p| p.as_str())
+            .ok_or_else(|| anyhow!("Ports without a physical port are not supported"))?
+            .to_owned();
+        let (appliance, interface) = resolver.physical_port_name(&physical_port)?;
+        for field in SERVER_PORT_FIELDS {
+            settings.remove(field);
+        }
+        Ok(Self {
+            appliance,
+            interface,
+            settings,
+        })
+    }
+
+    // The port as sent to the Edge API, on top of the current port if the
+    // physical port stays the same
+    fn to_api(&self, current: Option<Value>, resolver: &mut Resolver) -> anyhow::Result<Value> {
+        let physical_port = resolver.physical_port(&self.appliance, &self.interface)?.id;
+        let mut port = match current {
+            Some(Value::Object(current))
+                if current.get("physicalPort").and_then(|p| p.as_str())
+                    == Some(physical_port.as_str()) =>
+            {
+                current
+            }
+            _ => Map::new(),
+        };
+        port.extend(self.settings.clone());
+        port.insert("physicalPort".to_owned(), Value::String(physical_port));
+        Ok(Value::Object(port))
+    }
+
+    // The port as an input port. Like `input create`, ports that receive on
+    // an address default to the first address of the interface.
+    fn to_input_api(
+        &self,
+        current: Option<Value>,
+        resolver: &mut Resolver,
+    ) -> anyhow::Result<Value> {
+        let mut port = self.to_api(current, resolver)?;
+        if let Value::Object(ref mut port) = port {
+            port.entry("copies").or_insert(Value::from(1));
+            let mode = port.get("mode").and_then(|m| m.as_str());
+            if matches!(mode, Some("rtp" | "udp" | "rist")) && !port.contains_key("address") {
+                let interface = resolver.physical_port(&self.appliance, &self.interface)?;
+                let address = interface.addresses.first().ok_or_else(|| {
+                    anyhow!(
+                        "Interface {} on {} has no address",
+                        self.interface,
+                        self.appliance
+                    )
+                })?;
+                port.insert(
+                    "address".to_owned(),
+                    Value::from(address.address.to_owned()),
+                );
+            }
+        }
+        Ok(port)
+    }
+
+    // The port as an output port. Outputs are sent to the Edge API as typed
+    // ports, so a setting that does not come out of them as written would
+    // never reach the server and would show up as drift forever.
+    fn to_output_port(
+        &self,
+        current: Option<Value>,
+        resolver: &mut Resolver,
+    ) -> anyhow::Result<OutputPort> {
+        let port = self.to_api(current, resolver)?;
+        let output_port = serde_json::from_value::<OutputPort>(port)?;
+        let sent = serde_json::to_value(&output_port)?;
+        for (key, value) in &self.settings {
+            if sent.get(key) != Some(value) {
+                return Err(anyhow!("Unsupported value for port setting {}", key));
+            }
+        }
+        Ok(output_port)
+    }
+}
+
+impl RegionManifest {
+    pub(crate) fn from_region(region: &Region) -> Self {
+        Self {
+            name: region.name.to_owned(),
+            external: region.external.clone(),
+        }
+    }
+}
+
+impl InputManifest {
+    pub(crate) fn from_input(input: &Input, resolver: &mut Resolver) -> anyhow::Result<Self> {
+        let ports = input
+            .ports
+            .iter()
+            .flatten()
+            .map(|port| PortManifest::from_api(serde_json::to_value(port)?, resolver))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let derive_from = match &input.derive_from {
+            Some(derive_from) => Some(DeriveFromManifest {
+                parent_input: resolver.input_name(&derive_from.parent_input)?,
+                delay: derive_from.delay,
+                ingest_transform: derive_from.ingest_transform.clone(),
+            }),
+            None => None,
+        };
+
+        Ok(Self {
+            name: input.name.to_owned(),
+            group: Some(resolver.group_name(&input.owner)?),
+            admin_status: input.admin_status.clone(),
+            buffer_size: input.buffer_size,
+            thumbnail_mode: input.thumbnail_mode.clone(),
+            tr101290_enabled: input.tr101290_enabled,
+            ports,
+            derive_from,
+        })
+    }
+
+    pub(crate) fn to_new_input(&self, resolver: &mut Resolver) -> anyhow::Result<NewInput> {
+        let ports = self
+            .ports
+            .iter()
+            .map(|port| {
+                let port = port.to_input_api(None, resolver)?;
+                serde_json::from_value::<NewInputPort>(port)
+                    .with_context(|| format!("Invalid port on input {}", self.name))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        Ok(NewInput {
+            name: self.name.to_owned(),
+            tr101290_enabled: self.tr101290_enabled,
+            broadcast_standard: "dvb".to_owned(),
+            thumbnail_mode: self.thumbnail_mode.clone(),
+            video_preview_mode: if let ThumbnailMode::Core = self.thumbnail_mode {
+                "on demand".to_owned()
+            } else {
+                "off".to_owned()
+            },
+            admin_status: self.admin_status.clone(),
+            ports,
+            buffer_size: u16::try_from(self.buffer_size)
+                .with_context(|| format!("Invalid buffer size {}", self.buffer_size))?,
+            max_bitrate: None,
+            owner: self
+                .group
+                .as_deref()
+                .map(|g| resolver.group_id(g))
+                .transpose()?,
+            derive_from: self
+                .derive_from
+                .as_ref()
+                .map(|d| d.to_api(resolver))
+                .transpose()?,
+        })
+    }
+
+    /// Updates `input` to match the manifest, keeping everything the manifest
+    /// leaves up to the server
+    pub(crate) fn update(&self, input: &mut Input, resolver: &mut Resolver) -> anyhow::Result<()> {
+        let current = input.ports.take().unwrap_or_default();
+        let ports = self
+            .ports
+            .iter()
+            .enumerate()
+            .map(|(i, port)| {
+                let current = current.get(i).map(serde_json::to_value).transpose()?;
+                let port = port.to_input_api(current, resolver)?;
+                serde_json::from_value::<InputPort>(port)
+                    .with_context(|| format!("Invalid port on input {}", self.name))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        input.ports = Some(ports);
+        input.admin_status = self.admin_status.clone();
+        input.buffer_size = self.buffer_size;
+        input.thumbnail_mode = self.thumbnail_mode.clone();
+        input.tr101290_enabled = self.tr101290_enabled;
+        if let Some(group) = &self.group {
+            input.owner = resolver.group_id(group)?;
+        }
+        input.derive_from = match &self.derive_from {
+            Some(derive_from) => {
+                let mut new = derive_from.to_api(resolver)?;
+                if new.ingest_transform.is_none() {
+                    new.ingest_transform = input
+                        .derive_from
+                        .take()
+                        .and_then(|current| current.ingest_transform);
+                }
+                Some(new)
+            }
+            None => None,
+        };
+        Ok(())
+    }
+}
+
+impl DeriveFromManifest {
+    fn to_api(&self, resolver: &mut Resolver) -> anyhow::Result<DerivableInputSource> {
+        if let Some(IngestTransform::MptsDemuxTransform {
+            pid_map: Some(pid_map),
+            ..
+        }) = &self.ingest_transform
+        {
+            check_pid_rules(&pid_map.rules).with_context(|| {
+                format!(
+                    "Invalid PID map of the input derived from {}",
+                    self.parent_input
+                )
+            })?;
+        }
+        Ok(DerivableInputSource {
+            parent_input: resolver.input_id(&self.parent_input)?,
+            delay: self.delay,
+            ingest_transform: self.ingest_transform.clone(),
+        })
+    }
+}
+
+impl OutputManifest {
+    pub(crate) fn from_output(output: &Output, resolver: &mut Resolver) -> anyhow::Result<Self> {
+        let ports = output
+            .ports
+            .iter()
+            .map(|port| PortManifest::from_api(serde_json::to_value(port)?, resolver))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        Ok(Self {
+            name: output.name.to_owned(),
+            group: Some(resolver.group_name(&output.group)?),
+            input: output
+                .input
+                .as_deref()
+                .map(|i| resolver.input_name(i))
+                .transpose()?,
+            admin_status: output.admin_status.clone(),
+            redundancy_mode: output.redundancy_mode.clone(),
+            delay: output.delay,
+            delay_mode: output.delay_mode.clone(),
+            tags: output.tags.clone(),
+            ports,
+        })
+    }
+
+    pub(crate) fn to_new_output(&self, resolver: &mut Resolver) -> anyhow::Result<NewOutput> {
+        let ports = self
+            .ports
+            .iter()
+            .map(|port| {
+                port.to_output_port(None, resolver)
+                    .with_context(|| format!("Invalid port on output {}", self.name))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        Ok(NewOutput {
+            name: self.name.to_owned(),
+            admin_status: self.admin_status.clone(),
+            delay: self.delay.map(f64::from),
+            delay_mode: self.delay_mode.clone(),
+            group: self
+                .group
+                .as_deref()
+                .map(|g| resolver.group_id(g))
+                .transpose()?,
+            input: self
+                .input
+                .as_deref()
+                .map(|i| resolver.input_id(i))
+                .transpose()?,
+            ports,
+            redundancy_mode: self.redundancy_mode.clone(),
+            tags: self.tags.clone(),
+        })
+    }
+
+    /// Updates `output` to match the manifest, keeping everything the manifest
+    /// leaves up to the server
+    pub(crate) fn update(
+        &self,
+        output: &mut Output,
+        resolver: &mut Resolver,
+    ) -> anyhow::Result<()> {
+        let current = std::mem::take(&mut output.ports);
+        output.ports = self
+            .ports
+            .iter()
+            .enumerate()
+            .map(|(i, port)| {
+                let current = current.get(i).map(serde_json::to_value).transpose()?;
+                port.to_output_port(current, resolver)
+                    .with_context(|| format!("Invalid port on output {}", self.name))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        output.admin_status = self.admin_status.clone();
+        if let Some(group) = &self.group {
+            output.group = resolver.group_id(group)?;
+        }
+        output.input = self
+            .input
+            .as_deref()
+            .map(|i| resolver.input_id(i))
+            .transpose()?;
+        if self.redundancy_mode.is_some() {
+            output.redundancy_mode = self.redundancy_mode.clone();
+        }
+        if self.delay.is_some() {
+            output.delay = self.delay;
+        }
+        if self.delay_mode.is_some() {
+            output.delay_mode = self.delay_mode.clone();
+        }
+        output.tags = self.tags.clone();
+        Ok(())
+    }
+}
+
+impl OutputListManifest {
+    pub(crate) fn from_list(
+        list: &OutputRecipientList,
+        client: &EdgeClient,
+        resolver: &mut Resolver,
+    ) -> anyhow::Result<Self> {
+        let mut outputs = client
+            .get_output_list_members(&list.id)
+            .context("Failed to get output list members")?
+            .into_iter()
+            .map(|o| o.name)
+            .collect::<Vec<_>>();
+        outputs.sort();
+        Ok(Self {
+            name: list.name.to_owned(),
+            description: list.description.clone(),
+            group: list
+                .group
+                .as_deref()
+                .map(|g| resolver.group_name(g))
+                .transpose()?,
+            outputs,
+        })
+    }
+}
+
+impl GroupListManifest {
+    pub(crate) fn from_list(
+        list: &GroupRecipientList,
+        client: &EdgeClient,
+        resolver: &mut Resolver,
+    ) -> anyhow::Result<Self> {
+        let mut groups = client
+            .get_group_list_members(&list.id)
+            .context("Failed to get group list members")?
+            .into_iter()
+            .map(|g| g.name)
+            .collect::<Vec<_>>();
+        groups.sort();
+        Ok(Self {
+            name: list.name.to_owned(),
+            description: list.description.clone(),
+            group: list
+                .group
+                .as_deref()
+                .map(|g| resolver.group_name(g))
+                .transpose()?,
+            groups,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_multiple_manifests() {
+        let manifests = parse(
+            r#"
+kind: Output
+name: out
+input: in
+ports:
+  - appliance: app
+    interface: eth0
+    mode: udp
+    address: 10.0.0.1
+    port: 4000
+---
+kind: Input
+name: in
+ports:
+  - appliance: app
+    interface: eth0
+    mode: rtp
+    port: 5000
+"#,
+        )
+        .unwrap();
+        assert_eq!(manifests.len(), 2);
+        let Manifest::Output(output) = &manifests[0] else {
+            panic!("expected an output");
+        };
+        assert_eq!(output.input.as_deref(), Some("in"));
+        assert_eq!(output.ports[0].interface, "eth0");
+        assert_eq!(output.ports[0].settings["port"], Value::from(4000));
+        let Manifest::Input(input) = &manifests[1] else {
+            panic!("expected an input");
+        };
+        assert_eq!(input.buffer_size, 6000);
+        assert!(input.tr101290_enabled);
+
+        let mut sorted = manifests.clone();
+        sort(&mut sorted);
+        assert_eq!(sorted[0].kind(), "input");
+    }
+}
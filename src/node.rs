@@ -2,18 +2,23 @@ use clap::{ArgMatches, Command};
 
 use crate::kubernetes;
 
-use crate::edge::new_client;
+use crate::client::new_client;
+use crate::format;
 
 pub(crate) fn subcommand() -> clap::Command {
     Command::new("node")
         .about("Show information about kubernetes nodes")
         .subcommand_required(true)
-        .subcommand(Command::new("list").about("List kubernetes nodes"))
+        .subcommand(
+            Command::new("list")
+                .about("List kubernetes nodes")
+                .arg(format::arg(&["short"])),
+        )
 }
 
 pub(crate) fn run(subcmd: &ArgMatches) {
     match subcmd.subcommand() {
-        Some(("list", _)) => kubernetes::list_nodes(new_client()),
+        Some(("list", args)) => kubernetes::list_nodes(new_client(), format::structured(args)),
         _ => unreachable!("subcommand_required prevents `None` or other options"),
     }
 }
@@ -1,39 +1,56 @@
-use std::collections::BTreeMap;
-use std::fmt;
+use std::collections::{BTreeMap, BTreeSet};
 use std::process;
+use std::thread;
+use std::time::{Duration, Instant};
 
 use anyhow::{anyhow, Context};
 use clap::{Arg, ArgMatches, Command};
+use clap_complete::ArgValueCompleter;
+use serde_json::{Map, Value};
 use tabled::{builder::Builder, settings::Style};
 
+use crate::client::{new_client, new_client_for_context};
+use crate::context::context_name_completer;
 use crate::edge::{
-    new_client, EdgeClient, Group, Input, Output, OutputAdminStatus, OutputHealthState, OutputPort,
-    OutputPortFec, RistOutputPort, RtpOutputPort, SrtCallerOutputPort, SrtKeylen,
-    SrtListenerOutputPort, SrtOutputPort, SrtRateLimiting, UdpOutputPort, ZixiOutputPort,
+    EdgeClient, Group, Input, NewPortAllocation, Output, OutputAdminStatus, OutputDelayMode,
+    OutputHealth, OutputHealthState, OutputPort, OutputPortFec, OutputRedundancyMode,
+    RistOutputPort, RtmpOutputPort, RtpOutputPort, SrtCallerOutputPort, SrtListenerOutputPort,
+    SrtOutputPort, SrtRateLimiting, SrtRendezvousOutputPort, UdpOutputPort, ZixiLink,
+    ZixiOutputPort, ZixiPullOutputPort, ZixiPushOutputPort,
 };
+use crate::editor;
+use crate::filter;
+use crate::format::{self, Format};
+use crate::glob;
+use crate::input::find_physical_port;
+use crate::legs::{self, PortSpec};
+use crate::manifest::{self, OutputManifest, Resolver};
+use crate::query::Query;
+use crate::srt;
+use crate::{exit_with, OrExit};
 use crate::{green, grey, red, yellow};
 
+// How long an output has to stay healthy after switching its input when its
+// health does not change
+const SETTLE_PERIOD: Duration = Duration::from_secs(5);
+
 pub(crate) fn subcommand() -> clap::Command {
     Command::new("output")
         .about("Manage outputs")
         .subcommand_required(true)
         .subcommand(
             Command::new("list")
-                .arg(
-                    Arg::new("output")
-                        .long("output")
-                        .short('o')
-                        .value_parser(["short", "wide"])
-                        .default_value("short")
-                        .help("Change the output format"),
-                ),
+                .arg(format::arg(&["short", "wide"]))
+                .args(filter::args()),
         )
         .subcommand(
-            Command::new("show").arg(
-                Arg::new("name")
-                    .required(true)
-                    .help("The output name to show details for"),
-            ),
+            Command::new("show")
+                .arg(
+                    Arg::new("name")
+                        .required(true)
+                        .help("The output name to show details for"),
+                )
+                .arg(format::arg(&["text"])),
         )
         .subcommand(
             Command::new("create")
@@ -46,7 +63,7 @@ pub(crate) fn subcommand() -> clap::Command {
                     Arg::new("appliance")
                         .short('a')
                         .long("appliance")
-                        .required(true)
+                        .required_unless_present("port-spec")
                         .help("The appliance to create the input on"),
                 )
                 .arg(
@@ -55,14 +72,14 @@ pub(crate) fn subcommand() -> clap::Command {
                         .long("mode")
                         .required(true)
                         .value_parser(clap::builder::PossibleValuesParser::new([
-                            "rtp", "udp", "sdi", "srt", "rist",
+                            "rtp", "udp", "sdi", "srt", "rist", "rtmp", "zixi",
                         ]))
                         .help("The input mode"),
                 )
                 .arg(
                     Arg::new("interface")
                         .long("interface")
-                        .required(true)
+                        .required_unless_present("port-spec")
                         .help("The interface on the appliance to create the input on"),
                 )
                 .arg(
@@ -76,19 +93,12 @@ pub(crate) fn subcommand() -> clap::Command {
                         .short('d')
                         .long("dest")
                         .required(false)
-                        .required_if_eq_any([
-                            ("mode", "rtp"),
-                            ("mode", "udp"),
-                            ("mode", "rist"),
-                            ("caller", "true"),
-                        ])
                         .help("The destination to send the output to in format ip:port, e.g. 198.51.100.12:4000"),
                 )
                 .arg(
                     Arg::new("port")
                         .long("port")
                         .required(false)
-                        .required_if_eq("listener", "true")
                         .value_parser(clap::value_parser!(u16).range(1..))
                         .help("The port to listen on. Only applicable for SRT listeners"),
                 )
@@ -138,6 +148,195 @@ pub(crate) fn subcommand() -> clap::Command {
                     clap::ArgGroup::new("srt_mode")
                         .args(["caller", "listener", "rendezvous"])
                         .required(false)
+                )
+                .args(srt::args())
+                .arg(srt::rate_limit_arg())
+                .arg(legs::arg())
+                .arg(
+                    Arg::new("redundancy-mode")
+                        .long("redundancy-mode")
+                        .value_parser(["none", "failover", "active"])
+                        .help("How the legs of a redundant output are used [default: active with more than one --port-spec]"),
+                )
+                .arg(
+                    Arg::new("url")
+                        .long("url")
+                        .required_if_eq("mode", "rtmp")
+                        .help("The RTMP server to publish to, e.g. rtmp://198.51.100.12/live. Only applicable for RTMP outputs"),
+                )
+                .arg(
+                    Arg::new("stream-key")
+                        .long("stream-key")
+                        .required_if_eq("mode", "rtmp")
+                        .help("The stream key to publish with. Only applicable for RTMP outputs"),
+                )
+                .arg(
+                    Arg::new("push")
+                        .long("push")
+                        .num_args(0)
+                        .help("Push the stream to a Zixi receiver. Only applicable for Zixi outputs."),
+                )
+                .arg(
+                    Arg::new("pull")
+                        .long("pull")
+                        .num_args(0)
+                        .help("Let a Zixi receiver pull the stream. Only applicable for Zixi outputs."),
+                )
+                .group(
+                    clap::ArgGroup::new("zixi_mode")
+                        .args(["push", "pull"])
+                        .required(false)
+                )
+                .arg(
+                    Arg::new("stream-id")
+                        .long("stream-id")
+                        .required_if_eq("mode", "zixi")
+                        .help("The Zixi stream ID, or the stream ID to send when calling for SRT callers"),
+                )
+                .arg(
+                    Arg::new("link")
+                        .long("link")
+                        .action(clap::ArgAction::Append)
+                        .required_if_eq("push", "true")
+                        .conflicts_with("pull")
+                        .help("A link of the first link set of Zixi push outputs in format ip:port, can be given up to three times"),
+                )
+                .arg(
+                    Arg::new("backup-link")
+                        .long("backup-link")
+                        .action(clap::ArgAction::Append)
+                        .requires("link")
+                        .help("A link of the second link set of Zixi push outputs in format ip:port, can be given up to three times"),
+                ),
+        )
+        .subcommand(
+            Command::new("update")
+                .about("Change an existing output")
+                .arg(
+                    Arg::new("name")
+                        .required(true)
+                        .help("The name of the output to update"),
+                )
+                .arg(
+                    Arg::new("destination")
+                        .short('d')
+                        .long("dest")
+                        .help("Change the destination in format ip:port. Applicable for UDP, RTP, RIST and SRT callers"),
+                )
+                .arg(
+                    Arg::new("source")
+                        .long("source")
+                        .help("Change the source IP address. Applicable for UDP, RTP and RIST"),
+                )
+                .arg(
+                    Arg::new("fec")
+                        .long("fec")
+                        .value_parser(["1D", "2D", "none"])
+                        .help("Change the FEC of RTP outputs"),
+                )
+                .arg(
+                    Arg::new("fec-rows")
+                        .long("fec-rows")
+                        .value_parser(clap::value_parser!(u8).range(4..20))
+                        .help("FEC rows"),
+                )
+                .arg(
+                    Arg::new("fec-cols")
+                        .long("fec-cols")
+                        .value_parser(clap::value_parser!(u8).range(1..20))
+                        .help("FEC columns"),
+                )
+                .arg(
+                    Arg::new("latency")
+                        .long("latency")
+                        .value_parser(clap::value_parser!(u16))
+                        .help("Change the latency of SRT outputs in milliseconds"),
+                )
+                .arg(
+                    Arg::new("delay")
+                        .long("delay")
+                        .value_parser(clap::value_parser!(u32))
+                        .help("Change the delay in milliseconds"),
+                )
+                .arg(
+                    Arg::new("delay-mode")
+                        .long("delay-mode")
+                        .value_parser(["arrival", "origin"])
+                        .help("Base the delay on the arrival time or the origin time of the packets"),
+                )
+                .arg(
+                    Arg::new("redundancy-mode")
+                        .long("redundancy-mode")
+                        .value_parser(["none", "failover", "active"])
+                        .help("Change the redundancy mode"),
+                )
+                .arg(
+                    Arg::new("add-tag")
+                        .long("add-tag")
+                        .action(clap::ArgAction::Append)
+                        .help("Add a tag, can be repeated"),
+                )
+                .arg(
+                    Arg::new("remove-tag")
+                        .long("remove-tag")
+                        .action(clap::ArgAction::Append)
+                        .help("Remove a tag, can be repeated"),
+                ),
+        )
+        .subcommand(
+            Command::new("edit")
+                .about("Edit an output in $EDITOR")
+                .arg(
+                    Arg::new("name")
+                        .required(true)
+                        .help("The name of the output to edit"),
+                ),
+        )
+        .subcommand(
+            Command::new("set-input")
+                .about("Change the input of an output")
+                .arg(
+                    Arg::new("output")
+                        .required(true)
+                        .help("The name of the output to change"),
+                )
+                .arg(
+                    Arg::new("input")
+                        .required(true)
+                        .help("The name of the input to use"),
+                )
+                .arg(
+                    Arg::new("timeout")
+                        .long("timeout")
+                        .value_parser(humantime::parse_duration)
+                        .default_value("30s")
+                        .help("How long to wait for the output to become healthy"),
+                )
+                .arg(
+                    Arg::new("no-wait")
+                        .long("no-wait")
+                        .num_args(0)
+                        .help("Do not wait for the output to become healthy"),
+                ),
+        )
+        .subcommand(
+            Command::new("enable")
+                .about("Enable outputs")
+                .arg(
+                    Arg::new("name")
+                        .required(true)
+                        .num_args(1..)
+                        .help("The names of the outputs to enable, may contain * and ? wildcards"),
+                ),
+        )
+        .subcommand(
+            Command::new("disable")
+                .about("Disable outputs")
+                .arg(
+                    Arg::new("name")
+                        .required(true)
+                        .num_args(1..)
+                        .help("The names of the outputs to disable, may contain * and ? wildcards"),
                 ),
         )
         .subcommand(
@@ -148,15 +347,52 @@ pub(crate) fn subcommand() -> clap::Command {
                     .help("The name of the outputs to remove"),
             ),
         )
+        .subcommand(
+            Command::new("copy")
+                .about("Copy an output to another installation")
+                .arg(
+                    Arg::new("name")
+                        .required(true)
+                        .help("The name of the output to copy"),
+                )
+                .arg(
+                    Arg::new("to-context")
+                        .long("to-context")
+                        .required(true)
+                        .help("The context of the installation to copy the output to")
+                        .add(ArgValueCompleter::new(context_name_completer)),
+                ),
+        )
+        .subcommand(
+            Command::new("move")
+                .about("Move an output to another installation")
+                .arg(
+                    Arg::new("name")
+                        .required(true)
+                        .help("The name of the output to move"),
+                )
+                .arg(
+                    Arg::new("to-context")
+                        .long("to-context")
+                        .required(true)
+                        .help("The context of the installation to move the output to")
+                        .add(ArgValueCompleter::new(context_name_completer)),
+                ),
+        )
 }
 
 pub(crate) fn run(subcmd: &ArgMatches) {
     match subcmd.subcommand() {
         Some(("list", args)) => {
             let client = new_client();
-            match args.get_one::<String>("output").map(|s| s.as_str()) {
-                Some("wide") => list_wide(client),
-                _ => list(client),
+            let query = filter::query(&client, args).unwrap_or_else(|e| exit_with(e));
+            match (
+                format::structured(args),
+                args.get_one::<String>("output").map(|s| s.as_str()),
+            ) {
+                (Some(format), _) => list_structured(client, format, query),
+                (None, Some("wide")) => list_wide(client, query),
+                _ => list(client, query),
             };
         }
         Some(("show", args)) => {
@@ -166,7 +402,10 @@ pub(crate) fn run(subcmd: &ArgMatches) {
                 .map(|s| s.as_str())
                 .expect("output name should not be None");
 
-            show(client, name);
+            match format::structured(args) {
+                Some(format) => show_structured(client, name, format),
+                None => show(client, name),
+            }
         }
         Some(("create", args)) => {
             let client = new_client();
@@ -174,19 +413,11 @@ pub(crate) fn run(subcmd: &ArgMatches) {
                 .get_one::<String>("name")
                 .map(|s| s.as_str())
                 .expect("name is required");
-            let appliance = args
-                .get_one::<String>("appliance")
-                .map(|s| s.as_str())
-                .expect("appliance is required");
             let mode = args
                 .get_one::<String>("mode")
                 .map(|s| s.as_str())
                 .expect("mode is required");
             let dest = args.get_one::<String>("destination").map(|s| s.as_str());
-            let interface = args
-                .get_one::<String>("interface")
-                .map(|s| s.as_str())
-                .expect("interface is required");
             let input = args
                 .get_one::<String>("input")
                 .map(|s| s.as_str())
@@ -209,146 +440,337 @@ pub(crate) fn run(subcmd: &ArgMatches) {
                 }
             }
 
-            let mode = match mode {
-                "rtp" => {
-                    let dest = match dest {
-                        Some(d) => d,
-                        None => {
-                            eprintln!("Dest is required for UDP outputs");
-                            process::exit(1);
-                        }
-                    };
-                    let address = dest.split(':').next().expect("dest address is missing");
-                    let port = dest
-                        .split(':')
-                        .next_back()
-                        .expect("Port number is required for --dest")
-                        .parse::<u16>()
-                        .expect("port needs to be a number between 0 and 65535");
-
-                    let fec = args.get_one::<String>("fec").map(|fec| {
-                            match (args.get_one::<u8>("fec-rows"),args.get_one::<u8>("fec-cols")) {
-                                (Some(rows), Some(cols)) => Fec {
-                                    mode: match fec.as_ref() {
-                                        "1D" => FecMode::OneD,
-                                        "2D" => FecMode::TwoD,
-                                        // clap ensures only 1D or 2D are possible values
-                                        _ => panic!("Invalid FEC mode. This is bug"),
+            let legs = match args.get_many::<PortSpec>("port-spec") {
+                Some(specs) => specs
+                    .map(|spec| leg(mode, spec, args))
+                    .collect::<anyhow::Result<Vec<_>>>()
+                    .unwrap_or_else(|e| exit_with(e)),
+                None => {
+                    let mode = match mode {
+                        "rtp" => {
+                            let dest = match dest {
+                                Some(d) => d,
+                                None => {
+                                    eprintln!("Dest is required for UDP outputs");
+                                    process::exit(1);
+                                }
+                            };
+                            let address = dest.split(':').next().expect("dest address is missing");
+                            let port = dest
+                                .split(':')
+                                .next_back()
+                                .expect("Port number is required for --dest")
+                                .parse::<u16>()
+                                .expect("port needs to be a number between 0 and 65535");
+
+                            let fec = args.get_one::<String>("fec").map(|fec| {
+                                match (args.get_one::<u8>("fec-rows"),args.get_one::<u8>("fec-cols")) {
+                                    (Some(rows), Some(cols)) => Fec {
+                                        mode: match fec.as_ref() {
+                                            "1D" => FecMode::OneD,
+                                            "2D" => FecMode::TwoD,
+                                            // clap ensures only 1D or 2D are possible values
+                                            _ => panic!("Invalid FEC mode. This is bug"),
+                                        },
+                                        rows: *rows,
+                                        cols: *cols,
                                     },
-                                    rows: *rows,
-                                    cols: *cols,
-                                },
-                                _ =>  {
-                                    eprintln!("The --fec argument requires the --fec-rows and --fec-cols arguments");
+                                    _ =>  {
+                                        eprintln!("The --fec argument requires the --fec-rows and --fec-cols arguments");
+                                        process::exit(1);
+                                    }
+                                }
+                            });
+
+                            NewOutputMode::Rtp(NewRtpOutputMode {
+                                address: address.to_owned(),
+                                port,
+                                fec,
+                                source_addr: source,
+                            })
+                        }
+                        "udp" => {
+                            let dest = match dest {
+                                Some(d) => d,
+                                None => {
+                                    eprintln!("Dest is required for UDP outputs");
                                     process::exit(1);
                                 }
+                            };
+                            let address = dest.split(':').next().expect("dest address is missing");
+                            let port = dest
+                                .split(':')
+                                .next_back()
+                                .expect("Port number is required for --dest")
+                                .parse::<u16>()
+                                .expect("port needs to be a number between 0 and 65535");
+                            NewOutputMode::Udp(NewUdpOutputMode {
+                                address: address.to_owned(),
+                                port,
+                                source_addr: source,
+                            })
+                        }
+                        "srt" => {
+                            let settings = srt::settings(args).unwrap_or_else(|e| exit_with(e));
+                            let rate_limiting = srt::rate_limiting(args);
+                            let stream_id = args.get_one::<String>("stream-id").cloned();
+                            if stream_id.is_some() && !args.get_flag("caller") {
+                                eprintln!("The --stream-id flag is only supported for SRT callers");
+                                process::exit(1);
                             }
-                        });
+                            if args.get_flag("caller") {
+                                let dest = match args.get_one::<String>("destination") {
+                                    Some(d) => d,
+                                    None => {
+                                        eprintln!("Dest is required for SRT caller outputs");
+                                        process::exit(1);
+                                    }
+                                };
+                                let address =
+                                    dest.split(':').next().expect("dest address is missing");
+                                let port = dest
+                                    .split(':')
+                                    .next_back()
+                                    .expect("Port number is required for --dest")
+                                    .parse::<u16>()
+                                    .expect("port needs to be a number between 0 and 65535");
 
-                    NewOutputMode::Rtp(NewRtpOutputMode {
-                        address: address.to_owned(),
-                        port,
-                        fec,
-                        source_addr: source,
-                    })
-                }
-                "udp" => {
-                    let dest = match dest {
-                        Some(d) => d,
-                        None => {
-                            eprintln!("Dest is required for UDP outputs");
-                            process::exit(1);
+                                NewOutputMode::Srt(NewSrtOutputMode::Caller {
+                                    address: address.to_owned(),
+                                    port,
+                                    stream_id,
+                                    settings,
+                                    rate_limiting,
+                                })
+                            } else if args.get_flag("rendezvous") {
+                                let dest = match args.get_one::<String>("destination") {
+                                    Some(d) => d,
+                                    None => {
+                                        eprintln!("Dest is required for SRT rendezvous outputs");
+                                        process::exit(1);
+                                    }
+                                };
+                                let (address, port) =
+                                    parse_destination(dest).unwrap_or_else(|e| exit_with(e));
+                                NewOutputMode::Srt(NewSrtOutputMode::Rendezvous {
+                                    address,
+                                    port,
+                                    settings,
+                                    rate_limiting,
+                                })
+                            } else if args.get_flag("listener") {
+                                let port = match args.get_one::<u16>("port") {
+                                    Some(port) => port,
+                                    None => {
+                                        eprintln!("--port is required for srt listener outputs");
+                                        process::exit(1);
+                                    }
+                                };
+                                NewOutputMode::Srt(NewSrtOutputMode::Listener {
+                                    port: *port,
+                                    settings,
+                                    rate_limiting,
+                                })
+                            } else {
+                                eprintln!("Need to specify either --caller, --listener or --rendezvous for SRT output");
+                                process::exit(1);
+                            }
                         }
-                    };
-                    let address = dest.split(':').next().expect("dest address is missing");
-                    let port = dest
-                        .split(':')
-                        .next_back()
-                        .expect("Port number is required for --dest")
-                        .parse::<u16>()
-                        .expect("port needs to be a number between 0 and 65535");
-                    NewOutputMode::Udp(NewUdpOutputMode {
-                        address: address.to_owned(),
-                        port,
-                        source_addr: source,
-                    })
-                }
-                "srt" => {
-                    if args.get_flag("caller") {
-                        let dest = match args.get_one::<String>("destination") {
-                            Some(d) => d,
-                            None => {
-                                eprintln!("Dest is required for SRT caller outputs");
+                        "rist" => {
+                            let dest = match dest {
+                                Some(d) => d,
+                                None => {
+                                    eprintln!("Dest is required for RIST outputs");
+                                    process::exit(1);
+                                }
+                            };
+                            let address = dest.split(':').next().expect("dest address is missing");
+                            let port = dest
+                                .split(':')
+                                .next_back()
+                                .expect("Port number is required for --dest")
+                                .parse::<u16>()
+                                .expect("port needs to be a number between 0 and 65535");
+                            NewOutputMode::Rist(NewRistOutputMode {
+                                address: address.to_owned(),
+                                port,
+                                source_addr: source,
+                            })
+                        }
+                        "rtmp" => {
+                            let url = args
+                                .get_one::<String>("url")
+                                .expect("url is required for RTMP outputs");
+                            let stream_key = args
+                                .get_one::<String>("stream-key")
+                                .expect("stream key is required for RTMP outputs");
+                            if !url.starts_with("rtmp://") && !url.starts_with("rtmps://") {
+                                eprintln!("The --url needs to start with rtmp:// or rtmps://");
                                 process::exit(1);
                             }
-                        };
-                        let address = dest.split(':').next().expect("dest address is missing");
-                        let port = dest
-                            .split(':')
-                            .next_back()
-                            .expect("Port number is required for --dest")
-                            .parse::<u16>()
-                            .expect("port needs to be a number between 0 and 65535");
-
-                        NewOutputMode::Srt(NewSrtOutputMode::Caller {
-                            address: address.to_owned(),
-                            port,
-                        })
-                    } else if args.get_flag("rendezvous") {
-                        eprintln!("--rendezvous is not yet implemented");
-                        process::exit(1);
-                    } else if args.get_flag("listener") {
-                        let port = match args.get_one::<u16>("port") {
-                            Some(port) => port,
-                            None => {
-                                eprintln!("--port is required for srt listener outputs");
+                            NewOutputMode::Rtmp(format!(
+                                "{}/{}",
+                                url.trim_end_matches('/'),
+                                stream_key
+                            ))
+                        }
+                        "zixi" => {
+                            let stream_id = args
+                                .get_one::<String>("stream-id")
+                                .expect("stream ID is required for Zixi outputs")
+                                .to_owned();
+                            if args.get_flag("push") {
+                                let links = |arg: &str| -> Vec<ZixiLink> {
+                                    let links = args
+                                        .get_many::<String>(arg)
+                                        .into_iter()
+                                        .flatten()
+                                        .map(|link| match parse_destination(link) {
+                                            Ok((remote_ip, remote_port)) => ZixiLink {
+                                                remote_ip,
+                                                remote_port,
+                                            },
+                                            Err(e) => {
+                                                eprintln!("Invalid --{}: {}", arg, e);
+                                                process::exit(1);
+                                            }
+                                        })
+                                        .collect::<Vec<_>>();
+                                    if links.len() > 3 {
+                                        eprintln!(
+                                            "A link set has at most three links, got {} for --{}",
+                                            links.len(),
+                                            arg
+                                        );
+                                        process::exit(1);
+                                    }
+                                    links
+                                };
+                                let link_set_1 = links("link");
+                                let link_set_2 = links("backup-link");
+                                NewOutputMode::Zixi(NewZixiOutputMode::Push {
+                                    stream_id,
+                                    link_set_1,
+                                    link_set_2: (!link_set_2.is_empty()).then_some(link_set_2),
+                                })
+                            } else if args.get_flag("pull") {
+                                NewOutputMode::Zixi(NewZixiOutputMode::Pull { stream_id })
+                            } else {
+                                eprintln!(
+                                    "Need to specify either --push or --pull for Zixi outputs"
+                                );
                                 process::exit(1);
                             }
-                        };
-                        NewOutputMode::Srt(NewSrtOutputMode::Listener { port: *port })
-                    } else {
-                        eprintln!("Need to specify either --caller, --listener or --rendezvous for SRT output");
-                        process::exit(1);
-                    }
-                }
-                "rist" => {
-                    let dest = match dest {
-                        Some(d) => d,
-                        None => {
-                            eprintln!("Dest is required for RIST outputs");
+                        }
+                        e => {
+                            eprintln!("Invalid mode: {}", e);
                             process::exit(1);
                         }
                     };
-                    let address = dest.split(':').next().expect("dest address is missing");
-                    let port = dest
-                        .split(':')
-                        .next_back()
-                        .expect("Port number is required for --dest")
-                        .parse::<u16>()
-                        .expect("port needs to be a number between 0 and 65535");
-                    NewOutputMode::Rist(NewRistOutputMode {
-                        address: address.to_owned(),
-                        port,
-                        source_addr: source,
-                    })
+                    vec![NewOutputLeg {
+                        appliance: args
+                            .get_one::<String>("appliance")
+                            .cloned()
+                            .expect("appliance is required"),
+                        interface: args
+                            .get_one::<String>("interface")
+                            .cloned()
+                            .expect("interface is required"),
+                        mode,
+                    }]
                 }
-                e => {
-                    eprintln!("Invalid mode: {}", e);
+            };
+            let redundancy_mode = match redundancy_mode(args) {
+                None if legs.len() > 1 => Some(OutputRedundancyMode::Active),
+                Some(OutputRedundancyMode::Failover | OutputRedundancyMode::Active)
+                    if legs.len() == 1 =>
+                {
+                    eprintln!("A redundant output needs more than one --port-spec");
                     process::exit(1);
                 }
+                mode => mode,
             };
 
             create(
                 client,
                 NewOutput {
                     name: name.to_owned(),
-                    appliance: appliance.to_owned(),
-                    interface: interface.to_owned(),
                     input: input.to_owned(),
-                    mode,
+                    legs,
+                    redundancy_mode,
                 },
             )
         }
+        Some(("update", args)) => {
+            let client = new_client();
+            let name = args
+                .get_one::<String>("name")
+                .expect("Output name is mandatory");
+            if let Err(e) = update(&client, name, args) {
+                eprintln!("Failed to update output {}: {:#}", name, e);
+                process::exit(1);
+            }
+        }
+        Some(("edit", args)) => {
+            let client = new_client();
+            let name = args
+                .get_one::<String>("name")
+                .expect("Output name is mandatory");
+            if let Err(e) = edit(&client, name) {
+                eprintln!("Failed to edit output {}: {:#}", name, e);
+                process::exit(1);
+            }
+        }
+        Some(("set-input", args)) => {
+            let client = new_client();
+            let output = args
+                .get_one::<String>("output")
+                .expect("Output name is mandatory");
+            let input = args
+                .get_one::<String>("input")
+                .expect("Input name is mandatory");
+            let timeout = if args.get_flag("no-wait") {
+                None
+            } else {
+                args.get_one::<Duration>("timeout").copied()
+            };
+            if let Err(e) = set_input(&client, output, input, timeout) {
+                eprintln!("Failed to set input of output {}: {:#}", output, e);
+                process::exit(1);
+            }
+        }
+        Some(("enable", args)) => {
+            let client = new_client();
+            let mut failed = false;
+            for name in args
+                .get_many::<String>("name")
+                .expect("Output name is mandatory")
+            {
+                if let Err(e) = set_admin_status(&client, name, OutputAdminStatus::On) {
+                    eprintln!("Failed to enable output {}: {:#}", name, e);
+                    failed = true;
+                }
+            }
+            if failed {
+                process::exit(1);
+            }
+        }
+        Some(("disable", args)) => {
+            let client = new_client();
+            let mut failed = false;
+            for name in args
+                .get_many::<String>("name")
+                .expect("Output name is mandatory")
+            {
+                if let Err(e) = set_admin_status(&client, name, OutputAdminStatus::Off) {
+                    eprintln!("Failed to disable output {}: {:#}", name, e);
+                    failed = true;
+                }
+            }
+            if failed {
+                process::exit(1);
+            }
+        }
         Some(("delete", args)) => {
             let client = new_client();
             let mut failed = false;
@@ -365,6 +787,32 @@ pub(crate) fn run(subcmd: &ArgMatches) {
                 process::exit(1);
             }
         }
+        Some(("copy", args)) => {
+            let client = new_client();
+            let name = args
+                .get_one::<String>("name")
+                .expect("Output name is mandatory");
+            let to_context = args
+                .get_one::<String>("to-context")
+                .expect("Destination context is mandatory");
+            if let Err(e) = transfer(&client, name, to_context, false) {
+                eprintln!("Failed to copy output {}: {:#}", name, e);
+                process::exit(1);
+            }
+        }
+        Some(("move", args)) => {
+            let client = new_client();
+            let name = args
+                .get_one::<String>("name")
+                .expect("Output name is mandatory");
+            let to_context = args
+                .get_one::<String>("to-context")
+                .expect("Destination context is mandatory");
+            if let Err(e) = transfer(&client, name, to_context, true) {
+                eprintln!("Failed to move output {}: {:#}", name, e);
+                process::exit(1);
+            }
+        }
         Some((cmd, _)) => {
             eprintln!("Command output {cmd} is not yet implemented");
             process::exit(1);
@@ -373,50 +821,53 @@ pub(crate) fn run(subcmd: &ArgMatches) {
     }
 }
 
-impl fmt::Display for OutputHealthState {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            Self::NotConfigured => write!(f, "{} Not configured", red!("✗")),
-            Self::MetricsMissing => write!(f, "{} Missing metrics", red!("✗")),
-            Self::Tr101290Priority1Error => {
-                write!(f, "{} TR 101 290 Priority 1 errors", red!("✗"))
-            }
-            Self::ReducedRedundancy => write!(f, "{} Reduced redundancy", yellow!("⚠")),
-            Self::AllOk => write!(f, "{}", green!("✓")),
-            Self::NotAcknowledged => write!(f, "No ACKs recieved"),
-            Self::InputError => write!(f, "{} Input error", red!("✗")),
-            Self::OutputError => write!(f, "{} Output error", red!("✗")),
-            Self::Alarm => write!(f, "{} Alarm", red!("✗")),
+pub(crate) fn health_state(state: &OutputHealthState) -> String {
+    match state {
+        OutputHealthState::NotConfigured => format!("{} Not configured", red!("✗")),
+        OutputHealthState::MetricsMissing => format!("{} Missing metrics", red!("✗")),
+        OutputHealthState::Tr101290Priority1Error => {
+            format!("{} TR 101 290 Priority 1 errors", red!("✗"))
         }
+        OutputHealthState::ReducedRedundancy => format!("{} Reduced redundancy", yellow!("⚠")),
+        OutputHealthState::AllOk => green!("✓"),
+        OutputHealthState::NotAcknowledged => "No ACKs recieved".to_owned(),
+        OutputHealthState::InputError => format!("{} Input error", red!("✗")),
+        OutputHealthState::OutputError => format!("{} Output error", red!("✗")),
+        OutputHealthState::Alarm => format!("{} Alarm", red!("✗")),
     }
 }
 
-impl Output {
-    fn health_fmt(&self) -> String {
-        match self.admin_status {
-            OutputAdminStatus::On => self
-                .health
-                .as_ref()
-                .map(|h| {
-                    if h.title.is_empty() {
-                        h.state.to_string()
-                    } else {
-                        format!("{} ({})", h.state, h.title)
-                    }
-                })
-                .unwrap_or("unknown".to_owned()),
-            OutputAdminStatus::Off => format!("{} Disabled", grey!("⏻")),
-        }
+fn health_fmt(output: &Output) -> String {
+    match output.admin_status {
+        OutputAdminStatus::On => output
+            .health
+            .as_ref()
+            .map(|h| {
+                if h.title.is_empty() {
+                    health_state(&h.state)
+                } else {
+                    format!("{} ({})", health_state(&h.state), h.title)
+                }
+            })
+            .unwrap_or("unknown".to_owned()),
+        OutputAdminStatus::Off => format!("{} Disabled", grey!("⏻")),
     }
 }
 
-fn list(client: EdgeClient) {
-    let outputs = client.list_outputs().expect("Failed to list outputs");
+fn list_structured(client: EdgeClient, format: Format, query: Query) {
+    format::print_items(format, client.output_pages(query).items())
+        .or_exit("Failed to list outputs");
+}
+
+fn list(client: EdgeClient, query: Query) {
+    let outputs = client
+        .query_outputs(query)
+        .or_exit("Failed to list outputs");
     let mut builder = Builder::default();
     builder.push_record(["ID", "Name", "Health"]);
 
     for output in outputs {
-        let health = output.health_fmt();
+        let health = health_fmt(&output);
         builder.push_record([output.id, output.name, health]);
     }
 
@@ -425,8 +876,10 @@ fn list(client: EdgeClient) {
     println!("{}", table);
 }
 
-fn list_wide(client: EdgeClient) {
-    let outputs = client.list_outputs().expect("Failed to list outputs");
+fn list_wide(client: EdgeClient, query: Query) {
+    let outputs = client
+        .query_outputs(query)
+        .or_exit("Failed to list outputs");
     let mut builder = Builder::default();
     builder.push_record([
         "ID",
@@ -440,29 +893,32 @@ fn list_wide(client: EdgeClient) {
         "Appliances",
         "Health",
     ]);
-    let groups: Vec<String> = outputs.iter().map(|o| o.group.clone()).collect();
-    // TODO: Improve performance here by doing a bulk fetch of groups
-    let groups: BTreeMap<String, Group> = groups
-        .into_iter()
-        .filter_map(|id| {
-            let group = client.get_group(&id);
-            group.map(|group| (id, group)).ok()
-        })
-        .collect();
-    let inputs: Vec<String> = outputs
+    let input_ids: Vec<String> = outputs
         .iter()
         .filter_map(|o| o.input.as_ref().map(|i| i.to_owned()))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let (groups, inputs) = thread::scope(|s| {
+        let groups = s.spawn(|| client.list_groups());
+        let inputs = s.spawn(|| client.list_inputs_by_ids(input_ids));
+        (
+            groups.join().expect("Group request thread panicked"),
+            inputs.join().expect("Input request thread panicked"),
+        )
+    });
+    let groups: BTreeMap<String, Group> = groups
+        .or_exit("Failed to list groups")
+        .into_iter()
+        .map(|group| (group.id.to_owned(), group))
         .collect();
-    // TODO: Improve performance here by doing a bulk fetch of inputs
     let inputs: BTreeMap<String, Input> = inputs
+        .or_exit("Failed to list inputs")
         .into_iter()
-        .filter_map(|id| {
-            let input = client.get_input(&id);
-            input.map(|input| (id, input)).ok()
-        })
+        .map(|input| (input.id.to_owned(), input))
         .collect();
     for output in outputs {
-        let health = output.health_fmt();
+        let health = health_fmt(&output);
         let input = match output.input {
             Some(input) => inputs
                 .get(&input)
@@ -506,19 +962,34 @@ fn list_wide(client: EdgeClient) {
     println!("{}", table);
 }
 
+fn show_structured(client: EdgeClient, name: &str, format: Format) {
+    match client.find_outputs(name) {
+        Ok(outputs) => format::print(format, &outputs),
+        Err(e) => {
+            exit_with(anyhow::Error::from(e).context("Failed to find output"));
+        }
+    }
+}
+
 fn show(client: EdgeClient, name: &str) {
     let outputs = client.find_outputs(name);
     let outputs = match outputs {
         Ok(outputs) => outputs,
         Err(e) => {
-            eprintln!("Failed to find output: {:?}", e);
-            process::exit(1);
+            exit_with(anyhow::Error::from(e).context("Failed to find output"));
         }
     };
 
+    // The state of every leg comes from the alarms on its physical port
+    let port_alarms = if outputs.iter().any(|o| !o.ports.is_empty()) {
+        client.list_alarms().ok()
+    } else {
+        None
+    };
+
     let many_outputs = outputs.len() > 1;
     for output in outputs {
-        let health = output.health_fmt();
+        let health = health_fmt(&output);
         let group = client.get_group(&output.group);
         let group_name = group.map(|g| g.name).unwrap_or("unknown".to_owned());
         let input = output.input.and_then(|input| client.get_input(&input).ok());
@@ -575,21 +1046,34 @@ fn show(client: EdgeClient, name: &str) {
         println!("Group:          {}", group_name);
         println!("Delay:          {}", delay);
         println!("Delay mode:     {}", delay_mode);
+        println!("Tags:           {}", output.tags.join(", "));
 
+        let port_alarms = port_alarms.as_ref().map(|alarms| {
+            alarms
+                .iter()
+                .filter(|a| a.output_id.as_deref() == Some(output.id.as_str()))
+                .cloned()
+                .collect::<Vec<_>>()
+        });
         println!("Ports:");
         for port in output.ports {
+            let physical_port = port.physical_port().map(|p| p.to_owned());
             match port {
                 OutputPort::Srt(SrtOutputPort::Listener(port)) => {
                     let addr = format!("{}:{}", port.local_ip, port.local_port);
                     println!("  - Mode:             srt");
                     println!("    SRT mode:         listener");
                     println!("    Listening at:     {}", addr);
+                    println!("    Latency:          {}ms", port.latency);
+                    println!("    Encryption:       {}", port.pbkeylen);
                 }
                 OutputPort::Srt(SrtOutputPort::Caller(port)) => {
                     let addr = format!("{}:{}", port.remote_ip, port.remote_port);
                     println!("  - Mode:             srt");
                     println!("    SRT mode:         caller");
                     println!("    Calling:          {}", addr);
+                    println!("    Latency:          {}ms", port.latency);
+                    println!("    Encryption:       {}", port.pbkeylen);
                 }
                 OutputPort::Srt(SrtOutputPort::Rendezvous(port)) => {
                     let source = format!("{}:{}", port.local_ip, port.remote_port);
@@ -598,6 +1082,8 @@ fn show(client: EdgeClient, name: &str) {
                     println!("    SRT mode:         rendezvous");
                     println!("    Source:           {}", source);
                     println!("    Destination:      {}", dest);
+                    println!("    Latency:          {}ms", port.latency);
+                    println!("    Encryption:       {}", port.pbkeylen);
                 }
                 OutputPort::Zixi(ZixiOutputPort::Pull(port)) => {
                     println!("  - Mode:             Zixi");
@@ -677,6 +1163,17 @@ fn show(client: EdgeClient, name: &str) {
                 }
                 _ => println!("- Mode:              Unsupported mode!"), // TODO
             }
+            if let Some(physical_port) = physical_port {
+                let appliance = client
+                    .get_port(&physical_port)
+                    .map(|p| p.appliance.name)
+                    .unwrap_or("unknown".to_owned());
+                println!("    Appliance:        {}", appliance);
+                println!(
+                    "    State:            {}",
+                    legs::state(port_alarms.as_deref(), &physical_port)
+                );
+            }
         }
 
         println!("Alarms:         {}", alarms);
@@ -697,6 +1194,9 @@ enum NewOutputMode {
     Rtp(NewRtpOutputMode),
     Srt(NewSrtOutputMode),
     Rist(NewRistOutputMode),
+    // The destination URL including the stream key
+    Rtmp(String),
+    Zixi(NewZixiOutputMode),
 }
 
 struct NewUdpOutputMode {
@@ -726,8 +1226,35 @@ enum FecMode {
 }
 
 enum NewSrtOutputMode {
-    Listener { port: u16 },
-    Caller { address: String, port: u16 },
+    Listener {
+        port: u16,
+        settings: srt::Settings,
+        rate_limiting: SrtRateLimiting,
+    },
+    Caller {
+        address: String,
+        port: u16,
+        stream_id: Option<String>,
+        settings: srt::Settings,
+        rate_limiting: SrtRateLimiting,
+    },
+    Rendezvous {
+        address: String,
+        port: u16,
+        settings: srt::Settings,
+        rate_limiting: SrtRateLimiting,
+    },
+}
+
+enum NewZixiOutputMode {
+    Pull {
+        stream_id: String,
+    },
+    Push {
+        stream_id: String,
+        link_set_1: Vec<ZixiLink>,
+        link_set_2: Option<Vec<ZixiLink>>,
+    },
 }
 
 struct NewRistOutputMode {
@@ -738,52 +1265,126 @@ struct NewRistOutputMode {
 
 struct NewOutput {
     pub name: String,
+    pub input: String,
+    pub legs: Vec<NewOutputLeg>,
+    pub redundancy_mode: Option<OutputRedundancyMode>,
+}
+
+// A port of the output, more than one for redundant outputs
+struct NewOutputLeg {
     pub appliance: String,
     pub interface: String,
-    pub input: String,
     pub mode: NewOutputMode,
 }
 
-fn create(client: EdgeClient, new_output: NewOutput) {
-    let appl = match client.find_appliances(&new_output.appliance) {
-        Ok(appls) if appls.is_empty() => {
-            println!("Could not find appliance {}", new_output.appliance);
-            process::exit(1);
-        }
-        Ok(appls) if appls.len() > 1 => {
-            println!(
-                "Found more than one appliance matching {}: {}",
-                new_output.appliance,
-                appls
-                    .into_iter()
-                    .map(|a| a.name)
-                    .collect::<Vec<_>>()
-                    .join(", ")
-            );
-            process::exit(1);
-        }
-        Ok(mut appls) => appls.pop().unwrap(),
-        Err(e) => {
-            println!("Failed to find appliance {}: {}", new_output.appliance, e);
-            process::exit(1);
-        }
+// One leg of a redundant output
+fn leg(mode: &str, spec: &PortSpec, args: &ArgMatches) -> anyhow::Result<NewOutputLeg> {
+    let PortSpec {
+        appliance,
+        interface,
+        address,
+        port,
+    } = spec.clone();
+    let no_address = || match &address {
+        Some(address) => Err(anyhow!(
+            "The address {} in --port-spec is not supported for {} outputs",
+            address,
+            mode
+        )),
+        None => Ok(()),
+    };
+    let with_address = || {
+        address.clone().ok_or_else(|| {
+            anyhow!(
+                "--port-spec needs the destination address for {} outputs",
+                mode
+            )
+        })
     };
+    let source_addr = args.get_one::<String>("source").cloned();
 
-    let interface = match appl
-        .physical_ports
-        .iter()
-        .find(|p| p.name == new_output.interface)
-    {
-        Some(interface) => interface,
-        None => {
-            println!(
-                "Failed to find interface {} on appliance {}",
-                new_output.interface, appl.name
-            );
-            process::exit(1);
+    let mode = match mode {
+        "udp" => NewOutputMode::Udp(NewUdpOutputMode {
+            address: with_address()?,
+            port,
+            source_addr,
+        }),
+        "rtp" => NewOutputMode::Rtp(NewRtpOutputMode {
+            address: with_address()?,
+            port,
+            fec: args.get_one::<String>("fec").map(|fec| Fec {
+                mode: if fec == "2D" {
+                    FecMode::TwoD
+                } else {
+                    FecMode::OneD
+                },
+                rows: *args
+                    .get_one::<u8>("fec-rows")
+                    .expect("--fec requires --fec-rows"),
+                cols: *args
+                    .get_one::<u8>("fec-cols")
+                    .expect("--fec requires --fec-cols"),
+            }),
+            source_addr,
+        }),
+        "rist" => NewOutputMode::Rist(NewRistOutputMode {
+            address: with_address()?,
+            port,
+            source_addr,
+        }),
+        "srt" => {
+            let settings = srt::settings(args)?;
+            let rate_limiting = srt::rate_limiting(args);
+            let stream_id = args.get_one::<String>("stream-id").cloned();
+            if stream_id.is_some() && !args.get_flag("caller") {
+                return Err(anyhow!(
+                    "The --stream-id flag is only supported for SRT callers"
+                ));
+            }
+            NewOutputMode::Srt(if args.get_flag("caller") {
+                NewSrtOutputMode::Caller {
+                    address: with_address()?,
+                    port,
+                    stream_id,
+                    settings,
+                    rate_limiting,
+                }
+            } else if args.get_flag("rendezvous") {
+                NewSrtOutputMode::Rendezvous {
+                    address: with_address()?,
+                    port,
+                    settings,
+                    rate_limiting,
+                }
+            } else if args.get_flag("listener") {
+                no_address()?;
+                NewSrtOutputMode::Listener {
+                    port,
+                    settings,
+                    rate_limiting,
+                }
+            } else {
+                return Err(anyhow!(
+                    "Need to specify either --caller, --listener or --rendezvous for SRT output"
+                ));
+            })
+        }
+        mode => {
+            return Err(anyhow!(
+                "--port-spec is only supported for UDP, RTP, RIST and SRT outputs, not {}",
+                mode
+            ))
         }
     };
 
+    Ok(NewOutputLeg {
+        appliance,
+        interface,
+        mode,
+    })
+}
+
+fn create(client: EdgeClient, new_output: NewOutput) {
     let input = match client.find_inputs(&new_output.input) {
         Ok(inputs) if inputs.is_empty() => {
             println!("Could not find input {}", new_output.input);
@@ -808,59 +1409,133 @@ fn create(client: EdgeClient, new_output: NewOutput) {
         }
     };
 
-    let ports = match new_output.mode {
-        NewOutputMode::Udp(udp) => vec![OutputPort::Udp(UdpOutputPort {
-            address: udp.address,
-            port: udp.port,
-            physical_port: interface.id.to_owned(),
-            source_address: udp.source_addr,
-        })],
-        NewOutputMode::Rtp(rtp) => {
-            let fec = rtp.fec.as_ref().map(|fec| match fec.mode {
-                FecMode::OneD => OutputPortFec::Fec1D,
-                FecMode::TwoD => OutputPortFec::Fec2D,
-            });
-
-            vec![OutputPort::Rtp(RtpOutputPort {
-                address: rtp.address,
-                port: rtp.port,
+    let mut ports = Vec::new();
+    for leg in new_output.legs {
+        let interface = find_physical_port(&client, &leg.appliance, &leg.interface)
+            .unwrap_or_else(|e| exit_with(e));
+        ports.extend(match leg.mode {
+            NewOutputMode::Udp(udp) => vec![OutputPort::Udp(UdpOutputPort {
+                id: String::new(),
+                address: udp.address,
+                port: udp.port,
                 physical_port: interface.id.to_owned(),
-                fec,
-                fec_rows: rtp.fec.as_ref().map(|fec| fec.rows),
-                fec_cols: rtp.fec.as_ref().map(|fec| fec.cols),
-                source_address: rtp.source_addr,
-            })]
-        }
-        NewOutputMode::Srt(NewSrtOutputMode::Listener { port }) => vec![OutputPort::Srt(
-            SrtOutputPort::Listener(SrtListenerOutputPort {
-                local_ip: interface.addresses[0].address.to_owned(),
-                local_port: port,
+                source_address: udp.source_addr,
+                other: Map::new(),
+            })],
+            NewOutputMode::Rtp(rtp) => {
+                let fec = rtp.fec.as_ref().map(|fec| match fec.mode {
+                    FecMode::OneD => OutputPortFec::Fec1D,
+                    FecMode::TwoD => OutputPortFec::Fec2D,
+                });
+
+                vec![OutputPort::Rtp(RtpOutputPort {
+                    id: String::new(),
+                    address: rtp.address,
+                    port: rtp.port,
+                    physical_port: interface.id.to_owned(),
+                    fec,
+                    fec_rows: rtp.fec.as_ref().map(|fec| fec.rows),
+                    fec_cols: rtp.fec.as_ref().map(|fec| fec.cols),
+                    source_address: rtp.source_addr,
+                    other: Map::new(),
+                })]
+            }
+            NewOutputMode::Srt(NewSrtOutputMode::Listener {
+                port,
+                settings,
+                rate_limiting,
+            }) => vec![OutputPort::Srt(SrtOutputPort::Listener(
+                SrtListenerOutputPort {
+                    id: String::new(),
+                    local_ip: interface.addresses[0].address.to_owned(),
+                    local_port: port,
+                    physical_port: interface.id.to_owned(),
+                    latency: settings.latency,
+                    pbkeylen: settings.keylen,
+                    rate_limiting,
+                    passphrase: settings.passphrase,
+                    whitelist_cidr_block: Some(settings.whitelist),
+                    other: Map::new(),
+                },
+            ))],
+            NewOutputMode::Srt(NewSrtOutputMode::Caller {
+                address,
+                port,
+                stream_id,
+                settings,
+                rate_limiting,
+            }) => vec![OutputPort::Srt(SrtOutputPort::Caller(
+                SrtCallerOutputPort {
+                    id: String::new(),
+                    physical_port: interface.id.to_owned(),
+                    remote_ip: address,
+                    remote_port: port,
+
+                    latency: settings.latency,
+                    pbkeylen: settings.keylen,
+                    rate_limiting,
+                    passphrase: settings.passphrase,
+                    stream_id,
+                    other: Map::new(),
+                },
+            ))],
+            NewOutputMode::Srt(NewSrtOutputMode::Rendezvous {
+                address,
+                port,
+                settings,
+                rate_limiting,
+            }) => vec![OutputPort::Srt(SrtOutputPort::Rendezvous(
+                SrtRendezvousOutputPort {
+                    id: String::new(),
+                    physical_port: interface.id.to_owned(),
+                    local_ip: interface.addresses[0].address.to_owned(),
+                    remote_ip: address,
+                    remote_port: port,
+                    latency: settings.latency,
+                    pbkeylen: settings.keylen,
+                    rate_limiting,
+                    passphrase: settings.passphrase,
+                    whitelist_cidr_block: Some(settings.whitelist),
+                    other: Map::new(),
+                },
+            ))],
+            NewOutputMode::Rist(rist) => vec![OutputPort::Rist(RistOutputPort {
+                id: String::new(),
+                address: rist.address,
+                port: rist.port,
                 physical_port: interface.id.to_owned(),
-                latency: 120,
-                pbkeylen: SrtKeylen::None,
-                rate_limiting: SrtRateLimiting::NotEnforced,
-                whitelist_cidr_block: Some(vec!["0.0.0.0/0".to_owned()]),
-            }),
-        )],
-        NewOutputMode::Srt(NewSrtOutputMode::Caller { address, port }) => vec![OutputPort::Srt(
-            SrtOutputPort::Caller(SrtCallerOutputPort {
+                source_address: rist.source_addr,
+                profile: "simple".to_owned(),
+                other: Map::new(),
+            })],
+            NewOutputMode::Rtmp(address) => vec![OutputPort::Rtmp(RtmpOutputPort {
+                id: String::new(),
                 physical_port: interface.id.to_owned(),
-                remote_ip: address,
-                remote_port: port,
-
-                latency: 120,
-                pbkeylen: SrtKeylen::None,
-                rate_limiting: SrtRateLimiting::NotEnforced,
-            }),
-        )],
-        NewOutputMode::Rist(rist) => vec![OutputPort::Rist(RistOutputPort {
-            address: rist.address,
-            port: rist.port,
-            physical_port: interface.id.to_owned(),
-            source_address: rist.source_addr,
-            profile: "simple".to_owned(),
-        })],
-    };
+                rtmp_destination_address: address,
+                other: Map::new(),
+            })],
+            NewOutputMode::Zixi(NewZixiOutputMode::Pull { stream_id }) => {
+                vec![OutputPort::Zixi(ZixiOutputPort::Pull(ZixiPullOutputPort {
+                    id: String::new(),
+                    physical_port: interface.id.to_owned(),
+                    stream_id,
+                    other: Map::new(),
+                }))]
+            }
+            NewOutputMode::Zixi(NewZixiOutputMode::Push {
+                stream_id,
+                link_set_1,
+                link_set_2,
+            }) => vec![OutputPort::Zixi(ZixiOutputPort::Push(ZixiPushOutputPort {
+                id: String::new(),
+                physical_port: interface.id.to_owned(),
+                stream_id,
+                link_set_1,
+                link_set_2,
+                other: Map::new(),
+            }))],
+        });
+    }
 
     if let Err(e) = client.create_output(crate::edge::NewOutput {
         name: new_output.name,
@@ -868,14 +1543,292 @@ fn create(client: EdgeClient, new_output: NewOutput) {
         delay: None,
         delay_mode: None,
         group: None,
-        input: input.id,
-        redundancy_mode: None,
+        input: Some(input.id),
+        redundancy_mode: new_output.redundancy_mode,
         tags: Vec::new(),
         ports,
     }) {
-        eprintln!("Failed to create output: {}", e);
-        process::exit(1);
+        exit_with(anyhow::Error::from(e).context("Failed to create output"));
+    }
+}
+
+fn update(client: &EdgeClient, name: &str, args: &ArgMatches) -> anyhow::Result<()> {
+    let mut output =
+        manifest::find_output(client, name)?.ok_or_else(|| anyhow!("Output not found"))?;
+
+    if let Some(delay) = args.get_one::<u32>("delay") {
+        output.delay = Some(*delay);
+    }
+    match args.get_one::<String>("delay-mode").map(|s| s.as_str()) {
+        Some("arrival") => output.delay_mode = Some(OutputDelayMode::BasedOnArrivalTime),
+        Some("origin") => output.delay_mode = Some(OutputDelayMode::BasedOnOriginTime),
+        _ => {}
+    }
+    if let Some(mode) = redundancy_mode(args) {
+        output.redundancy_mode = Some(mode);
+    }
+    for tag in args.get_many::<String>("add-tag").into_iter().flatten() {
+        if !output.tags.contains(tag) {
+            output.tags.push(tag.to_owned());
+        }
+    }
+    for tag in args.get_many::<String>("remove-tag").into_iter().flatten() {
+        output.tags.retain(|t| t != tag);
+    }
+
+    let destination = args
+        .get_one::<String>("destination")
+        .map(|d| parse_destination(d))
+        .transpose()?;
+    let source = args.get_one::<String>("source");
+    let fec = args.get_one::<String>("fec");
+    let fec_rows = args.get_one::<u8>("fec-rows");
+    let fec_cols = args.get_one::<u8>("fec-cols");
+    let latency = args.get_one::<u16>("latency");
+    if destination.is_some()
+        || source.is_some()
+        || fec.is_some()
+        || fec_rows.is_some()
+        || fec_cols.is_some()
+        || latency.is_some()
+    {
+        let [port] = output.ports.as_mut_slice() else {
+            return Err(anyhow!(
+                "Only outputs with a single port can be changed with flags, use `edgectl output edit`"
+            ));
+        };
+        if let Some((address, dest_port)) = destination {
+            match port {
+                OutputPort::Udp(UdpOutputPort {
+                    address: a,
+                    port: p,
+                    ..
+                })
+                | OutputPort::Rtp(RtpOutputPort {
+                    address: a,
+                    port: p,
+                    ..
+                })
+                | OutputPort::Rist(RistOutputPort {
+                    address: a,
+                    port: p,
+                    ..
+                })
+                | OutputPort::Srt(SrtOutputPort::Caller(SrtCallerOutputPort {
+                    remote_ip: a,
+                    remote_port: p,
+                    ..
+                })) => {
+                    *a = address;
+                    *p = dest_port;
+                }
+                _ => return Err(anyhow!("Cannot change the destination of this output")),
+            }
+        }
+        if let Some(source) = source {
+            match port {
+                OutputPort::Udp(UdpOutputPort { source_address, .. })
+                | OutputPort::Rtp(RtpOutputPort { source_address, .. })
+                | OutputPort::Rist(RistOutputPort { source_address, .. }) => {
+                    *source_address = Some(source.to_owned());
+                }
+                _ => {
+                    return Err(anyhow!(
+                        "The --source flag is only supported for UDP, RTP and RIST outputs"
+                    ))
+                }
+            }
+        }
+        if fec.is_some() || fec_rows.is_some() || fec_cols.is_some() {
+            let OutputPort::Rtp(rtp) = port else {
+                return Err(anyhow!("FEC is only supported for RTP outputs"));
+            };
+            match fec.map(|s| s.as_str()) {
+                Some("1D") => rtp.fec = Some(OutputPortFec::Fec1D),
+                Some("2D") => rtp.fec = Some(OutputPortFec::Fec2D),
+                Some(_) => {
+                    rtp.fec = None;
+                    rtp.fec_rows = None;
+                    rtp.fec_cols = None;
+                }
+                None => {}
+            }
+            if rtp.fec.is_some() {
+                rtp.fec_rows = fec_rows.copied().or(rtp.fec_rows);
+                rtp.fec_cols = fec_cols.copied().or(rtp.fec_cols);
+                if rtp.fec_rows.is_none() || rtp.fec_cols.is_none() {
+                    return Err(anyhow!("FEC requires --fec-rows and --fec-cols"));
+                }
+            } else if fec_rows.is_some() || fec_cols.is_some() {
+                return Err(anyhow!("FEC is not enabled, use --fec 1D or --fec 2D"));
+            }
+        }
+        if let Some(latency) = latency {
+            match port {
+                OutputPort::Srt(SrtOutputPort::Listener(SrtListenerOutputPort {
+                    latency: l,
+                    ..
+                }))
+                | OutputPort::Srt(SrtOutputPort::Caller(SrtCallerOutputPort {
+                    latency: l, ..
+                }))
+                | OutputPort::Srt(SrtOutputPort::Rendezvous(SrtRendezvousOutputPort {
+                    latency: l,
+                    ..
+                })) => *l = *latency,
+                _ => return Err(anyhow!("The latency can only be changed for SRT outputs")),
+            }
+        }
+    }
+
+    client
+        .update_output(&output)
+        .context("Failed to update output")?;
+    println!("Updated output {}", output.name);
+    Ok(())
+}
+
+fn edit(client: &EdgeClient, name: &str) -> anyhow::Result<()> {
+    let output = manifest::find_output(client, name)?.ok_or_else(|| anyhow!("Output not found"))?;
+    let Some(mut edited) = editor::edit(&output)? else {
+        println!("Output {} unchanged", name);
+        return Ok(());
+    };
+    // The ID identifies the output to update and cannot be changed
+    edited.id = output.id;
+    client
+        .update_output(&edited)
+        .context("Failed to update output")?;
+    println!("Updated output {}", edited.name);
+    Ok(())
+}
+
+// The redundancy mode given with --redundancy-mode, if any
+fn redundancy_mode(args: &ArgMatches) -> Option<OutputRedundancyMode> {
+    match args
+        .get_one::<String>("redundancy-mode")
+        .map(|s| s.as_str())
+    {
+        Some("none") => Some(OutputRedundancyMode::None),
+        Some("failover") => Some(OutputRedundancyMode::Failover),
+        Some("active") => Some(OutputRedundancyMode::Active),
+        _ => None,
+    }
+}
+
+// Splits a destination in format ip:port
+pub(crate) fn parse_destination(dest: &str) -> anyhow::Result<(String, u16)> {
+    let (address, port) = dest
+        .rsplit_once(':')
+        .ok_or_else(|| anyhow!("The destination {} should be in format ip:port", dest))?;
+    let port = port.parse::<u16>().map_err(|_| {
+        anyhow!(
+            "The port of {} needs to be a number between 0 and 65535",
+            dest
+        )
+    })?;
+    Ok((address.to_owned(), port))
+}
+
+fn set_input(
+    client: &EdgeClient,
+    output_name: &str,
+    input_name: &str,
+    timeout: Option<Duration>,
+) -> anyhow::Result<()> {
+    let output =
+        manifest::find_output(client, output_name)?.ok_or_else(|| anyhow!("Output not found"))?;
+    let input = manifest::find_input(client, input_name)?
+        .ok_or_else(|| anyhow!("Input {} not found", input_name))?;
+    if output.input.as_deref() == Some(input.id.as_str()) {
+        println!("Output {} already uses input {}", output.name, input.name);
+        return Ok(());
+    }
+
+    let mut last_health = health_fmt(&output);
+    println!("Health: {}", last_health);
+    client
+        .set_output_input(&output.id, &input.id)
+        .context("Failed to update output")?;
+    println!("Output {} now uses input {}", output.name, input.name);
+
+    let Some(timeout) = timeout else {
+        return Ok(());
+    };
+    // Right after the switch the output still reports the health it had with
+    // the old input, so an output that is healthy at once is only trusted
+    // once its health changed or it stayed healthy for a while
+    let settle = SETTLE_PERIOD.min(timeout);
+    let mut health_changed = false;
+    let start = Instant::now();
+    loop {
+        let output = client
+            .get_output(&output.id)
+            .context("Failed to get output")?;
+        let health = health_fmt(&output);
+        if health != last_health {
+            println!("Health: {} after {}s", health, start.elapsed().as_secs());
+            last_health = health;
+            health_changed = true;
+        }
+        if let Some(OutputHealth {
+            state: OutputHealthState::AllOk,
+            ..
+        }) = output.health
+        {
+            if health_changed || start.elapsed() >= settle {
+                return Ok(());
+            }
+        }
+        if start.elapsed() >= timeout {
+            return Err(anyhow!(
+                "Output is not healthy after {}",
+                humantime::format_duration(timeout)
+            ));
+        }
+        thread::sleep(Duration::from_secs(1));
+    }
+}
+
+fn set_admin_status(
+    client: &EdgeClient,
+    name: &str,
+    admin_status: OutputAdminStatus,
+) -> anyhow::Result<()> {
+    let outputs = if glob::is_pattern(name) {
+        client
+            .query_outputs(glob::query(name))
+            .context("Failed to list outputs")?
+            .into_iter()
+            .filter(|o| glob::matches(name, &o.name))
+            .collect::<Vec<_>>()
+    } else {
+        manifest::find_output(client, name)?.into_iter().collect()
+    };
+    if outputs.is_empty() {
+        return Err(anyhow!("Output not found"));
+    }
+    // Keep going when a pattern matches several outputs and one of them fails
+    let mut failed = 0;
+    for output in &outputs {
+        if let Err(e) = client.set_output_admin_status(&output.id, admin_status.clone()) {
+            eprintln!("Failed to update output {}: {}", output.name, e);
+            failed += 1;
+            continue;
+        }
+        match admin_status {
+            OutputAdminStatus::On => println!("Enabled output {}", output.name),
+            OutputAdminStatus::Off => println!("Disabled output {}", output.name),
+        }
     }
+    if failed > 0 {
+        return Err(anyhow!(
+            "{} of {} outputs could not be updated",
+            failed,
+            outputs.len()
+        ));
+    }
+    Ok(())
 }
 
 fn delete(client: &EdgeClient, name: &str) -> anyhow::Result<()> {
@@ -894,3 +1847,79 @@ fn delete(client: &EdgeClient, name: &str) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+// Copies an output to the installation of another context by way of its
+// manifest, so that groups and physical ports are mapped by name
+fn transfer(
+    client: &EdgeClient,
+    name: &str,
+    to_context: &str,
+    remove_source: bool,
+) -> anyhow::Result<()> {
+    let output = manifest::find_output(client, name)?.ok_or_else(|| anyhow!("Output not found"))?;
+    let mut manifest = OutputManifest::from_output(&output, &mut Resolver::new(client))?;
+
+    let destination = new_client_for_context(to_context);
+    if manifest::find_output(&destination, name)?.is_some() {
+        return Err(anyhow!("Output already exists in context {}", to_context));
+    }
+    let mut resolver = Resolver::new(&destination);
+    allocate_regional_ports(&destination, &mut resolver, &mut manifest)?;
+    let new = manifest.to_new_output(&mut resolver)?;
+    destination
+        .create_output(new)
+        .with_context(|| format!("Failed to create output in context {}", to_context))?;
+    println!("Created output {} in context {}", name, to_context);
+
+    if remove_source {
+        client
+            .delete_output(&output.id)
+            .context("Failed to delete the source output")?;
+        println!("Deleted output {}", name);
+    }
+    Ok(())
+}
+
+// The ports of regional outputs are allocated by the region instead of being
+// bound to an appliance, so they get a new port in the region of the same name
+fn allocate_regional_ports(
+    destination: &EdgeClient,
+    resolver: &mut Resolver,
+    manifest: &mut OutputManifest,
+) -> anyhow::Result<()> {
+    let input = manifest.input.clone();
+    for port in &mut manifest.ports {
+        let Some(region) = port.settings.remove("region") else {
+            continue;
+        };
+        let region_name = region
+            .get("name")
+            .and_then(|n| n.as_str())
+            .ok_or_else(|| anyhow!("The region of a port has no name"))?;
+        let region = manifest::find_region(destination, region_name)?
+            .ok_or_else(|| anyhow!("Region {} not found", region_name))?;
+        let input = input
+            .as_deref()
+            .ok_or_else(|| anyhow!("Regional outputs without an input cannot be copied"))?;
+
+        let allocated = destination
+            .allocate_port(NewPortAllocation {
+                purpose: "output".to_owned(),
+                region_id: region.id,
+                input_id: resolver.input_id(input)?,
+            })
+            .with_context(|| format!("Failed to allocate a port in region {}", region.name))?;
+        let physical_port = destination
+            .get_port(&allocated.physical_port.id)
+            .with_context(|| {
+                format!("Failed to get physical port {}", allocated.physical_port.id)
+            })?;
+        port.appliance = physical_port.appliance.name;
+        port.interface = physical_port.name;
+        port.settings
+            .insert("localPort".to_owned(), Value::from(allocated.port_number));
+        port.settings
+            .insert("allocatedPortId".to_owned(), Value::String(allocated.id));
+    }
+    Ok(())
+}
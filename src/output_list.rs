@@ -1,13 +1,21 @@
 use clap::{parser::ValuesRef, Arg, ArgMatches, Command};
+use serde::Serialize;
 use tabled::{builder::Builder, settings::Style};
 
-use crate::edge::{new_client, NewOutputRecipientList};
+use crate::client::new_client;
+use crate::edge::{NewOutputRecipientList, Output, OutputRecipientList};
+use crate::format::{self, Format};
+use crate::OrExit;
 
 pub(crate) fn subcommand() -> clap::Command {
     Command::new("output-list")
         .about("Manage output lists")
         .subcommand_required(true)
-        .subcommand(Command::new("list").about("List all output lists"))
+        .subcommand(
+            Command::new("list")
+                .about("List all output lists")
+                .arg(format::arg(&["short"])),
+        )
         .subcommand(
             Command::new("show")
                 .about("Show details of an output list")
@@ -15,7 +23,8 @@ pub(crate) fn subcommand() -> clap::Command {
                     Arg::new("name")
                         .required(true)
                         .help("The name of the output list"),
-                ),
+                )
+                .arg(format::arg(&["text"])),
         )
         .subcommand(
             Command::new("create")
@@ -87,7 +96,7 @@ pub(crate) fn subcommand() -> clap::Command {
 
 pub(crate) fn run(args: &ArgMatches) {
     match args.subcommand() {
-        Some(("list", _)) => list(),
+        Some(("list", sub_args)) => list(format::structured(sub_args)),
         Some(("show", sub_args)) => show(sub_args),
         Some(("create", sub_args)) => create(sub_args),
         Some(("delete", sub_args)) => delete(sub_args),
@@ -100,11 +109,16 @@ pub(crate) fn run(args: &ArgMatches) {
     }
 }
 
-fn list() {
+fn list(format: Option<Format>) {
     let client = new_client();
     let lists = client
         .list_output_recipient_lists()
-        .expect("Failed to list output lists");
+        .or_exit("Failed to list output lists");
+
+    if let Some(format) = format {
+        format::print(format, &lists);
+        return;
+    }
 
     if lists.is_empty() {
         println!("No output lists found");
@@ -130,7 +144,7 @@ fn show(args: &ArgMatches) {
 
     let lists = client
         .find_output_recipient_lists(name)
-        .expect("Failed to find output list");
+        .or_exit("Failed to find output list");
 
     if lists.is_empty() {
         eprintln!("Output list '{}' not found", name);
@@ -141,7 +155,18 @@ fn show(args: &ArgMatches) {
 
     let outputs = client
         .get_output_list_members(&list.id)
-        .expect("Failed to get outputs in list");
+        .or_exit("Failed to get outputs in list");
+
+    if let Some(format) = format::structured(args) {
+        #[derive(Serialize)]
+        struct OutputListDetails<'a> {
+            #[serde(flatten)]
+            list: &'a OutputRecipientList,
+            outputs: Vec<Output>,
+        }
+        format::print(format, &OutputListDetails { list, outputs });
+        return;
+    }
 
     println!("ID:          {}", list.id);
     println!("Name:        {}", list.name);
@@ -184,7 +209,7 @@ fn create(args: &ArgMatches) {
 
     let groups = client
         .find_groups(&group_name)
-        .expect("Failed to find group");
+        .or_exit("Failed to find group");
 
     if groups.is_empty() {
         eprintln!("Group '{}' not found", group_name);
@@ -202,7 +227,7 @@ fn create(args: &ArgMatches) {
 
     client
         .create_output_recipient_list(new_list)
-        .expect("Failed to create output list");
+        .or_exit("Failed to create output list");
 }
 
 fn delete(args: &ArgMatches) {
@@ -228,7 +253,7 @@ fn delete(args: &ArgMatches) {
 
         client
             .delete_output_recipient_list(&list.id)
-            .expect("Failed to delete output list");
+            .or_exit("Failed to delete output list");
 
         println!("Deleted output list '{}'", list.name);
     }
@@ -241,7 +266,7 @@ fn add_output(args: &ArgMatches) {
 
     let lists = client
         .find_output_recipient_lists(list_name)
-        .expect("Failed to find output list");
+        .or_exit("Failed to find output list");
     if lists.is_empty() {
         eprintln!("Output list '{}' not found", list_name);
         std::process::exit(1);
@@ -252,7 +277,7 @@ fn add_output(args: &ArgMatches) {
 
     client
         .add_output_to_list(&list.id, &list.name, outputs)
-        .expect("Failed to add output to list");
+        .or_exit("Failed to add output to list");
 }
 
 fn remove_output(args: &ArgMatches) {
@@ -262,7 +287,7 @@ fn remove_output(args: &ArgMatches) {
 
     let lists = client
         .find_output_recipient_lists(list_name)
-        .expect("Failed to find output list");
+        .or_exit("Failed to find output list");
     if lists.is_empty() {
         eprintln!("Output list '{}' not found", list_name);
         std::process::exit(1);
@@ -273,7 +298,7 @@ fn remove_output(args: &ArgMatches) {
 
     client
         .remove_output_from_list(&list.id, &list.name, outputs)
-        .expect("Failed to remove output from list");
+        .or_exit("Failed to remove output from list");
 }
 
 fn get_output_ids_by_names(
@@ -0,0 +1,58 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+/// How many requests a client sends at the same time when a call needs more
+/// than one, e.g. for the pages of a list
+const MAX_CONCURRENT_REQUESTS: usize = 8;
+
+/// Calls `f` on every item from up to `MAX_CONCURRENT_REQUESTS` threads. The
+/// results are in the same order as `items`.
+pub(crate) fn map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    if items.len() <= 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let mut results = thread::scope(|s| {
+        let workers = (0..items.len().min(MAX_CONCURRENT_REQUESTS))
+            .map(|_| {
+                s.spawn(|| {
+                    let mut results = Vec::new();
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(item) = items.get(i) else {
+                            return results;
+                        };
+                        results.push((i, f(item)));
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        workers
+            .into_iter()
+            .flat_map(|worker| match worker.join() {
+                Ok(results) => results,
+                Err(panic) => std::panic::resume_unwind(panic),
+            })
+            .collect::<Vec<_>>()
+    });
+    results.sort_by_key(|(i, _)| *i);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_keeps_the_order_of_the_items() {
+        let items = (0..100).collect::<Vec<u32>>();
+        let results = map(&items, |i| i * 2);
+        assert_eq!(results, (0..100).map(|i| i * 2).collect::<Vec<u32>>());
+    }
+}
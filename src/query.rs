@@ -0,0 +1,266 @@
+//! Queries for the list endpoints of the Edge API, and lazy iteration over
+//! their pages
+
+use std::marker::PhantomData;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::edge::{EdgeClient, EdgeError};
+
+/// The objects in a list are fetched in pages of this size
+pub(crate) const PAGE_SIZE: usize = 100;
+
+/// A query for a list endpoint, sent as JSON in the `q` parameter.
+///
+/// ```
+/// use edge_api::{Query, SortOrder};
+///
+/// let query = Query::new()
+///     .admin_status(true)
+///     .group("5f3c")
+///     .sort("name", SortOrder::Ascending)
+///     .limit(10);
+/// ```
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Query {
+    filter: Filter,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sort: Option<Sort>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    skip: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    limit: Option<usize>,
+}
+
+/// The filters of a query, only the ones that are set are sent
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Filter {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    search_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ids: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    admin_status: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    group_ids: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    appliance_ids: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    input_ids: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    from_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    to_date: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Sort {
+    field: String,
+    order: SortOrder,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum SortOrder {
+    #[serde(rename = "asc")]
+    Ascending,
+    #[serde(rename = "desc")]
+    Descending,
+}
+
+impl Query {
+    /// A query for every object
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only objects whose name contains `name`
+    pub fn search_name(mut self, name: impl Into<String>) -> Self {
+        self.filter.search_name = Some(name.into());
+        self
+    }
+
+    /// Only the objects with the given ids
+    pub fn ids(mut self, ids: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.filter.ids = Some(ids.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Only enabled inputs or outputs if `on`, otherwise only disabled ones
+    pub fn admin_status(mut self, on: bool) -> Self {
+        self.filter.admin_status = Some(on.into());
+        self
+    }
+
+    /// Only objects owned by the group with `id`, can be repeated
+    pub fn group(mut self, id: impl Into<String>) -> Self {
+        self.filter
+            .group_ids
+            .get_or_insert_with(Vec::new)
+            .push(id.into());
+        self
+    }
+
+    /// Only objects running on the appliance with `id`, can be repeated
+    pub fn appliance(mut self, id: impl Into<String>) -> Self {
+        self.filter
+            .appliance_ids
+            .get_or_insert_with(Vec::new)
+            .push(id.into());
+        self
+    }
+
+    /// Only alarms of the input with `id`, can be repeated
+    pub fn input(mut self, id: impl Into<String>) -> Self {
+        self.filter
+            .input_ids
+            .get_or_insert_with(Vec::new)
+            .push(id.into());
+        self
+    }
+
+    /// Only alarms raised at or after `date`, in RFC 3339
+    pub fn from_date(mut self, date: impl Into<String>) -> Self {
+        self.filter.from_date = Some(date.into());
+        self
+    }
+
+    /// Only alarms raised before `date`, in RFC 3339
+    pub fn to_date(mut self, date: impl Into<String>) -> Self {
+        self.filter.to_date = Some(date.into());
+        self
+    }
+
+    pub fn sort(mut self, field: impl Into<String>, order: SortOrder) -> Self {
+        self.sort = Some(Sort {
+            field: field.into(),
+            order,
+        });
+        self
+    }
+
+    /// Skips the first `skip` objects
+    pub fn skip(mut self, skip: usize) -> Self {
+        self.skip = Some(skip);
+        self
+    }
+
+    /// Returns at most `limit` objects in total, over all pages
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// The query for `limit` objects starting at `skip`, keeping the filters
+    pub(crate) fn page(&self, skip: usize, limit: usize) -> Self {
+        Self {
+            skip: Some(skip),
+            limit: Some(limit),
+            ..self.clone()
+        }
+    }
+
+    pub(crate) fn first(&self) -> usize {
+        self.skip.unwrap_or(0)
+    }
+
+    /// The index after the last object to return, if limited
+    pub(crate) fn end(&self) -> Option<usize> {
+        self.limit.map(|limit| self.first() + limit)
+    }
+
+    /// The query as an URL encoded `q` parameter
+    pub(crate) fn to_param(&self) -> String {
+        let json = serde_json::to_string(self).expect("Failed to serialize query as JSON");
+        format!("q={}", urlencoding::encode(&json))
+    }
+}
+
+/// Iterates lazily over the pages of a list, one request per page. Stops
+/// after the first error.
+pub struct Pages<'a, T> {
+    client: &'a EdgeClient,
+    path: &'static str,
+    query: Query,
+    next: usize,
+    done: bool,
+    items: PhantomData<T>,
+}
+
+impl<'a, T: DeserializeOwned> Pages<'a, T> {
+    pub(crate) fn new(client: &'a EdgeClient, path: &'static str, query: Query) -> Self {
+        Self {
+            client,
+            path,
+            next: query.first(),
+            query,
+            done: false,
+            items: PhantomData,
+        }
+    }
+
+    /// Iterates over the objects instead of the pages
+    pub fn items(self) -> impl Iterator<Item = Result<T, EdgeError>> + 'a
+    where
+        T: 'a,
+    {
+        self.flat_map(|page| match page {
+            Ok(items) => items.into_iter().map(Ok).collect::<Vec<_>>(),
+            Err(e) => vec![Err(e)],
+        })
+    }
+}
+
+impl<T: DeserializeOwned> Iterator for Pages<'_, T> {
+    type Item = Result<Vec<T>, EdgeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let limit = match self.query.end() {
+            Some(end) if end <= self.next => return None,
+            Some(end) => PAGE_SIZE.min(end - self.next),
+            None => PAGE_SIZE,
+        };
+
+        match self
+            .client
+            .list_page::<T>(self.path, &self.query.page(self.next, limit))
+        {
+            Ok(page) => {
+                self.done = page.items.len() < limit;
+                self.next += limit;
+                Some(Ok(page.items))
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_only_serializes_set_filters() {
+        let query = Query::new().admin_status(true).group("a").group("b");
+        assert_eq!(
+            serde_json::to_string(&query).unwrap(),
+            r#"{"filter":{"adminStatus":1,"groupIds":["a","b"]}}"#
+        );
+    }
+
+    #[test]
+    fn it_url_encodes_the_query() {
+        let query = Query::new().search_name("a&b c").page(100, 50);
+        assert_eq!(
+            query.to_param(),
+            "q=%7B%22filter%22%3A%7B%22searchName%22%3A%22a%26b%20c%22%7D%2C%22skip%22%3A100%2C%22limit%22%3A50%7D"
+        );
+    }
+}
@@ -1,14 +1,21 @@
-use std::{fmt, process};
+use std::process;
 
 use clap::{Arg, ArgMatches, Command};
 use tabled::{builder::Builder, settings::Style};
 
-use crate::edge::{new_client, EdgeClient, ExternalRegionMode, NewRegion};
+use crate::client::new_client;
+use crate::edge::{EdgeClient, ExternalRegionMode, NewRegion};
+use crate::exit_with;
+use crate::format::{self, Format};
 
 pub(crate) fn subcommand() -> clap::Command {
     Command::new("region")
         .about("Manage regions")
-        .subcommand(Command::new("list").about("List regions"))
+        .subcommand(
+            Command::new("list")
+                .about("List regions")
+                .arg(format::arg(&["short"])),
+        )
         .subcommand(
             Command::new("create").arg(
                 Arg::new("name")
@@ -27,7 +34,8 @@ pub(crate) fn subcommand() -> clap::Command {
 
 pub(crate) fn run(subcmd: &ArgMatches) {
     match subcmd.subcommand() {
-        Some(("list", _)) | None => list(new_client()),
+        Some(("list", args)) => list(new_client(), format::structured(args)),
+        None => list(new_client(), None),
         Some(("create", args)) => {
             let client = new_client();
             let name = args
@@ -48,25 +56,19 @@ pub(crate) fn run(subcmd: &ArgMatches) {
     }
 }
 
-impl fmt::Display for ExternalRegionMode {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::Core => f.pad("core"),
-            Self::ExternalK8s => f.pad("external kubernetes"),
-            Self::External => f.pad("external"),
-        }
-    }
-}
-
-fn list(client: EdgeClient) {
+fn list(client: EdgeClient, format: Option<Format>) {
     let regions = match client.list_regions() {
         Ok(regions) => regions,
         Err(e) => {
-            eprintln!("Failed to list regions: {}", e);
-            process::exit(1);
+            exit_with(anyhow::Error::from(e).context("Failed to list regions"));
         }
     };
 
+    if let Some(format) = format {
+        format::print(format, &regions);
+        return;
+    }
+
     let mut builder = Builder::default();
     builder.push_record(["Name", "ID", "is default", "Type"]);
     for region in regions {
@@ -108,8 +110,7 @@ fn delete(client: EdgeClient, name: &str) {
             process::exit(1);
         }
         Err(e) => {
-            eprintln!("Failed to list inputs for deleteion: {}", e);
-            process::exit(1);
+            exit_with(anyhow::Error::from(e).context("Failed to list inputs for deleteion"));
         }
     };
     if let Err(e) = client.delete_region(&region.id) {
@@ -1,6 +1,6 @@
-use std::process;
-
-use crate::edge::{new_client, EdgeClient};
+use crate::client::new_client;
+use crate::edge::EdgeClient;
+use crate::{exit_with, OrExit};
 
 use clap::{builder::PossibleValuesParser, Arg, ArgMatches, Command};
 
@@ -43,7 +43,7 @@ pub(crate) fn run(subcmd: &ArgMatches) {
 fn list(client: EdgeClient) {
     let settings = client
         .global_settings()
-        .expect("Failed to fetch global settingst");
+        .or_exit("Failed to fetch global settingst");
     eprintln!("Log level:   {:?}", settings.log_level);
 }
 
@@ -52,14 +52,12 @@ fn set_log_level(client: EdgeClient, level: crate::edge::LogLevel) {
     let mut settings = match client.global_settings() {
         Ok(s) => s,
         Err(e) => {
-            eprintln!("Failed to fetch settings for update: {}", e);
-            process::exit(1);
+            exit_with(anyhow::Error::from(e).context("Failed to fetch settings for update"));
         }
     };
     settings.log_level = level;
 
     if let Err(e) = client.set_global_settings(settings) {
-        eprintln!("Failed to set log level: {}", e);
-        process::exit(1);
+        exit_with(anyhow::Error::from(e).context("Failed to set log level"));
     }
 }
@@ -0,0 +1,125 @@
+use std::net::IpAddr;
+
+use anyhow::anyhow;
+use clap::{builder::PossibleValuesParser, Arg, ArgAction, ArgMatches};
+
+use crate::edge::{SrtKeylen, SrtRateLimiting};
+
+const DEFAULT_LATENCY: u16 = 120;
+
+/// The SRT settings shared by `input create` and `output create`
+pub(crate) struct Settings {
+    pub latency: u16,
+    pub passphrase: Option<String>,
+    pub keylen: SrtKeylen,
+    // Who may connect to listeners and rendezvous ports
+    pub whitelist: Vec<String>,
+}
+
+/// The arguments of `input create` and `output create` that tune SRT ports
+pub(crate) fn args() -> [Arg; 4] {
+    [
+        Arg::new("latency")
+            .long("latency")
+            .value_parser(clap::value_parser!(u16))
+            .help("The SRT latency in milliseconds [default: 120]"),
+        Arg::new("passphrase")
+            .long("passphrase")
+            .help("Encrypt the SRT stream with this passphrase of 10 to 79 characters"),
+        Arg::new("keylen")
+            .long("keylen")
+            .value_parser(PossibleValuesParser::new(["128", "192", "256"]))
+            .requires("passphrase")
+            .help("The AES key length in bits of encrypted SRT streams [default: 128]"),
+        Arg::new("whitelist")
+            .long("whitelist")
+            .action(ArgAction::Append)
+            .conflicts_with("caller")
+            .help("Only accept SRT connections from this CIDR block, can be repeated [default: 0.0.0.0/0]"),
+    ]
+}
+
+/// The `--rate-limit` argument of `output create`
+pub(crate) fn rate_limit_arg() -> Arg {
+    Arg::new("rate-limit")
+        .long("rate-limit")
+        .value_parser(PossibleValuesParser::new(["absolute", "relative"]))
+        .help("Limit the bandwidth of SRT outputs, absolute or relative to the input")
+}
+
+/// The SRT settings in `args`
+pub(crate) fn settings(args: &ArgMatches) -> anyhow::Result<Settings> {
+    let passphrase = args.get_one::<String>("passphrase").cloned();
+    if let Some(passphrase) = &passphrase {
+        if !(10..=79).contains(&passphrase.chars().count()) {
+            return Err(anyhow!(
+                "The SRT passphrase needs to be 10 to 79 characters long"
+            ));
+        }
+    }
+    let keylen = match (
+        &passphrase,
+        args.get_one::<String>("keylen").map(|s| s.as_str()),
+    ) {
+        (None, _) => SrtKeylen::None,
+        (Some(_), Some("192")) => SrtKeylen::Aes192,
+        (Some(_), Some("256")) => SrtKeylen::Aes256,
+        (Some(_), _) => SrtKeylen::Aes128,
+    };
+    let whitelist = match args.get_many::<String>("whitelist") {
+        Some(blocks) => blocks
+            .map(|block| parse_cidr(block).map(|_| block.to_owned()))
+            .collect::<anyhow::Result<Vec<_>>>()?,
+        None => vec!["0.0.0.0/0".to_owned()],
+    };
+
+    Ok(Settings {
+        latency: args
+            .get_one::<u16>("latency")
+            .copied()
+            .unwrap_or(DEFAULT_LATENCY),
+        passphrase,
+        keylen,
+        whitelist,
+    })
+}
+
+/// The rate limiting of SRT outputs in `args`
+pub(crate) fn rate_limiting(args: &ArgMatches) -> SrtRateLimiting {
+    match args.get_one::<String>("rate-limit").map(|s| s.as_str()) {
+        Some("absolute") => SrtRateLimiting::Absolute,
+        Some("relative") => SrtRateLimiting::RelativeToInput,
+        _ => SrtRateLimiting::NotEnforced,
+    }
+}
+
+fn parse_cidr(block: &str) -> anyhow::Result<(IpAddr, u8)> {
+    let invalid = || {
+        anyhow!(
+            "Invalid CIDR block {}, expected e.g. 198.51.100.0/24",
+            block
+        )
+    };
+    let (address, prefix) = block.split_once('/').ok_or_else(invalid)?;
+    let address = address.parse::<IpAddr>().map_err(|_| invalid())?;
+    let prefix = prefix.parse::<u8>().map_err(|_| invalid())?;
+    let max = if address.is_ipv4() { 32 } else { 128 };
+    if prefix > max {
+        return Err(invalid());
+    }
+    Ok((address, prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_cidr_blocks() {
+        assert!(parse_cidr("198.51.100.0/24").is_ok());
+        assert!(parse_cidr("2001:db8::/32").is_ok());
+        assert!(parse_cidr("198.51.100.0").is_err());
+        assert!(parse_cidr("198.51.100.0/33").is_err());
+        assert!(parse_cidr("edge-1/24").is_err());
+    }
+}
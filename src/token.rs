@@ -1,14 +1,72 @@
-use crate::config::Config;
-use crate::edge;
-use clap::{Arg, ArgMatches, Command};
+use crate::client::{new_client, selected_context};
+use crate::config::{Config, ContextConfig};
+use crate::credentials;
+use crate::edge::{ApiTokenInit, EdgeClient};
+use crate::format::{self, Format};
+use crate::{exit_with, OrExit};
+use anyhow::{anyhow, Context};
+use chrono::{DateTime, Duration, Utc};
+use clap::{builder::PossibleValuesParser, Arg, ArgAction, ArgMatches, Command};
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::sync::Mutex;
 use tabled::builder::Builder;
 use tabled::settings::Style;
 
+/// How long the tokens created by edgectl are valid
+pub(crate) const TOKEN_LIFETIME_DAYS: i64 = 90;
+
+/// Tokens expiring sooner than this are warned about, or rotated if enabled
+const RENEW_BEFORE_DAYS: i64 = 14;
+
+// Clients for several contexts can be created in parallel, keep them from
+// overwriting each other's changes to the config
+static CONFIG_LOCK: Mutex<()> = Mutex::new(());
+
 pub fn subcommand() -> Command {
     Command::new("token")
         .about("Manage API tokens")
         .subcommand_required(true)
-        .subcommand(Command::new("list").about("List all API tokens"))
+        .subcommand(
+            Command::new("list")
+                .about("List all API tokens")
+                .arg(format::arg(&["short"])),
+        )
+        .subcommand(
+            Command::new("create")
+                .about("Create an API token, e.g. for a service account")
+                .arg(
+                    Arg::new("name")
+                        .required(true)
+                        .help("The name of the token"),
+                )
+                .arg(
+                    Arg::new("role")
+                        .long("role")
+                        .required(true)
+                        .help("The role of the token, e.g. admin"),
+                )
+                .arg(
+                    Arg::new("expires-in")
+                        .long("expires-in")
+                        .default_value("90d")
+                        .value_parser(humantime::parse_duration)
+                        .help("How long the token is valid, e.g. 30d or 1y"),
+                )
+                .arg(
+                    Arg::new("scope")
+                        .long("scope")
+                        .action(ArgAction::Append)
+                        .help("A scope of the token, can be repeated [default: api]"),
+                )
+                .arg(
+                    Arg::new("file")
+                        .long("file")
+                        .short('f')
+                        .help("Write the token to this file, readable only by the owner, instead of printing it"),
+                ),
+        )
         .subcommand(
             Command::new("delete").about("Delete an API token").arg(
                 Arg::new("name")
@@ -16,12 +74,27 @@ pub fn subcommand() -> Command {
                     .help("The name of the token to delete"),
             ),
         )
+        .subcommand(
+            Command::new("rotate").about("Replace the token of the current context with a new one"),
+        )
+        .subcommand(
+            Command::new("auto-rotate")
+                .about("Replace the token of the current context automatically before it expires")
+                .arg(
+                    Arg::new("state")
+                        .required(true)
+                        .value_parser(PossibleValuesParser::new(["on", "off"])),
+                ),
+        )
 }
 
 pub fn run(args: &ArgMatches) {
     match args.subcommand() {
-        Some(("list", _)) => list(),
+        Some(("list", sub_args)) => list(format::structured(sub_args)),
+        Some(("create", sub_args)) => create(sub_args),
         Some(("delete", sub_args)) => delete(sub_args),
+        Some(("rotate", _)) => rotate_current(),
+        Some(("auto-rotate", sub_args)) => auto_rotate(sub_args),
         _ => {
             eprintln!("Unknown subcommand");
             std::process::exit(1);
@@ -29,10 +102,17 @@ pub fn run(args: &ArgMatches) {
     }
 }
 
-fn list() {
-    let client = edge::new_client();
+fn list(format: Option<Format>) {
+    let client = new_client();
+
+    let tokens = client
+        .list_api_tokens()
+        .or_exit("Failed to list API tokens");
 
-    let tokens = client.list_api_tokens().expect("Failed to list API tokens");
+    if let Some(format) = format {
+        format::print(format, &tokens);
+        return;
+    }
 
     let mut builder = Builder::default();
     builder.push_record(["Name", "Role", "Expires At", "Scopes"]);
@@ -53,11 +133,81 @@ fn list() {
     println!("{}", table);
 }
 
+fn create(args: &ArgMatches) {
+    let name = args.get_one::<String>("name").unwrap();
+    let role = args.get_one::<String>("role").unwrap();
+    let expires_in = *args.get_one::<std::time::Duration>("expires-in").unwrap();
+    let scopes = args
+        .get_many::<String>("scope")
+        .map(|scopes| scopes.cloned().collect())
+        .unwrap_or_else(|| vec!["api".to_owned()]);
+    let expires_in = Duration::from_std(expires_in).unwrap_or_else(|_| {
+        eprintln!("The lifetime of the token is too long");
+        std::process::exit(1);
+    });
+
+    let client = new_client();
+    let token = client
+        .create_api_token(ApiTokenInit {
+            name: name.to_owned(),
+            role: role.to_owned(),
+            expires_at: (Utc::now() + expires_in).to_rfc3339(),
+            scopes,
+        })
+        .unwrap_or_else(|e| {
+            exit_with(anyhow::Error::from(e).context("Failed to create API token"));
+        });
+    let secret = token.token.unwrap_or_else(|| {
+        eprintln!("The API did not return the secret of token '{}'", name);
+        std::process::exit(1);
+    });
+
+    // The secret cannot be fetched again, so it is only shown this once
+    match args.get_one::<String>("file") {
+        Some(path) => {
+            if let Err(e) = write_secret(path, &secret) {
+                eprintln!("Failed to write token to {}: {:#}", path, e);
+                eprintln!("Delete it with 'edgectl token delete {}'", name);
+                std::process::exit(1);
+            }
+            println!(
+                "Created token '{}' expiring at {}, written to {}",
+                name, token.expires_at, path
+            );
+        }
+        None => {
+            eprintln!("Created token '{}' expiring at {}", name, token.expires_at);
+            println!("{}", secret);
+        }
+    }
+}
+
+fn write_secret(path: &str, secret: &str) -> anyhow::Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    // The mode only applies to new files
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+    }
+    writeln!(file, "{}", secret)?;
+    Ok(())
+}
+
 fn delete(args: &ArgMatches) {
     let name = args.get_one::<String>("name").unwrap();
-    let client = edge::new_client();
+    let client = new_client();
 
-    let tokens = client.list_api_tokens().expect("Failed to list API tokens");
+    let tokens = client
+        .list_api_tokens()
+        .or_exit("Failed to list API tokens");
 
     let token = tokens.iter().find(|t| &t.name == name).unwrap_or_else(|| {
         eprintln!("Token '{}' not found", name);
@@ -74,7 +224,189 @@ fn delete(args: &ArgMatches) {
 
     client
         .delete_api_token(&token.id)
-        .expect("Failed to delete API token");
+        .or_exit("Failed to delete API token");
 
     println!("Deleted token '{}'", name);
 }
+
+fn current_context() -> (String, ContextConfig) {
+    let name = selected_context().unwrap_or_else(|| {
+        eprintln!("No context selected. Run 'edgectl login' to authenticate.");
+        std::process::exit(1);
+    });
+    let context = Config::load()
+        .contexts
+        .get(&name)
+        .cloned()
+        .unwrap_or_else(|| {
+            eprintln!("Context '{}' does not exist", name);
+            std::process::exit(1);
+        });
+    (name, context)
+}
+
+fn rotate_current() {
+    let (name, context) = current_context();
+
+    let context = credentials::token(&name, &context)
+        .and_then(|token| {
+            Ok(EdgeClient::with_options(
+                &context.url,
+                &token,
+                &context.http_options(),
+            )?)
+        })
+        .and_then(|client| rotate(&client, &name, &context))
+        .unwrap_or_else(|e| {
+            exit_with(e.context("Failed to rotate token"));
+        });
+
+    println!(
+        "Rotated the token of context '{}', the new token '{}' expires at {}",
+        name,
+        context.token_name,
+        context.expires_at.unwrap_or_default()
+    );
+}
+
+fn auto_rotate(args: &ArgMatches) {
+    let (name, mut context) = current_context();
+    context.auto_rotate = args.get_one::<String>("state").map(|s| s.as_str()) == Some("on");
+    let enabled = context.auto_rotate;
+
+    if let Err(e) = save_context(&name, context) {
+        exit_with(e.context("Failed to save configuration"));
+    }
+    println!(
+        "Automatic token rotation {} for context '{}'",
+        if enabled { "enabled" } else { "disabled" },
+        name
+    );
+}
+
+/// The name of a new token, unique per host and second
+pub(crate) fn new_token_name() -> String {
+    let hostname = env::var("HOSTNAME")
+        .ok()
+        .unwrap_or(gethostname::gethostname().to_string_lossy().to_string());
+    let timestamp = Utc::now().format("%Y%m%d%H%M%S").to_string();
+    format!("edgectl-{}-{}", hostname, timestamp)
+}
+
+/// Creates a replacement for the token of context `name` with the same role
+/// and scopes, saves it and deletes the old token. The old token is only
+/// deleted once the new one is saved, so that a failure never leaves the
+/// context without a working token.
+pub(crate) fn rotate(
+    client: &EdgeClient,
+    name: &str,
+    context: &ContextConfig,
+) -> anyhow::Result<ContextConfig> {
+    let tokens = client
+        .list_api_tokens()
+        .context("Failed to list API tokens")?;
+    let old = tokens
+        .into_iter()
+        .find(|t| t.name == context.token_name)
+        .ok_or_else(|| {
+            anyhow!(
+                "Token '{}' not found, run 'edgectl login' to create a new one",
+                context.token_name
+            )
+        })?;
+
+    let new = client
+        .create_api_token(ApiTokenInit {
+            name: new_token_name(),
+            role: old.role,
+            expires_at: (Utc::now() + Duration::days(TOKEN_LIFETIME_DAYS)).to_rfc3339(),
+            scopes: old.scopes,
+        })
+        .context("Failed to create API token")?;
+    let token = new
+        .token
+        .ok_or_else(|| anyhow!("The new API token {} has no secret", new.name))?;
+
+    let rotated = save_context(
+        name,
+        ContextConfig {
+            token: token.clone(),
+            token_name: new.name.clone(),
+            expires_at: Some(new.expires_at),
+            ..context.clone()
+        },
+    )
+    .with_context(|| format!("Failed to save the new API token {}", new.name))?;
+
+    // Authenticate with the new token, the old one stops working. The new
+    // token is in use by now, so a token that is left behind is no reason to
+    // fail.
+    if let Err(e) = EdgeClient::with_options(&context.url, &token, &context.http_options())
+        .map_err(anyhow::Error::from)
+        .and_then(|client| Ok(client.delete_api_token(&old.id)?))
+    {
+        eprintln!(
+            "Warning: failed to delete the old API token {}, delete it with 'edgectl token delete': {:#}",
+            old.name, e
+        );
+    }
+
+    Ok(rotated)
+}
+
+/// Warns if the token of a stored context is about to expire, or replaces it
+/// if automatic rotation is enabled. Returns the context to connect with.
+pub(crate) fn check_expiry(name: &str, context: ContextConfig) -> ContextConfig {
+    let Some(expires_at) = context
+        .expires_at
+        .as_deref()
+        .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+    else {
+        return context;
+    };
+    let remaining = expires_at.to_utc() - Utc::now();
+    if remaining > Duration::days(RENEW_BEFORE_DAYS) {
+        return context;
+    }
+    if remaining <= Duration::zero() {
+        eprintln!(
+            "Warning: the API token of context '{}' has expired. Run 'edgectl login' to create a new one.",
+            name
+        );
+        return context;
+    }
+
+    if context.auto_rotate {
+        let rotated = credentials::token(name, &context)
+            .and_then(|token| {
+                Ok(EdgeClient::with_options(
+                    &context.url,
+                    &token,
+                    &context.http_options(),
+                )?)
+            })
+            .and_then(|client| rotate(&client, name, &context));
+        match rotated {
+            Ok(new) => return new,
+            Err(e) => eprintln!(
+                "Warning: failed to rotate the API token of context '{}': {:#}",
+                name, e
+            ),
+        }
+    } else {
+        eprintln!(
+            "Warning: the API token of context '{}' expires in {} days. Run 'edgectl token rotate' to replace it.",
+            name,
+            remaining.num_days()
+        );
+    }
+    context
+}
+
+fn save_context(name: &str, context: ContextConfig) -> anyhow::Result<ContextConfig> {
+    let _lock = CONFIG_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut config = Config::load();
+    config.contexts.insert(name.to_owned(), context.clone());
+    config.save()?;
+    Ok(context)
+}
@@ -1,9 +1,14 @@
+use crate::client::new_client_for_context;
 use crate::config::Config;
-use crate::edge::{new_client, EdgeClient};
+use crate::edge::EdgeClient;
 use crate::tui::resources::{
-    clear_resource, delete_resource, fetch_resources, ResourceAction, ResourceItem, ResourceType,
+    clear_resource, delete_resource, fetch_resources, toggle_admin_status, ResourceAction,
+    ResourceItem, ResourceType,
 };
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::Arc;
+use std::thread;
 use std::time::Instant;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -21,7 +26,7 @@ pub struct ThumbnailEntry {
 }
 
 pub struct App {
-    pub client: EdgeClient,
+    pub client: Arc<EdgeClient>,
     pub current_resource_type: ResourceType,
     pub items: Vec<ResourceItem>,
     pub selected_index: usize,
@@ -37,15 +42,19 @@ pub struct App {
     pub auto_refresh_enabled: bool,
     pub content_area_height: u16,
     pub delete_button_selected: usize,
+    /// The action the confirmation dialog asks for
+    pub pending_action: Option<ResourceAction>,
     pub thumbnails: Vec<ThumbnailEntry>,
     pub inactive_channels: Vec<u32>,
     pub config: Config,
+    /// The result of the refresh running in the background, if any
+    pending_refresh: Option<Receiver<Result<Vec<ResourceItem>>>>,
 }
 
 impl App {
     pub fn new(client: EdgeClient) -> Result<Self> {
         let mut app = Self {
-            client,
+            client: Arc::new(client),
             current_resource_type: ResourceType::Input,
             items: Vec::new(),
             selected_index: 0,
@@ -61,9 +70,11 @@ impl App {
             auto_refresh_enabled: true,
             content_area_height: 24,
             delete_button_selected: 0,
+            pending_action: None,
             thumbnails: Vec::new(),
             inactive_channels: Vec::new(),
             config: Config::load(),
+            pending_refresh: None,
         };
 
         app.refresh_data()?;
@@ -74,7 +85,8 @@ impl App {
         self.config.set_current_context(context_name.to_owned())?;
         self.config.save()?;
 
-        self.client = new_client();
+        self.client = Arc::new(new_client_for_context(context_name));
+        self.items.clear();
 
         self.config = Config::load();
 
@@ -83,15 +95,42 @@ impl App {
         Ok(())
     }
 
+    /// Starts fetching the items of the current resource type in the
+    /// background, `poll_refresh` picks up the result
     pub fn refresh_data(&mut self) -> Result<()> {
         self.loading = true;
         self.error_message = None;
 
-        match fetch_resources(&self.client, self.current_resource_type) {
+        let (tx, rx) = mpsc::channel();
+        let client = Arc::clone(&self.client);
+        let resource_type = self.current_resource_type;
+        thread::spawn(move || {
+            // The receiver is gone if another refresh has been started since
+            let _ = tx.send(fetch_resources(&client, resource_type));
+        });
+        self.pending_refresh = Some(rx);
+
+        Ok(())
+    }
+
+    /// Applies the result of the background refresh once it is done. Returns
+    /// true if the items were updated.
+    pub fn poll_refresh(&mut self) -> bool {
+        let Some(rx) = &self.pending_refresh else {
+            return false;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return false,
+            Err(TryRecvError::Disconnected) => Err(anyhow!("the request thread stopped")),
+        };
+        self.pending_refresh = None;
+        self.loading = false;
+        self.last_refresh = Instant::now();
+
+        match result {
             Ok(items) => {
                 self.items = items;
-                self.last_refresh = Instant::now();
-                self.loading = false;
 
                 if self.selected_index >= self.items.len() && !self.items.is_empty() {
                     self.selected_index = self.items.len() - 1;
@@ -103,17 +142,16 @@ impl App {
                 {
                     self.view_mode = ViewMode::Describe;
                 }
+                true
             }
             Err(e) => {
                 self.error_message = Some(format!(
                     "Failed to fetch {}: {}",
                     self.current_resource_type, e
                 ));
-                self.loading = false;
+                false
             }
         }
-
-        Ok(())
     }
 
     pub fn switch_resource(&mut self, resource_type: ResourceType) -> Result<()> {
@@ -121,13 +159,8 @@ impl App {
         self.selected_index = 0;
         self.scroll_offset = 0;
         self.view_mode = ViewMode::List;
-        self.refresh_data()?;
-
-        if resource_type.is_single_item() && !self.items.is_empty() {
-            self.view_mode = ViewMode::Describe;
-        }
-
-        Ok(())
+        self.items.clear();
+        self.refresh_data()
     }
 
     pub fn move_selection_up(&mut self) {
@@ -164,13 +197,10 @@ impl App {
                 return;
             }
             // Check if selected item is actually deletable/clearable
-            if let Some(item) = self.selected_item() {
-                if item.deletable_action().is_none() {
-                    return; // Don't enter delete mode for non-deletable resources
-                }
-            } else {
-                return;
-            }
+            let Some(action) = self.selected_item().and_then(|i| i.deletable_action()) else {
+                return; // Don't enter delete mode for non-deletable resources
+            };
+            self.pending_action = Some(action);
             self.delete_button_selected = 0;
         }
         self.view_mode = mode;
@@ -307,7 +337,7 @@ impl App {
 
         let item = &self.items[self.selected_index];
 
-        match item.deletable_action() {
+        match self.pending_action.take() {
             Some(ResourceAction::Delete) => match delete_resource(&self.client, item) {
                 Ok(_) => {
                     self.view_mode = ViewMode::List;
@@ -328,6 +358,18 @@ impl App {
                     self.view_mode = ViewMode::List;
                 }
             },
+            Some(ResourceAction::Enable | ResourceAction::Disable) => {
+                match toggle_admin_status(&self.client, item) {
+                    Ok(_) => {
+                        self.view_mode = ViewMode::List;
+                        self.refresh_data()?;
+                    }
+                    Err(e) => {
+                        self.error_message = Some(format!("Failed to enable/disable: {}", e));
+                        self.view_mode = ViewMode::List;
+                    }
+                }
+            }
             None => {
                 self.error_message = Some("This resource cannot be deleted or cleared".to_string());
                 self.view_mode = ViewMode::List;
@@ -337,6 +379,17 @@ impl App {
         Ok(())
     }
 
+    /// Asks for confirmation before enabling or disabling the selected input
+    /// or output, as that takes it on or off the air
+    pub fn toggle_selected_admin_status(&mut self) {
+        let Some(action) = self.selected_item().and_then(|i| i.admin_status_action()) else {
+            return;
+        };
+        self.pending_action = Some(action);
+        self.delete_button_selected = 0;
+        self.view_mode = ViewMode::ConfirmDelete;
+    }
+
     pub fn scroll_up(&mut self) {
         if self.scroll_offset > 0 {
             self.scroll_offset -= 1;
@@ -371,7 +424,7 @@ impl App {
     }
 
     pub fn get_help_content_lines(&self) -> usize {
-        51
+        52
     }
 
     pub fn get_about_content_lines(&self) -> usize {
@@ -68,12 +68,14 @@ fn run_app<B: ratatui::backend::Backend>(terminal: &mut Terminal<B>, app: &mut A
             break;
         }
 
+        // Pick up the items once a refresh running in the background is done
+        if app.poll_refresh() && app.view_mode == ViewMode::Describe {
+            app.fetch_thumbnail_for_current_item();
+        }
+
         // Check if we should refresh (only if auto-refresh is enabled)
-        if app.auto_refresh_enabled && app.should_refresh() {
+        if app.auto_refresh_enabled && app.should_refresh() && !app.loading {
             let _ = app.refresh_data();
-            if app.view_mode == ViewMode::Describe {
-                app.fetch_thumbnail_for_current_item();
-            }
         }
     }
 
@@ -115,6 +117,9 @@ fn handle_list_mode_input(app: &mut App, key: event::KeyEvent) -> Result<()> {
         KeyCode::Char('r') => {
             app.toggle_auto_refresh();
         }
+        KeyCode::Char('e') => {
+            app.toggle_selected_admin_status();
+        }
         _ => {}
     }
     Ok(())
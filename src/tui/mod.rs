@@ -3,7 +3,7 @@ pub mod events;
 pub mod resources;
 pub mod ui;
 
-use crate::edge::new_client;
+use crate::client::new_client;
 use anyhow::Result;
 pub use app::App;
 pub use events::run_event_loop;
@@ -3,6 +3,7 @@ use crate::edge::*;
 use anyhow::Result;
 use ratatui::style::Color;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 
 /// Strip common unicode symbols (checkmarks, crosses, emojis) from strings
@@ -126,6 +127,8 @@ impl fmt::Display for ResourceType {
 pub enum ResourceAction {
     Delete,
     Clear,
+    Enable,
+    Disable,
 }
 
 /// A resource item that can be displayed in the TUI
@@ -508,6 +511,21 @@ impl ResourceItem {
             Self::Settings(_) => None,
         }
     }
+
+    /// Enable for disabled inputs and outputs, disable for enabled ones
+    pub fn admin_status_action(&self) -> Option<ResourceAction> {
+        match self {
+            Self::Input(i) => match i.admin_status {
+                InputAdminStatus::On => Some(ResourceAction::Disable),
+                InputAdminStatus::Off => Some(ResourceAction::Enable),
+            },
+            Self::Output(o) => match o.admin_status {
+                OutputAdminStatus::On => Some(ResourceAction::Disable),
+                OutputAdminStatus::Off => Some(ResourceAction::Enable),
+            },
+            _ => None,
+        }
+    }
 }
 
 /// Fetch all items for a given resource type
@@ -582,8 +600,6 @@ pub fn fetch_resources(
             Ok(vec![ResourceItem::Settings(settings)])
         }
         ResourceType::Alarm => {
-            use std::collections::{HashMap, HashSet};
-
             let alarms = client.list_alarms()?;
 
             // Collect all unique input and output IDs
@@ -605,28 +621,8 @@ pub fn fetch_resources(
                 }
             }
 
-            // Fetch inputs and outputs to build lookup maps
-            let input_map: HashMap<String, String> = if !input_ids.is_empty() {
-                client
-                    .list_inputs()?
-                    .into_iter()
-                    .filter(|i| input_ids.contains(&i.id))
-                    .map(|i| (i.id, i.name))
-                    .collect()
-            } else {
-                HashMap::new()
-            };
-
-            let output_map: HashMap<String, String> = if !output_ids.is_empty() {
-                client
-                    .list_outputs()?
-                    .into_iter()
-                    .filter(|o| output_ids.contains(&o.id))
-                    .map(|o| (o.id, o.name))
-                    .collect()
-            } else {
-                HashMap::new()
-            };
+            // Look up the names of the inputs and outputs
+            let (input_map, output_map) = lookup_names(client, input_ids, output_ids)?;
 
             // Build enriched alarms
             let items: Vec<ResourceItem> = alarms
@@ -673,8 +669,6 @@ pub fn fetch_resources(
             Ok(items)
         }
         ResourceType::AlarmHistory => {
-            use std::collections::{HashMap, HashSet};
-
             // Fetch last 100 historical alarms
             let alarms = client.list_alarm_history(100, None, None)?;
 
@@ -691,28 +685,8 @@ pub fn fetch_resources(
                 }
             }
 
-            // Fetch inputs and outputs to build lookup maps
-            let input_map: HashMap<String, String> = if !input_ids.is_empty() {
-                client
-                    .list_inputs()?
-                    .into_iter()
-                    .filter(|i| input_ids.contains(&i.id))
-                    .map(|i| (i.id, i.name))
-                    .collect()
-            } else {
-                HashMap::new()
-            };
-
-            let output_map: HashMap<String, String> = if !output_ids.is_empty() {
-                client
-                    .list_outputs()?
-                    .into_iter()
-                    .filter(|o| output_ids.contains(&o.id))
-                    .map(|o| (o.id, o.name))
-                    .collect()
-            } else {
-                HashMap::new()
-            };
+            // Look up the names of the inputs and outputs
+            let (input_map, output_map) = lookup_names(client, input_ids, output_ids)?;
 
             // Build enriched alarm history
             let items: Vec<ResourceItem> = alarms
@@ -803,6 +777,29 @@ pub fn clear_resource(client: &EdgeClient, item: &ResourceItem) -> Result<()> {
     }
 }
 
+/// Enable a disabled input or output, or disable an enabled one
+pub fn toggle_admin_status(client: &EdgeClient, item: &ResourceItem) -> Result<()> {
+    match item {
+        ResourceItem::Input(i) => {
+            let admin_status = match i.admin_status {
+                InputAdminStatus::On => InputAdminStatus::Off,
+                InputAdminStatus::Off => InputAdminStatus::On,
+            };
+            Ok(client.set_input_admin_status(&i.id, admin_status)?)
+        }
+        ResourceItem::Output(o) => {
+            let admin_status = match o.admin_status {
+                OutputAdminStatus::On => OutputAdminStatus::Off,
+                OutputAdminStatus::Off => OutputAdminStatus::On,
+            };
+            Ok(client.set_output_admin_status(&o.id, admin_status)?)
+        }
+        _ => Err(anyhow::anyhow!(
+            "Resource type does not support enabling and disabling"
+        )),
+    }
+}
+
 /// Format time ago (e.g., "2h ago", "15m ago")
 fn format_time_ago(time_str: &str) -> String {
     use chrono::{DateTime, Utc};
@@ -846,6 +843,28 @@ fn format_entities(input_names: &[String], output_names: &[String]) -> String {
     }
 }
 
+/// Looks up the names of the inputs and outputs with the given ids
+/// concurrently
+fn lookup_names(
+    client: &EdgeClient,
+    input_ids: HashSet<String>,
+    output_ids: HashSet<String>,
+) -> Result<(HashMap<String, String>, HashMap<String, String>)> {
+    let (inputs, outputs) = std::thread::scope(|s| {
+        let inputs = s.spawn(|| client.list_inputs_by_ids(input_ids.into_iter().collect()));
+        let outputs = s.spawn(|| client.list_outputs_by_ids(output_ids.into_iter().collect()));
+        (
+            inputs.join().expect("Input request thread panicked"),
+            outputs.join().expect("Output request thread panicked"),
+        )
+    });
+
+    Ok((
+        inputs?.into_iter().map(|i| (i.id, i.name)).collect(),
+        outputs?.into_iter().map(|o| (o.id, o.name)).collect(),
+    ))
+}
+
 /// Truncate message to specified length
 fn truncate_message(msg: &str, max_len: usize) -> String {
     if msg.len() > max_len {
@@ -854,28 +873,3 @@ fn truncate_message(msg: &str, max_len: usize) -> String {
         msg.to_string()
     }
 }
-
-impl Input {
-    pub fn get_all_channels(&self) -> Vec<ChannelInfo> {
-        let Some(metrics) = self.metrics.as_ref() else {
-            return Vec::new();
-        };
-        let Some(rist_metrics) = metrics.rist_metrics.as_ref() else {
-            return Vec::new();
-        };
-
-        rist_metrics
-            .iter()
-            .filter_map(|metric| {
-                if metric.metric_type == "channel" {
-                    metric.channel_id.map(|id| ChannelInfo {
-                        channel_id: id,
-                        active: metric.state.as_deref() == Some("activated"),
-                    })
-                } else {
-                    None
-                }
-            })
-            .collect()
-    }
-}
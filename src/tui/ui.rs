@@ -77,7 +77,7 @@ fn draw_top_bar(f: &mut Frame, app: &mut App, area: Rect) {
                 match item.deletable_action() {
                     Some(ResourceAction::Delete) => "<ctrl-d> delete    ",
                     Some(ResourceAction::Clear) => "<ctrl-d> clear     ",
-                    None => "",
+                    _ => "",
                 }
             } else {
                 ""
@@ -147,11 +147,7 @@ fn draw_list_view(f: &mut Frame, app: &mut App, area: Rect) {
     let num_columns = columns.len();
     let column_spacing = num_columns.saturating_sub(1); // spaces between columns
     let usable_width = available_width.saturating_sub(column_spacing);
-    let col_width = if num_columns > 0 {
-        usable_width / num_columns
-    } else {
-        0
-    };
+    let col_width = usable_width.checked_div(num_columns).unwrap_or(0);
 
     // Calculate visible rows and apply scroll offset
     let visible_rows = (area.height.saturating_sub(3)) as usize; // subtract borders and header
@@ -439,9 +435,11 @@ fn draw_delete_confirm(f: &mut Frame, app: &mut App, area: Rect) {
     use crate::tui::resources::ResourceAction;
 
     if let Some(item) = app.selected_item() {
-        let (action_verb, title) = match item.deletable_action() {
+        let (action_verb, title) = match app.pending_action {
             Some(ResourceAction::Delete) => ("delete", " Delete "),
             Some(ResourceAction::Clear) => ("clear", " Clear "),
+            Some(ResourceAction::Enable) => ("enable", " Enable "),
+            Some(ResourceAction::Disable) => ("disable", " Disable "),
             None => ("", " Error "),
         };
 
@@ -664,6 +662,7 @@ fn draw_help_view(f: &mut Frame, app: &mut App, area: Rect) {
         Line::from("  Enter         Describe selected item (same as 'd')"),
         Line::from("  Ctrl-D        Delete/Clear selected item (if supported, with confirmation)"),
         Line::from("  :             Enter navigate mode"),
+        Line::from("  e             Enable/disable selected input or output (with confirmation)"),
         Line::from("  r             Toggle auto-refresh (enabled by default)"),
         Line::from("  ?             Show this help"),
         Line::from(""),
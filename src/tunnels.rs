@@ -1,6 +1,9 @@
 use tabled::{builder::Builder, settings::Style};
 
-use crate::edge::{new_client, EdgeClient};
+use crate::client::new_client;
+use crate::edge::EdgeClient;
+use crate::format::{self, Format};
+use crate::OrExit;
 
 use clap::{ArgMatches, Command};
 
@@ -8,18 +11,27 @@ pub(crate) fn subcommand() -> clap::Command {
     Command::new("tunnel")
         .about("Show information about tunnels")
         .subcommand_required(true)
-        .subcommand(Command::new("list").about("List tunnels"))
+        .subcommand(
+            Command::new("list")
+                .about("List tunnels")
+                .arg(format::arg(&["short"])),
+        )
 }
 
 pub(crate) fn run(subcmd: &ArgMatches) {
     match subcmd.subcommand() {
-        Some(("list", _)) => list(new_client()),
+        Some(("list", args)) => list(new_client(), format::structured(args)),
         _ => unreachable!("subcommand_required prevents `None` or other options"),
     }
 }
 
-fn list(client: EdgeClient) {
-    let tunnels = client.list_tunnels().expect("Failed to fetch tunnel list");
+fn list(client: EdgeClient, format: Option<Format>) {
+    let tunnels = client.list_tunnels().or_exit("Failed to fetch tunnel list");
+
+    if let Some(format) = format {
+        format::print(format, &tunnels);
+        return;
+    }
 
     let mut builder = Builder::default();
 
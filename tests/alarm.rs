@@ -0,0 +1,28 @@
+mod common;
+
+use common::{alarm, input, stdout, MockServer};
+
+#[test]
+fn it_lists_alarms_with_the_names_of_their_inputs() {
+    let server = MockServer::start();
+    server
+        .list(
+            "/api/alarm/",
+            vec![alarm("alarm-1", "Input bitrate low", Some("in-1"))],
+        )
+        .list(
+            "/api/input/",
+            vec![input("in-1", "studio"), input("in-2", "stadium")],
+        );
+
+    let output = server.edgectl(&["alarm", "list"]);
+
+    assert!(output.status.success());
+    let stdout = stdout(&output);
+    let line = stdout
+        .lines()
+        .find(|l| l.contains("Input bitrate low"))
+        .unwrap();
+    assert!(line.contains("input: studio"));
+    assert!(line.contains("edge-1"));
+}
@@ -0,0 +1,20 @@
+mod common;
+
+use common::{appliance, stdout, MockServer};
+
+#[test]
+fn it_lists_appliances() {
+    let server = MockServer::start();
+    server.list(
+        "/api/appliance/",
+        vec![appliance("a-1", "edge-1"), appliance("a-2", "edge-2")],
+    );
+
+    let output = server.edgectl(&["appliance", "list"]);
+
+    assert!(output.status.success());
+    let stdout = stdout(&output);
+    let line = stdout.lines().find(|l| l.contains("edge-2")).unwrap();
+    assert!(line.contains("a-2"));
+    assert!(line.contains("connected"));
+}
@@ -0,0 +1,374 @@
+//! An in-process mock of the Edge API, and helpers to run `edgectl` against
+//! it
+
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::process::{self, Command, Output};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use serde_json::{json, Value};
+
+// Gives every server its own config directory
+static SERVERS: AtomicUsize = AtomicUsize::new(0);
+
+type Handler = Box<dyn Fn(&Request) -> Response + Send + Sync>;
+
+/// A request received by the mock server
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    /// The decoded `q` parameter of list requests
+    pub query: Option<Value>,
+    pub headers: HashMap<String, String>,
+    pub body: Option<Value>,
+}
+
+pub struct Response {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl Response {
+    pub fn ok(body: Value) -> Self {
+        Self { status: 200, body }
+    }
+
+    /// An error in the format of the Edge API
+    pub fn error(status: u16, title: &str, detail: &str) -> Self {
+        Self {
+            status,
+            body: json!({
+                "title": title,
+                "detail": detail,
+                "type": "https://edge.example.com/errors",
+            }),
+        }
+    }
+}
+
+/// An HTTP server on localhost that answers with the registered handlers and
+/// records every request. Unknown paths get a 404 in the Edge API format.
+pub struct MockServer {
+    pub url: String,
+    routes: Arc<Mutex<Vec<(String, String, Handler)>>>,
+    requests: Arc<Mutex<Vec<Request>>>,
+    config_dir: PathBuf,
+}
+
+impl MockServer {
+    pub fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind mock server");
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let routes: Arc<Mutex<Vec<(String, String, Handler)>>> = Default::default();
+        let requests: Arc<Mutex<Vec<Request>>> = Default::default();
+
+        let (r, q) = (routes.clone(), requests.clone());
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let (routes, requests) = (r.clone(), q.clone());
+                thread::spawn(move || handle(stream, &routes, &requests));
+            }
+        });
+
+        let config_dir = std::env::temp_dir().join(format!(
+            "edgectl-test-{}-{}",
+            process::id(),
+            SERVERS.fetch_add(1, Ordering::Relaxed)
+        ));
+        Self {
+            url,
+            routes,
+            requests,
+            config_dir,
+        }
+    }
+
+    /// Answers requests for `method` and `path` with `handler`. Paths ending
+    /// in `*` match every path with that prefix.
+    pub fn on(
+        &self,
+        method: &str,
+        path: &str,
+        handler: impl Fn(&Request) -> Response + Send + Sync + 'static,
+    ) -> &Self {
+        self.routes
+            .lock()
+            .unwrap()
+            .push((method.to_owned(), path.to_owned(), Box::new(handler)));
+        self
+    }
+
+    /// Serves `items` from a list endpoint, honouring the filter on ids and
+    /// the skip and limit of the query like the Edge API does
+    pub fn list(&self, path: &str, items: Vec<Value>) -> &Self {
+        self.on("GET", path, move |req| paginate(&items, req.query.as_ref()))
+    }
+
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// The requests to `path`, ignoring the query
+    pub fn requests_to(&self, method: &str, path: &str) -> Vec<Request> {
+        self.requests()
+            .into_iter()
+            .filter(|r| r.method == method && r.path == path)
+            .collect()
+    }
+
+    /// The edgectl config file in the config directory of the server
+    pub fn config_file(&self) -> PathBuf {
+        self.config_dir.join("edgectl").join("config")
+    }
+
+    /// Writes a config with a single context `name` for the Edge API at
+    /// `url`, selected and with `token`
+    pub fn write_config(&self, name: &str, url: &str, token: &str) {
+        let path = self.config_file();
+        std::fs::create_dir_all(path.parent().unwrap()).expect("Failed to create config dir");
+        let config = format!(
+            "context = \"{name}\"\n\n[contexts.{name}]\nurl = \"{url}\"\ntoken = \"{token}\"\ntoken_name = \"{name}-token\"\nusername = \"admin\"\n"
+        );
+        std::fs::write(path, config).expect("Failed to write config");
+    }
+
+    /// Runs `edgectl` with `args` against the server, with a token from the
+    /// environment and an empty config directory
+    pub fn edgectl(&self, args: &[&str]) -> Output {
+        self.command(args)
+            .env("EDGE_TOKEN", "test-token")
+            .output()
+            .expect("Failed to run edgectl")
+    }
+
+    /// A command for `edgectl` that only sees the server URL, without
+    /// credentials
+    pub fn command(&self, args: &[&str]) -> Command {
+        let mut cmd = Command::new(env!("CARGO_BIN_EXE_edgectl"));
+        cmd.args(args)
+            .env_clear()
+            .env("PATH", std::env::var("PATH").unwrap_or_default())
+            .env("HOME", &self.config_dir)
+            .env("XDG_CONFIG_HOME", &self.config_dir)
+            .env("EDGE_URL", &self.url);
+        cmd
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.config_dir);
+    }
+}
+
+fn handle(
+    stream: TcpStream,
+    routes: &Mutex<Vec<(String, String, Handler)>>,
+    requests: &Mutex<Vec<Request>>,
+) {
+    let Some(request) = read_request(&stream) else {
+        return;
+    };
+    requests.lock().unwrap().push(request.clone());
+
+    let response = routes
+        .lock()
+        .unwrap()
+        .iter()
+        .rev()
+        .find(|(method, path, _)| {
+            *method == request.method
+                && match path.strip_suffix('*') {
+                    Some(prefix) => request.path.starts_with(prefix),
+                    None => *path == request.path,
+                }
+        })
+        .map(|(_, _, handler)| handler(&request))
+        .unwrap_or_else(|| Response::error(404, "Not Found", "No such route in the mock"));
+
+    let body = response.body.to_string();
+    let _ = write!(
+        &stream,
+        "HTTP/1.1 {} Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        body.len(),
+        body
+    );
+}
+
+fn read_request(stream: &TcpStream) -> Option<Request> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line).ok()?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next()?.to_owned();
+    let target = parts.next()?.to_owned();
+
+    let mut headers = HashMap::new();
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).ok()?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.insert(name.trim().to_lowercase(), value.trim().to_owned());
+        }
+    }
+
+    let length = headers
+        .get("content-length")
+        .and_then(|l| l.parse::<usize>().ok())
+        .unwrap_or(0);
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).ok()?;
+
+    let (path, query) = match target.split_once('?') {
+        Some((path, params)) => (path.to_owned(), parse_query(params)),
+        None => (target, None),
+    };
+
+    Some(Request {
+        method,
+        path,
+        query,
+        headers,
+        body: serde_json::from_slice(&body).ok(),
+    })
+}
+
+fn parse_query(params: &str) -> Option<Value> {
+    let q = params.split('&').find_map(|p| p.strip_prefix("q="))?;
+    let q = urlencoding::decode(q).expect("The query should be URL encoded");
+    Some(serde_json::from_str(&q).expect("The query should be JSON"))
+}
+
+fn paginate(items: &[Value], query: Option<&Value>) -> Response {
+    let filter = query.and_then(|q| q.get("filter"));
+    let items = items
+        .iter()
+        .filter(|item| match filter.and_then(|f| f.get("ids")) {
+            Some(Value::Array(ids)) => ids.contains(&item["id"]),
+            _ => true,
+        })
+        .filter(|item| match filter.and_then(|f| f.get("inputIds")) {
+            Some(Value::Array(ids)) => ids.contains(&item["inputId"]),
+            _ => true,
+        })
+        .filter(|item| match filter.and_then(|f| f.get("searchName")) {
+            Some(Value::String(name)) => item["name"].as_str().unwrap_or("").contains(name),
+            _ => true,
+        })
+        .collect::<Vec<_>>();
+    let total = items.len();
+    let skip = query.and_then(|q| q["skip"].as_u64()).unwrap_or(0) as usize;
+    let limit = query.and_then(|q| q["limit"].as_u64()).unwrap_or(10) as usize;
+
+    Response::ok(json!({
+        "items": items.into_iter().skip(skip).take(limit).collect::<Vec<_>>(),
+        "total": total,
+    }))
+}
+
+pub fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+pub fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+/// An input as returned by the Edge API
+pub fn input(id: &str, name: &str) -> Value {
+    json!({
+        "id": id,
+        "name": name,
+        "adminStatus": 1,
+        "owner": "group-1",
+        "bufferSize": 6000,
+        "createdAt": "2024-01-01T00:00:00.000Z",
+        "updatedAt": "2024-01-01T00:00:00.000Z",
+        "previewSettings": { "mode": "on demand" },
+        "thumbnailMode": 2,
+        "tr101290Enabled": true,
+        "appliances": [{ "name": "edge-1" }],
+        "health": { "state": "allOk", "title": "" },
+        "ports": [],
+    })
+}
+
+/// An output as returned by the Edge API
+pub fn output(id: &str, name: &str, input: Option<&str>) -> Value {
+    json!({
+        "id": id,
+        "name": name,
+        "adminStatus": 1,
+        "group": "group-1",
+        "input": input,
+        "health": { "state": "allOk", "title": "" },
+        "redundancyMode": 0,
+        "delay": 1000,
+        "delayMode": 1,
+        "createdAt": "2024-01-01T00:00:00.000Z",
+        "updatedAt": "2024-01-01T00:00:00.000Z",
+        "appliances": [{ "name": "edge-1" }],
+        "ports": [],
+    })
+}
+
+/// An appliance as returned by the Edge API
+pub fn appliance(id: &str, name: &str) -> Value {
+    json!({
+        "id": id,
+        "name": name,
+        "hostname": name,
+        "contact": "",
+        "serial": "1234",
+        "version": { "controlSoftwareVersion": "R3.20.0" },
+        "lastRegisteredAt": "2024-01-01T00:00:00.000Z",
+        "health": { "state": "connected", "title": "Connected" },
+        "physicalPorts": [],
+        "type": "core",
+        "owner": "group-1",
+        "alarms": [],
+    })
+}
+
+/// A physical port of an appliance, to put in its `physicalPorts`
+pub fn physical_port(id: &str, name: &str, address: &str) -> Value {
+    json!({
+        "id": id,
+        "name": name,
+        "portType": "ip",
+        "addresses": [{ "address": address }],
+        "networks": [],
+    })
+}
+
+/// An active alarm as returned by the Edge API
+pub fn alarm(id: &str, cause: &str, input_id: Option<&str>) -> Value {
+    json!({
+        "alarmId": id,
+        "alarmCause": cause,
+        "alarmSeverity": "major",
+        "type": "input",
+        "objectName": "edge-1",
+        "repeatCount": 1,
+        "inputId": input_id,
+        "applianceName": "edge-1",
+        "raisedAt": "2024-01-01T00:00:00.000Z",
+        "text": "Input is not receiving data",
+    })
+}
+
+pub fn group(id: &str, name: &str) -> Value {
+    json!({ "id": id, "name": name })
+}
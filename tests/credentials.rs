@@ -0,0 +1,70 @@
+mod common;
+
+use std::os::unix::fs::PermissionsExt;
+
+use common::{input, stderr, MockServer};
+
+// Writes an executable credential helper printing `output` for every call,
+// and a config whose selected context keeps its token in that helper
+fn use_helper(server: &MockServer, output: &str) {
+    let config = server.config_file();
+    let dir = config.parent().unwrap();
+    std::fs::create_dir_all(dir).unwrap();
+    let helper = dir.join("helper.sh");
+    std::fs::write(
+        &helper,
+        format!("#!/bin/sh\ncat >/dev/null\nprintf '{}'\n", output),
+    )
+    .unwrap();
+    std::fs::set_permissions(&helper, std::fs::Permissions::from_mode(0o755)).unwrap();
+    std::fs::write(
+        config,
+        format!(
+            "context = \"lab\"\n\n[contexts.lab]\nurl = \"{}\"\ntoken_name = \"lab-token\"\nusername = \"admin\"\ncredential_store = {{ type = \"helper\", command = \"{}\" }}\n",
+            server.url,
+            helper.display()
+        ),
+    )
+    .unwrap();
+}
+
+#[test]
+fn it_takes_the_token_line_of_git_style_helpers() {
+    let server = MockServer::start();
+    server.list("/api/input/", vec![input("in-1", "studio")]);
+    use_helper(
+        &server,
+        "context=lab\\nurl=https://edge.example.com\\ntoken=secret\\n",
+    );
+
+    let result = server.command(&["input", "list"]).output().unwrap();
+
+    assert!(result.status.success(), "{}", stderr(&result));
+    let requests = server.requests_to("GET", "/api/input/");
+    assert_eq!(requests[0].headers["edge-api-token"], "secret");
+}
+
+#[test]
+fn it_takes_the_only_line_of_helpers_that_print_the_token() {
+    let server = MockServer::start();
+    server.list("/api/input/", vec![input("in-1", "studio")]);
+    use_helper(&server, "secret\\n");
+
+    let result = server.command(&["input", "list"]).output().unwrap();
+
+    assert!(result.status.success(), "{}", stderr(&result));
+    let requests = server.requests_to("GET", "/api/input/");
+    assert_eq!(requests[0].headers["edge-api-token"], "secret");
+}
+
+#[test]
+fn it_fails_when_a_git_style_helper_has_no_token() {
+    let server = MockServer::start();
+    use_helper(&server, "context=lab\\nurl=https://edge.example.com\\n");
+
+    let result = server.command(&["input", "list"]).output().unwrap();
+
+    assert_eq!(result.status.code(), Some(1));
+    assert!(stderr(&result).contains("The credential helper returned no token for 'lab'"));
+    assert!(server.requests_to("GET", "/api/input/").is_empty());
+}
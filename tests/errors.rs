@@ -0,0 +1,76 @@
+mod common;
+
+use common::{input, stderr, MockServer, Response};
+use serde_json::json;
+
+#[test]
+fn it_exits_with_3_when_unauthorized() {
+    let server = MockServer::start();
+    server.on("GET", "/api/input/", |_| {
+        Response::error(401, "Unauthorized", "The token has expired")
+    });
+
+    let output = server.edgectl(&["input", "list"]);
+
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = stderr(&output);
+    assert!(stderr.contains("Not authorized (401 Unauthorized)"));
+    assert!(stderr.contains("The token has expired"));
+    assert!(stderr.contains("Run 'edgectl login' if the token has expired"));
+}
+
+#[test]
+fn it_exits_with_6_on_server_errors() {
+    let server = MockServer::start();
+    server.on("GET", "/api/output/", |_| {
+        Response::error(500, "Internal Server Error", "Database unavailable")
+    });
+
+    let output = server.edgectl(&["--retries", "1", "output", "list"]);
+
+    assert_eq!(output.status.code(), Some(6));
+    assert!(stderr(&output).contains("Database unavailable"));
+    // The failed request is retried once
+    assert_eq!(server.requests_to("GET", "/api/output/").len(), 2);
+}
+
+#[test]
+fn it_prints_invalid_parameters() {
+    let server = MockServer::start();
+    server.on("GET", "/api/input/", |_| Response {
+        status: 400,
+        body: json!({
+            "title": "Bad Request",
+            "detail": [{ "name": "limit", "reason": "must be at most 100" }],
+            "type": "validation",
+        }),
+    });
+
+    let output = server.edgectl(&["input", "list"]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("invalid parameters: limit: must be at most 100"));
+}
+
+#[test]
+fn it_logs_in_with_a_password() {
+    let server = MockServer::start();
+    server
+        .on("POST", "/api/login/", |_| {
+            Response::ok(json!({ "user": { "username": "admin", "role": "super" } }))
+        })
+        .list("/api/input/", vec![input("in-1", "studio")]);
+
+    let output = server
+        .command(&["input", "list"])
+        .env("EDGE_PASSWORD", "secret")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let login = server.requests_to("POST", "/api/login/");
+    assert_eq!(
+        login[0].body,
+        Some(json!({ "username": "admin", "password": "secret" }))
+    );
+}
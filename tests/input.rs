@@ -0,0 +1,439 @@
+mod common;
+
+use common::{
+    alarm, appliance, group, input, output, physical_port, stderr, stdout, MockServer, Response,
+};
+use serde_json::json;
+use serde_json::Value;
+
+#[test]
+fn it_lists_inputs_from_every_page() {
+    let server = MockServer::start();
+    server.list(
+        "/api/input/",
+        (0..250)
+            .map(|i| input(&format!("id-{}", i), &format!("input-{}", i)))
+            .collect(),
+    );
+
+    let output = server.edgectl(&["input", "list"]);
+
+    assert!(output.status.success());
+    let stdout = stdout(&output);
+    assert_eq!(stdout.lines().count(), 251);
+    assert!(stdout.contains("input-0 "));
+    assert!(stdout.contains("input-249 "));
+    let mut skips = server
+        .requests_to("GET", "/api/input/")
+        .iter()
+        .map(|r| r.query.as_ref().unwrap()["skip"].as_u64().unwrap())
+        .collect::<Vec<_>>();
+    skips.sort();
+    assert_eq!(skips, [0, 100, 200]);
+}
+
+#[test]
+fn it_prints_inputs_as_json() {
+    let server = MockServer::start();
+    server.list(
+        "/api/input/",
+        vec![input("id-1", "studio"), input("id-2", "stadium")],
+    );
+
+    let output = server.edgectl(&["input", "list", "-o", "json"]);
+
+    assert!(output.status.success());
+    let inputs: Value = serde_json::from_str(&stdout(&output)).unwrap();
+    let names = inputs
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|i| i["name"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["studio", "stadium"]);
+    assert_eq!(inputs[0]["adminStatus"], 1);
+}
+
+#[test]
+fn it_shows_an_input() {
+    let server = MockServer::start();
+    server
+        .list(
+            "/api/input/",
+            vec![input("id-1", "studio"), input("id-2", "stadium")],
+        )
+        .on("GET", "/api/group/group-1", |_| {
+            common::Response::ok(group("group-1", "broadcaster"))
+        });
+
+    let output = server.edgectl(&["input", "show", "studio"]);
+
+    assert!(output.status.success());
+    let stdout = stdout(&output);
+    assert!(stdout.contains("Name:           studio"));
+    assert!(stdout.contains("Owner:          broadcaster"));
+    assert!(!stdout.contains("stadium"));
+}
+
+#[test]
+fn it_filters_inputs_on_the_server() {
+    let server = MockServer::start();
+    server
+        .list("/api/input/", vec![input("id-1", "studio")])
+        .list(
+            "/api/group/",
+            vec![
+                group("group-1", "broadcaster"),
+                group("group-2", "broadcaster-2"),
+            ],
+        );
+
+    let output = server.edgectl(&[
+        "input",
+        "list",
+        "--admin-status",
+        "off",
+        "--group",
+        "broadcaster",
+    ]);
+
+    assert!(output.status.success());
+    let requests = server.requests_to("GET", "/api/input/");
+    let filter = &requests[0].query.as_ref().unwrap()["filter"];
+    assert_eq!(filter["adminStatus"], 0);
+    assert_eq!(filter["groupIds"], serde_json::json!(["group-1"]));
+}
+
+#[test]
+fn it_creates_encrypted_srt_caller_inputs() {
+    let server = MockServer::start();
+    let mut edge = appliance("a-1", "edge-1");
+    edge["physicalPorts"] = json!([physical_port("port-1", "eth0", "192.0.2.10")]);
+    server
+        .list("/api/appliance/", vec![edge])
+        .on("POST", "/api/input/", |_| Response::ok(json!({})));
+
+    let output = server.edgectl(&[
+        "input",
+        "create",
+        "remote-studio",
+        "--appliance",
+        "edge-1",
+        "--interface",
+        "eth0",
+        "--mode",
+        "srt",
+        "--caller",
+        "--dest",
+        "198.51.100.40:9000",
+        "--stream-id",
+        "studio-1",
+        "--passphrase",
+        "correct horse battery",
+        "--latency",
+        "250",
+    ]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    let requests = server.requests_to("POST", "/api/input/");
+    assert_eq!(
+        requests[0].body.as_ref().unwrap()["ports"],
+        json!([{
+            "mode": "srt",
+            "srtMode": "caller",
+            "physicalPort": "port-1",
+            "remoteIp": "198.51.100.40",
+            "remotePort": 9000,
+            "latency": 250,
+            "pbkeylen": "16",
+            "passphrase": "correct horse battery",
+            "streamId": "studio-1",
+            "reducedBitrateDetection": false,
+            "unrecoveredPacketsDetection": false,
+        }])
+    );
+}
+
+#[test]
+fn it_creates_redundant_inputs_from_port_specs() {
+    let server = MockServer::start();
+    let mut edge_1 = appliance("a-1", "edge-1");
+    edge_1["physicalPorts"] = json!([physical_port("port-1", "eth0", "192.0.2.10")]);
+    let mut edge_2 = appliance("a-2", "edge-2");
+    edge_2["physicalPorts"] = json!([physical_port("port-2", "eth1", "192.0.2.20")]);
+    server
+        .list("/api/appliance/", vec![edge_1, edge_2])
+        .on("POST", "/api/input/", |_| Response::ok(json!({})));
+
+    let output = server.edgectl(&[
+        "input",
+        "create",
+        "studio",
+        "--mode",
+        "rtp",
+        "--port-spec",
+        "edge-1:eth0:4000",
+        "--port-spec",
+        "edge-2:eth1:239.0.0.2:4000",
+    ]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    let requests = server.requests_to("POST", "/api/input/");
+    let ports = requests[0].body.as_ref().unwrap()["ports"].clone();
+    assert_eq!(ports.as_array().unwrap().len(), 2);
+    assert_eq!(ports[0]["physicalPort"], "port-1");
+    assert_eq!(ports[0]["address"], "192.0.2.10");
+    assert_eq!(ports[1]["physicalPort"], "port-2");
+    assert_eq!(ports[1]["multicastAddress"], "239.0.0.2");
+}
+
+#[test]
+fn it_shows_the_state_of_every_leg() {
+    let server = MockServer::start();
+    let mut studio = input("id-1", "studio");
+    studio["ports"] = json!([
+        { "mode": "rtp", "physicalPort": "port-1", "copies": 1, "port": 4000 },
+        { "mode": "rtp", "physicalPort": "port-2", "copies": 1, "port": 4000 },
+    ]);
+    let mut alarm = alarm("alarm-1", "Input is not receiving data", Some("id-1"));
+    alarm["physicalPortId"] = json!("port-2");
+    server
+        .list("/api/input/", vec![studio])
+        .list("/api/alarm/", vec![alarm])
+        .list(
+            "/api/port/",
+            vec![
+                json!({ "id": "port-1", "name": "eth0", "appliance": { "name": "edge-1" } }),
+                json!({ "id": "port-2", "name": "eth0", "appliance": { "name": "edge-2" } }),
+            ],
+        );
+
+    let output = server.edgectl(&["input", "show", "studio"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    let stdout = stdout(&output);
+    let states = stdout
+        .lines()
+        .filter(|l| l.contains("State:"))
+        .collect::<Vec<_>>();
+    assert_eq!(states.len(), 2);
+    assert!(states[0].contains('✓'));
+    assert!(states[1].contains("✗ Input is not receiving data"));
+    assert!(stdout.contains("edge-2"));
+    // Only the alarms of the input are listed, and the ports in one request
+    let requests = server.requests_to("GET", "/api/alarm/");
+    assert_eq!(
+        requests[0].query.as_ref().unwrap()["filter"]["inputIds"],
+        json!(["id-1"])
+    );
+    assert_eq!(server.requests_to("GET", "/api/port/").len(), 1);
+}
+
+#[test]
+fn it_reports_alarms_it_cannot_list_when_showing_legs() {
+    let server = MockServer::start();
+    let mut studio = input("id-1", "studio");
+    studio["ports"] = json!([
+        { "mode": "rtp", "physicalPort": "port-1", "copies": 1, "port": 4000 },
+    ]);
+    server
+        .list("/api/input/", vec![studio])
+        .on("GET", "/api/alarm/", |_| {
+            Response::error(500, "Internal error", "Alarms are unavailable")
+        });
+
+    let output = server.edgectl(&["input", "show", "studio"]);
+
+    assert_eq!(output.status.code(), Some(6));
+    assert!(stderr(&output).contains("Failed to list alarms"));
+}
+
+#[test]
+fn it_creates_derived_inputs_for_mpts_services() {
+    let server = MockServer::start();
+    server
+        .list("/api/input/", vec![input("id-1", "mpts")])
+        .on("POST", "/api/input/", |_| Response::ok(json!({})));
+
+    let output = server.edgectl(&[
+        "input",
+        "create",
+        "news",
+        "--mode",
+        "derived",
+        "--parent",
+        "mpts",
+        "--service",
+        "2",
+        "--service",
+        "3",
+        "--map",
+        "256",
+        "300",
+        "--delete",
+        "400",
+    ]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    let requests = server.requests_to("POST", "/api/input/");
+    assert_eq!(
+        requests[0].body.as_ref().unwrap()["deriveFrom"],
+        json!({
+            "parentInput": "id-1",
+            "delay": 1000,
+            "ingestTransform": {
+                "type": "mpts-demux",
+                "services": [2, 3],
+                "pidMap": { "rules": [
+                    { "action": "map", "pid": 256, "destPid": 300 },
+                    { "action": "delete", "pid": 400 },
+                ]},
+            },
+        })
+    );
+}
+
+#[test]
+fn it_rejects_pids_mapped_to_the_same_pid() {
+    let server = MockServer::start();
+    server.list("/api/input/", vec![input("id-1", "mpts")]);
+
+    let output = server.edgectl(&[
+        "input", "create", "news", "--mode", "derived", "--parent", "mpts", "--map", "256", "300",
+        "--map", "257", "300",
+    ]);
+
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("PIDs 256 and 257 are both mapped to 300"));
+    assert!(server.requests_to("POST", "/api/input/").is_empty());
+}
+
+#[test]
+fn it_rejects_services_for_inputs_that_are_not_derived() {
+    let server = MockServer::start();
+
+    let output = server.edgectl(&["input", "create", "news", "--mode", "rtp", "--service", "2"]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("--service is only supported for derived inputs"));
+    assert!(server.requests_to("POST", "/api/input/").is_empty());
+}
+
+#[test]
+fn it_fails_cleanly_when_the_parent_input_does_not_exist() {
+    let server = MockServer::start();
+    server.list("/api/input/", vec![input("id-1", "mpts-2")]);
+
+    let output = server.edgectl(&[
+        "input", "create", "news", "--mode", "derived", "--parent", "mpts",
+    ]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("Could not find parent input mpts"));
+    assert!(server.requests_to("POST", "/api/input/").is_empty());
+}
+
+#[test]
+fn it_shows_the_parent_and_pid_rules_of_derived_inputs() {
+    let server = MockServer::start();
+    let mut news = input("id-2", "news");
+    news["deriveFrom"] = json!({
+        "parentInput": "id-1",
+        "delay": 500,
+        "ingestTransform": {
+            "type": "mpts-demux",
+            "services": [2],
+            "pidMap": { "rules": [{ "action": "map", "pid": 256, "destPid": 300 }] },
+        },
+    });
+    server
+        .list("/api/input/", vec![news])
+        .on("GET", "/api/input/id-1", |_| {
+            Response::ok(input("id-1", "mpts"))
+        });
+
+    let output = server.edgectl(&["input", "show", "news"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    let stdout = stdout(&output);
+    assert!(stdout.contains("Derived from:   mpts"));
+    assert!(stdout.contains("Delay:          500ms"));
+    assert!(stdout.contains("Services:       2"));
+    assert!(stdout.contains("  - map 256 to 300"));
+}
+
+#[test]
+fn it_keeps_transforms_it_does_not_know_when_printing_json() {
+    let server = MockServer::start();
+    let transform = json!({ "type": "scte-35-strip", "pids": [500] });
+    let mut news = input("id-2", "news");
+    news["deriveFrom"] = json!({
+        "parentInput": "id-1",
+        "delay": 0,
+        "ingestTransform": transform,
+    });
+    server.list("/api/input/", vec![news]);
+
+    let output = server.edgectl(&["input", "show", "news", "-o", "json"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    let shown: Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(shown[0]["deriveFrom"]["ingestTransform"], transform);
+}
+
+#[test]
+fn it_only_changes_the_admin_status_when_disabling() {
+    let server = MockServer::start();
+    let mut studio = input("id-1", "studio");
+    studio["broadcastStandard"] = json!("dvb");
+    studio["maxBitrate"] = json!(20_000_000);
+    studio["videoPreviewMode"] = json!("on demand");
+    studio["ports"] = json!([{
+        "id": "port-id-1",
+        "mode": "rtp",
+        "physicalPort": "port-1",
+        "copies": 1,
+        "port": 4000,
+        "internalStreamId": 20485,
+    }]);
+    let live = studio.clone();
+    server
+        .list("/api/input/", vec![studio])
+        .on("GET", "/api/input/id-1", move |_| {
+            Response::ok(live.clone())
+        })
+        .on("PUT", "/api/input/id-1", |_| Response::ok(json!({})));
+
+    let output = server.edgectl(&["input", "disable", "studio"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    let requests = server.requests_to("PUT", "/api/input/id-1");
+    let body = requests[0].body.as_ref().unwrap();
+    assert_eq!(body["adminStatus"], 0);
+    assert_eq!(body["broadcastStandard"], "dvb");
+    assert_eq!(body["maxBitrate"], 20_000_000);
+    assert_eq!(body["videoPreviewMode"], "on demand");
+    assert_eq!(body["ports"][0]["internalStreamId"], 20485);
+}
+
+#[test]
+fn it_does_not_move_inputs_that_outputs_use() {
+    let server = MockServer::start();
+    server
+        .list("/api/input/", vec![input("in-1", "studio-in")])
+        .list(
+            "/api/output/",
+            vec![
+                output("out-1", "studio-out", Some("in-1")),
+                output("out-2", "other-out", Some("in-2")),
+            ],
+        );
+    server.write_config("dest", &server.url, "dest-token");
+
+    let result = server.edgectl(&["input", "move", "studio-in", "--to-context", "dest"]);
+
+    assert_eq!(result.status.code(), Some(1));
+    assert!(stderr(&result).contains("Input is used by outputs studio-out, use --force"));
+    assert!(server.requests_to("DELETE", "/api/input/in-1").is_empty());
+    assert!(server.requests_to("POST", "/api/input/").is_empty());
+}
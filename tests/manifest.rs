@@ -0,0 +1,173 @@
+mod common;
+
+use std::path::PathBuf;
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use common::{
+    appliance, group, input, output, physical_port, stderr, stdout, MockServer, Response,
+};
+use serde_json::{json, Value};
+
+static MANIFESTS: AtomicUsize = AtomicUsize::new(0);
+
+const OUTPUT_MANIFEST: &str = r#"
+kind: Output
+name: studio-out
+input: studio-in
+ports:
+  - appliance: edge-1
+    interface: eth0
+    mode: rtp
+    address: 198.51.100.10
+    port: 4000
+    ttl: 32
+"#;
+
+/// A manifest in a temporary file, removed again when dropped
+struct ManifestFile(PathBuf);
+
+impl ManifestFile {
+    fn new(content: &str) -> Self {
+        let path = std::env::temp_dir().join(format!(
+            "edgectl-manifest-{}-{}.yaml",
+            process::id(),
+            MANIFESTS.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::write(&path, content).expect("Failed to write manifest");
+        Self(path)
+    }
+
+    fn path(&self) -> &str {
+        self.0.to_str().unwrap()
+    }
+}
+
+impl Drop for ManifestFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+// Serves everything the output manifest refers to
+fn serve_references(server: &MockServer) {
+    let mut edge = appliance("appl-1", "edge-1");
+    edge["physicalPorts"] = json!([physical_port("port-1", "eth0", "198.51.100.1")]);
+    server
+        .list("/api/appliance/", vec![edge])
+        .list("/api/input/", vec![input("in-1", "studio-in")])
+        .list("/api/output/", vec![])
+        .on("GET", "/api/input/in-1", |_| {
+            Response::ok(input("in-1", "studio-in"))
+        })
+        .on("GET", "/api/group/group-1", |_| {
+            Response::ok(group("group-1", "studio"))
+        })
+        .on("GET", "/api/port/port-1", |_| {
+            Response::ok(json!({
+                "id": "port-1",
+                "name": "eth0",
+                "appliance": { "name": "edge-1" },
+            }))
+        })
+        .on("POST", "/api/output/", |_| Response::ok(json!({})));
+}
+
+#[test]
+fn it_shows_no_drift_after_applying_an_output() {
+    let server = MockServer::start();
+    serve_references(&server);
+    let manifest = ManifestFile::new(OUTPUT_MANIFEST);
+
+    let result = server.edgectl(&["apply", "-f", manifest.path()]);
+    assert!(result.status.success(), "{}", stderr(&result));
+
+    // The server keeps what it was sent and adds its own fields
+    let created = server.requests_to("POST", "/api/output/")[0]
+        .body
+        .clone()
+        .unwrap();
+    let mut live = output("out-1", "studio-out", Some("in-1"));
+    live["ports"] = created["ports"].clone();
+    live["ports"][0]["id"] = json!("out-port-1");
+    live["ports"][0]["internalStreamId"] = json!(7);
+    server.list("/api/output/", vec![live]);
+
+    let result = server.edgectl(&["diff", "-f", manifest.path()]);
+    assert_eq!(result.status.code(), Some(0), "{}", stdout(&result));
+}
+
+#[test]
+fn it_refuses_port_settings_it_cannot_send() {
+    let server = MockServer::start();
+    serve_references(&server);
+    let manifest = ManifestFile::new(&format!("{}    fec: null\n", OUTPUT_MANIFEST));
+
+    let result = server.edgectl(&["apply", "-f", manifest.path()]);
+
+    assert_eq!(result.status.code(), Some(1));
+    assert!(stderr(&result).contains("Unsupported value for port setting fec"));
+    assert!(server.requests_to("POST", "/api/output/").is_empty());
+}
+
+#[test]
+fn it_keeps_port_settings_it_does_not_model() {
+    let server = MockServer::start();
+    serve_references(&server);
+    let manifest = ManifestFile::new(OUTPUT_MANIFEST);
+
+    let result = server.edgectl(&["apply", "-f", manifest.path()]);
+
+    assert!(result.status.success(), "{}", stderr(&result));
+    let requests = server.requests_to("POST", "/api/output/");
+    let port: &Value = &requests[0].body.as_ref().unwrap()["ports"][0];
+    assert_eq!(port["ttl"], 32);
+    assert_eq!(port["physicalPort"], "port-1");
+}
+
+#[test]
+fn it_finds_exact_matches_beyond_the_first_page() {
+    let server = MockServer::start();
+    let mut groups = (0..150)
+        .map(|i| group(&format!("group-{}", i), &format!("studio-{}", i)))
+        .collect::<Vec<_>>();
+    groups.push(group("group-studio", "studio"));
+    server
+        .list("/api/group/", groups)
+        .on("POST", "/api/group/", |_| Response::ok(json!({})));
+    let manifest = ManifestFile::new("kind: Group\nname: studio\n");
+
+    let result = server.edgectl(&["apply", "-f", manifest.path()]);
+
+    assert!(result.status.success(), "{}", stderr(&result));
+    assert!(stdout(&result).contains("group/studio unchanged"));
+    assert!(server.requests_to("POST", "/api/group/").is_empty());
+}
+
+#[test]
+fn it_listens_on_the_interface_address_by_default() {
+    let server = MockServer::start();
+    serve_references(&server);
+    server
+        .list("/api/input/", vec![])
+        .on("POST", "/api/input/", |_| Response::ok(json!({})));
+    let manifest = ManifestFile::new(
+        r#"
+kind: Input
+name: studio-in
+ports:
+  - appliance: edge-1
+    interface: eth0
+    mode: rtp
+    port: 4000
+"#,
+    );
+
+    let result = server.edgectl(&["apply", "-f", manifest.path()]);
+
+    assert!(result.status.success(), "{}", stderr(&result));
+    let requests = server.requests_to("POST", "/api/input/");
+    let port: &Value = &requests[0].body.as_ref().unwrap()["ports"][0];
+    assert_eq!(port["address"], "198.51.100.1");
+    assert_eq!(port["physicalPort"], "port-1");
+}
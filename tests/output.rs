@@ -0,0 +1,680 @@
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use common::{
+    appliance, group, input, output, physical_port, stderr, stdout, MockServer, Response,
+};
+use serde_json::{json, Value};
+
+fn rtp_output(id: &str, name: &str) -> Value {
+    let mut output = output(id, name, Some("in-1"));
+    output["ports"] = json!([{
+        "mode": "rtp",
+        "address": "198.51.100.10",
+        "port": 4000,
+        "physicalPort": "port-1",
+    }]);
+    output["tags"] = json!(["studio"]);
+    output
+}
+
+#[test]
+fn it_lists_outputs_from_every_page() {
+    let server = MockServer::start();
+    server.list(
+        "/api/output/",
+        (0..150)
+            .map(|i| output(&format!("id-{}", i), &format!("output-{}", i), None))
+            .collect(),
+    );
+
+    let result = server.edgectl(&["output", "list"]);
+
+    assert!(result.status.success());
+    let stdout = stdout(&result);
+    assert_eq!(stdout.lines().count(), 151);
+    assert!(stdout.contains("output-149 "));
+}
+
+#[test]
+fn it_resolves_inputs_and_groups_in_the_wide_list() {
+    let server = MockServer::start();
+    server
+        .list(
+            "/api/output/",
+            vec![
+                output("out-1", "studio-out", Some("in-1")),
+                output("out-2", "idle-out", None),
+            ],
+        )
+        .list(
+            "/api/input/",
+            vec![input("in-1", "studio"), input("in-2", "stadium")],
+        )
+        .list("/api/group/", vec![group("group-1", "broadcaster")]);
+
+    let result = server.edgectl(&["output", "list", "-o", "wide"]);
+
+    assert!(result.status.success());
+    let stdout = stdout(&result);
+    let line = stdout.lines().find(|l| l.contains("studio-out")).unwrap();
+    assert!(line.contains("broadcaster"));
+    assert!(line.contains("studio"));
+    assert!(!stdout.contains("stadium"));
+    // The input is looked up by id instead of listing every input
+    let requests = server.requests_to("GET", "/api/input/");
+    assert_eq!(requests.len(), 1);
+    assert_eq!(
+        requests[0].query.as_ref().unwrap()["filter"]["ids"],
+        serde_json::json!(["in-1"])
+    );
+}
+
+#[test]
+fn it_resolves_more_inputs_than_the_default_page_size() {
+    let server = MockServer::start();
+    let outputs = (0..15)
+        .map(|i| {
+            let input = format!("in-{}", i);
+            output(
+                &format!("out-{}", i),
+                &format!("output-{}", i),
+                Some(&input),
+            )
+        })
+        .collect();
+    let inputs = (0..15)
+        .map(|i| input(&format!("in-{}", i), &format!("studio-{}", i)))
+        .collect();
+    server
+        .list("/api/output/", outputs)
+        .list("/api/input/", inputs)
+        .list("/api/group/", vec![group("group-1", "broadcaster")]);
+
+    let result = server.edgectl(&["output", "list", "-o", "wide"]);
+
+    assert!(result.status.success());
+    let stdout = stdout(&result);
+    for i in 0..15 {
+        let line = stdout
+            .lines()
+            .find(|l| l.contains(&format!("output-{} ", i)))
+            .unwrap();
+        assert!(line.contains(&format!("studio-{} ", i)), "{}", line);
+    }
+}
+
+#[test]
+fn it_updates_an_output_in_place() {
+    let server = MockServer::start();
+    server
+        .list("/api/output/", vec![rtp_output("out-1", "studio-out")])
+        .on("PUT", "/api/output/out-1", |_| Response::ok(json!({})));
+
+    let result = server.edgectl(&[
+        "output",
+        "update",
+        "studio-out",
+        "--dest",
+        "198.51.100.20:5000",
+        "--fec",
+        "2D",
+        "--fec-rows",
+        "10",
+        "--fec-cols",
+        "5",
+        "--delay",
+        "500",
+        "--delay-mode",
+        "origin",
+        "--add-tag",
+        "news",
+        "--remove-tag",
+        "studio",
+    ]);
+
+    assert!(result.status.success(), "{}", stderr(&result));
+    let requests = server.requests_to("PUT", "/api/output/out-1");
+    let body = requests[0].body.as_ref().unwrap();
+    assert_eq!(body["ports"][0]["address"], "198.51.100.20");
+    assert_eq!(body["ports"][0]["port"], 5000);
+    assert_eq!(body["ports"][0]["fec"], "2D");
+    assert_eq!(body["ports"][0]["fecRows"], 10);
+    assert_eq!(body["ports"][0]["fecCols"], 5);
+    assert_eq!(body["delay"], 500);
+    assert_eq!(body["delayMode"], 2);
+    assert_eq!(body["tags"], json!(["news"]));
+    // The output is changed, never deleted and recreated
+    assert!(server.requests_to("DELETE", "/api/output/out-1").is_empty());
+}
+
+#[test]
+fn it_keeps_the_fields_it_does_not_model_when_updating() {
+    let server = MockServer::start();
+    let mut output = rtp_output("out-1", "studio-out");
+    output["ports"][0]["id"] = json!("port-id-1");
+    output["ports"][0]["ttl"] = json!(32);
+    output["ports"][0]["region"] = json!({ "id": "region-1", "name": "eu-north" });
+    output["upstreamAppliances"] = json!([{ "name": "core-1" }]);
+    server
+        .list("/api/output/", vec![output])
+        .on("PUT", "/api/output/out-1", |_| Response::ok(json!({})));
+
+    let result = server.edgectl(&["output", "update", "studio-out", "--delay", "500"]);
+
+    assert!(result.status.success(), "{}", stderr(&result));
+    let requests = server.requests_to("PUT", "/api/output/out-1");
+    let body = requests[0].body.as_ref().unwrap();
+    assert_eq!(body["delay"], 500);
+    assert_eq!(body["ports"][0]["id"], "port-id-1");
+    assert_eq!(body["ports"][0]["ttl"], 32);
+    assert_eq!(body["ports"][0]["region"]["name"], "eu-north");
+    assert_eq!(body["ports"][0]["mode"], "rtp");
+    assert_eq!(body["upstreamAppliances"], json!([{ "name": "core-1" }]));
+}
+
+#[test]
+fn it_rejects_settings_that_do_not_apply_to_the_port() {
+    let server = MockServer::start();
+    server.list("/api/output/", vec![rtp_output("out-1", "studio-out")]);
+
+    let result = server.edgectl(&["output", "update", "studio-out", "--latency", "200"]);
+
+    assert_eq!(result.status.code(), Some(1));
+    assert!(stderr(&result).contains("latency can only be changed for SRT"));
+    assert!(server.requests_to("PUT", "/api/output/out-1").is_empty());
+}
+
+#[test]
+fn it_edits_an_output_in_the_editor() {
+    let server = MockServer::start();
+    server
+        .list("/api/output/", vec![rtp_output("out-1", "studio-out")])
+        .on("PUT", "/api/output/out-1", |_| Response::ok(json!({})));
+
+    let result = server
+        .command(&["output", "edit", "studio-out"])
+        .env("EDGE_TOKEN", "test-token")
+        .env("EDITOR", "sed -i s/198.51.100.10/198.51.100.30/")
+        .output()
+        .unwrap();
+
+    assert!(result.status.success(), "{}", stderr(&result));
+    let requests = server.requests_to("PUT", "/api/output/out-1");
+    let body = requests[0].body.as_ref().unwrap();
+    assert_eq!(body["id"], "out-1");
+    assert_eq!(body["ports"][0]["address"], "198.51.100.30");
+}
+
+// An installation with input studio on appliance edge-1, which has interface
+// eth0, accepting new outputs
+fn installation() -> MockServer {
+    let server = MockServer::start();
+    let mut edge = appliance("a-1", "edge-1");
+    edge["physicalPorts"] = json!([physical_port("port-1", "eth0", "192.0.2.10")]);
+    server
+        .list("/api/appliance/", vec![edge])
+        .list("/api/input/", vec![input("in-1", "studio")])
+        .on("POST", "/api/output/", |_| Response::ok(json!({})));
+    server
+}
+
+#[test]
+fn it_creates_rtmp_outputs() {
+    let server = installation();
+
+    let result = server.edgectl(&[
+        "output",
+        "create",
+        "cdn",
+        "--appliance",
+        "edge-1",
+        "--interface",
+        "eth0",
+        "--input",
+        "studio",
+        "--mode",
+        "rtmp",
+        "--url",
+        "rtmp://198.51.100.12/live/",
+        "--stream-key",
+        "abc123",
+    ]);
+
+    assert!(result.status.success(), "{}", stderr(&result));
+    let requests = server.requests_to("POST", "/api/output/");
+    assert_eq!(
+        requests[0].body.as_ref().unwrap()["ports"],
+        json!([{
+            "mode": "rtmp",
+            "physicalPort": "port-1",
+            "rtmpDestinationAddress": "rtmp://198.51.100.12/live/abc123",
+        }])
+    );
+}
+
+#[test]
+fn it_creates_zixi_push_outputs() {
+    let server = installation();
+
+    let result = server.edgectl(&[
+        "output",
+        "create",
+        "partner",
+        "--appliance",
+        "edge-1",
+        "--interface",
+        "eth0",
+        "--input",
+        "studio",
+        "--mode",
+        "zixi",
+        "--push",
+        "--stream-id",
+        "studio-feed",
+        "--link",
+        "198.51.100.20:2088",
+        "--link",
+        "198.51.100.21:2088",
+        "--backup-link",
+        "203.0.113.20:2088",
+    ]);
+
+    assert!(result.status.success(), "{}", stderr(&result));
+    let requests = server.requests_to("POST", "/api/output/");
+    assert_eq!(
+        requests[0].body.as_ref().unwrap()["ports"],
+        json!([{
+            "mode": "zixi",
+            "zixiMode": "push",
+            "physicalPort": "port-1",
+            "streamId": "studio-feed",
+            "linkSet1": [
+                { "remoteIp": "198.51.100.20", "remotePort": 2088 },
+                { "remoteIp": "198.51.100.21", "remotePort": 2088 },
+            ],
+            "linkSet2": [{ "remoteIp": "203.0.113.20", "remotePort": 2088 }],
+        }])
+    );
+}
+
+#[test]
+fn it_rejects_links_for_zixi_pull_outputs() {
+    let server = installation();
+
+    let result = server.edgectl(&[
+        "output",
+        "create",
+        "partner",
+        "--appliance",
+        "edge-1",
+        "--interface",
+        "eth0",
+        "--input",
+        "studio",
+        "--mode",
+        "zixi",
+        "--pull",
+        "--stream-id",
+        "studio-feed",
+        "--link",
+        "198.51.100.20:2088",
+    ]);
+
+    assert_eq!(result.status.code(), Some(2));
+    assert!(stderr(&result).contains("'--pull' cannot be used with '--link <link>'"));
+    assert!(server.requests_to("POST", "/api/output/").is_empty());
+}
+
+#[test]
+fn it_shows_outputs_whose_ports_have_no_physical_port() {
+    let server = MockServer::start();
+    let mut cdn = output("out-1", "cdn", None);
+    cdn["ports"] = json!([{
+        "mode": "rtmp",
+        "rtmpDestinationAddress": "rtmp://198.51.100.12/live/key",
+    }]);
+    server
+        .list("/api/output/", vec![cdn])
+        .list("/api/alarm/", vec![])
+        .on("GET", "/api/group/group-1", |_| {
+            Response::ok(group("group-1", "studio"))
+        });
+
+    let result = server.edgectl(&["output", "show", "cdn"]);
+
+    assert!(result.status.success(), "{}", stderr(&result));
+    assert!(stdout(&result).contains("rtmp://198.51.100.12/live/key"));
+}
+
+#[test]
+fn it_limits_zixi_link_sets_to_three_links() {
+    let server = installation();
+
+    let mut args = vec![
+        "output",
+        "create",
+        "partner",
+        "--appliance",
+        "edge-1",
+        "--interface",
+        "eth0",
+        "--input",
+        "studio",
+        "--mode",
+        "zixi",
+        "--push",
+        "--stream-id",
+        "studio-feed",
+    ];
+    for _ in 0..4 {
+        args.extend(["--link", "198.51.100.20:2088"]);
+    }
+    let result = server.edgectl(&args);
+
+    assert_eq!(result.status.code(), Some(1));
+    assert!(stderr(&result).contains("at most three links"));
+    assert!(server.requests_to("POST", "/api/output/").is_empty());
+}
+
+#[test]
+fn it_creates_encrypted_srt_rendezvous_outputs() {
+    let server = installation();
+
+    let result = server.edgectl(&[
+        "output",
+        "create",
+        "contribution",
+        "--appliance",
+        "edge-1",
+        "--interface",
+        "eth0",
+        "--input",
+        "studio",
+        "--mode",
+        "srt",
+        "--rendezvous",
+        "--dest",
+        "198.51.100.40:9000",
+        "--passphrase",
+        "correct horse battery",
+        "--keylen",
+        "256",
+        "--latency",
+        "400",
+        "--rate-limit",
+        "relative",
+        "--whitelist",
+        "198.51.100.0/24",
+    ]);
+
+    assert!(result.status.success(), "{}", stderr(&result));
+    let requests = server.requests_to("POST", "/api/output/");
+    assert_eq!(
+        requests[0].body.as_ref().unwrap()["ports"],
+        json!([{
+            "mode": "srt",
+            "srtMode": "rendezvous",
+            "physicalPort": "port-1",
+            "localIp": "192.0.2.10",
+            "remoteIp": "198.51.100.40",
+            "remotePort": 9000,
+            "latency": 400,
+            "pbkeylen": "32",
+            "rateLimiting": "Relative to input",
+            "passphrase": "correct horse battery",
+            "whitelistCidrBlock": ["198.51.100.0/24"],
+        }])
+    );
+}
+
+#[test]
+fn it_rejects_short_srt_passphrases() {
+    let server = installation();
+
+    let result = server.edgectl(&[
+        "output",
+        "create",
+        "contribution",
+        "--appliance",
+        "edge-1",
+        "--interface",
+        "eth0",
+        "--input",
+        "studio",
+        "--mode",
+        "srt",
+        "--listener",
+        "--port",
+        "9000",
+        "--passphrase",
+        "secret",
+    ]);
+
+    assert_eq!(result.status.code(), Some(1));
+    assert!(stderr(&result).contains("10 to 79 characters"));
+    assert!(server.requests_to("POST", "/api/output/").is_empty());
+}
+
+#[test]
+fn it_creates_redundant_outputs_from_port_specs() {
+    let server = installation();
+    let mut edge_2 = appliance("a-2", "edge-2");
+    edge_2["physicalPorts"] = json!([physical_port("port-2", "eth1", "192.0.2.20")]);
+    let mut edge_1 = appliance("a-1", "edge-1");
+    edge_1["physicalPorts"] = json!([physical_port("port-1", "eth0", "192.0.2.10")]);
+    server.list("/api/appliance/", vec![edge_1, edge_2]);
+
+    let result = server.edgectl(&[
+        "output",
+        "create",
+        "studio-out",
+        "--input",
+        "studio",
+        "--mode",
+        "rtp",
+        "--port-spec",
+        "edge-1:eth0:198.51.100.1:4000",
+        "--port-spec",
+        "edge-2:eth1:198.51.100.2:4000",
+    ]);
+
+    assert!(result.status.success(), "{}", stderr(&result));
+    let requests = server.requests_to("POST", "/api/output/");
+    let body = requests[0].body.as_ref().unwrap();
+    assert_eq!(body["redundancyMode"], 2);
+    assert_eq!(body["ports"][0]["physicalPort"], "port-1");
+    assert_eq!(body["ports"][0]["address"], "198.51.100.1");
+    assert_eq!(body["ports"][1]["physicalPort"], "port-2");
+    assert_eq!(body["ports"][1]["address"], "198.51.100.2");
+}
+
+#[test]
+fn it_needs_several_legs_for_redundant_outputs() {
+    let server = installation();
+
+    let result = server.edgectl(&[
+        "output",
+        "create",
+        "studio-out",
+        "--input",
+        "studio",
+        "--mode",
+        "rtp",
+        "--port-spec",
+        "edge-1:eth0:198.51.100.1:4000",
+        "--redundancy-mode",
+        "failover",
+    ]);
+
+    assert!(!result.status.success());
+    assert!(stderr(&result).contains("needs more than one --port-spec"));
+    assert!(server.requests_to("POST", "/api/output/").is_empty());
+}
+
+#[test]
+fn it_only_changes_the_admin_status_when_disabling() {
+    let server = MockServer::start();
+    let mut live = rtp_output("out-1", "studio-out");
+    live["ports"][0]["ttl"] = json!(32);
+    live["ports"][0]["allocatedPortId"] = json!("allocated-1");
+    let studio_out = live.clone();
+    server
+        .list("/api/output/", vec![studio_out])
+        .on("GET", "/api/output/out-1", move |_| {
+            Response::ok(live.clone())
+        })
+        .on("PUT", "/api/output/out-1", |_| Response::ok(json!({})));
+
+    let result = server.edgectl(&["output", "disable", "studio-out"]);
+
+    assert!(result.status.success(), "{}", stderr(&result));
+    let requests = server.requests_to("PUT", "/api/output/out-1");
+    let body = requests[0].body.as_ref().unwrap();
+    assert_eq!(body["adminStatus"], 0);
+    assert_eq!(body["ports"][0]["ttl"], 32);
+    assert_eq!(body["ports"][0]["allocatedPortId"], "allocated-1");
+}
+
+#[test]
+fn it_keeps_disabling_the_matches_of_a_pattern_after_a_failure() {
+    let server = MockServer::start();
+    let outputs = vec![
+        rtp_output("out-1", "studio-a"),
+        rtp_output("out-2", "studio-b"),
+    ];
+    let (studio_a, studio_b) = (outputs[0].clone(), outputs[1].clone());
+    server
+        .list("/api/output/", outputs)
+        .on("GET", "/api/output/out-1", move |_| {
+            Response::ok(studio_a.clone())
+        })
+        .on("GET", "/api/output/out-2", move |_| {
+            Response::ok(studio_b.clone())
+        })
+        .on("PUT", "/api/output/out-1", |_| {
+            Response::error(409, "Conflict", "The output is locked")
+        })
+        .on("PUT", "/api/output/out-2", |_| Response::ok(json!({})));
+
+    let result = server.edgectl(&["output", "disable", "studio-*"]);
+
+    assert_eq!(result.status.code(), Some(1));
+    assert!(stderr(&result).contains("Failed to update output studio-a"));
+    assert!(stderr(&result).contains("1 of 2 outputs could not be updated"));
+    assert!(stdout(&result).contains("Disabled output studio-b"));
+    assert_eq!(server.requests_to("PUT", "/api/output/out-2").len(), 1);
+    // The server narrows the list down to the names starting like the pattern
+    let requests = server.requests_to("GET", "/api/output/");
+    assert_eq!(
+        requests[0].query.as_ref().unwrap()["filter"]["searchName"],
+        "studio-"
+    );
+}
+
+#[test]
+fn it_does_not_trust_the_health_from_before_switching_inputs() {
+    let server = MockServer::start();
+    let mut live = rtp_output("out-1", "studio-out");
+    live["ports"][0]["ttl"] = json!(32);
+    let studio_out = live.clone();
+    let polls = AtomicUsize::new(0);
+    server
+        .list("/api/output/", vec![studio_out])
+        .list("/api/input/", vec![input("in-2", "backup-in")])
+        .on("GET", "/api/output/out-1", move |_| {
+            // The first poll still sees the health with the old input
+            let mut output = live.clone();
+            if polls.fetch_add(1, Ordering::Relaxed) > 1 {
+                output["health"] = json!({ "state": "inputError", "title": "No input" });
+            }
+            Response::ok(output)
+        })
+        .on("PUT", "/api/output/out-1", |_| Response::ok(json!({})));
+
+    let result = server.edgectl(&[
+        "output",
+        "set-input",
+        "studio-out",
+        "backup-in",
+        "--timeout",
+        "3s",
+    ]);
+
+    assert_eq!(result.status.code(), Some(1));
+    assert!(stderr(&result).contains("Output is not healthy after 3s"));
+    let requests = server.requests_to("PUT", "/api/output/out-1");
+    let body = requests[0].body.as_ref().unwrap();
+    assert_eq!(body["input"], "in-2");
+    assert_eq!(body["ports"][0]["ttl"], 32);
+}
+
+#[test]
+fn it_allocates_a_port_in_the_region_when_copying_regional_outputs() {
+    let source = MockServer::start();
+    let mut regional = output("out-1", "studio-out", Some("in-1"));
+    regional["ports"] = json!([{
+        "mode": "rtp",
+        "address": "198.51.100.10",
+        "port": 4000,
+        "physicalPort": "port-1",
+        "region": { "id": "region-1", "name": "eu" },
+        "allocatedPortId": "alloc-1",
+    }]);
+    source
+        .list("/api/output/", vec![regional])
+        .on("GET", "/api/group/group-1", |_| {
+            Response::ok(group("group-1", "studio"))
+        })
+        .on("GET", "/api/input/in-1", |_| {
+            Response::ok(input("in-1", "studio-in"))
+        })
+        .on("GET", "/api/port/port-1", |_| {
+            Response::ok(
+                json!({ "id": "port-1", "name": "eth0", "appliance": { "name": "core-1" } }),
+            )
+        });
+
+    let destination = MockServer::start();
+    let mut core = appliance("appl-9", "core-9");
+    core["physicalPorts"] = json!([physical_port("port-9", "eth1", "203.0.113.1")]);
+    destination
+        .list("/api/output/", vec![])
+        .list("/api/input/", vec![input("in-9", "studio-in")])
+        .list("/api/group/", vec![group("group-9", "studio")])
+        .list(
+            "/api/region/",
+            vec![json!({ "id": "region-9", "name": "eu", "external": 0 })],
+        )
+        .list("/api/appliance/", vec![core])
+        .on("POST", "/api/allocatePort", |_| {
+            Response::ok(json!({
+                "id": "alloc-9",
+                "physicalPort": { "id": "port-9" },
+                "portNumber": 6000,
+            }))
+        })
+        .on("GET", "/api/port/port-9", |_| {
+            Response::ok(
+                json!({ "id": "port-9", "name": "eth1", "appliance": { "name": "core-9" } }),
+            )
+        })
+        .on("POST", "/api/output/", |_| Response::ok(json!({})));
+    source.write_config("dest", &destination.url, "dest-token");
+
+    let result = source.edgectl(&["output", "copy", "studio-out", "--to-context", "dest"]);
+
+    assert!(result.status.success(), "{}", stderr(&result));
+    let allocation = &destination.requests_to("POST", "/api/allocatePort")[0];
+    assert_eq!(
+        allocation.body,
+        Some(json!({ "purpose": "output", "regionId": "region-9", "inputId": "in-9" }))
+    );
+    let created = &destination.requests_to("POST", "/api/output/")[0];
+    let port = &created.body.as_ref().unwrap()["ports"][0];
+    assert_eq!(port["physicalPort"], "port-9");
+    assert_eq!(port["allocatedPortId"], "alloc-9");
+    assert_eq!(port["localPort"], 6000);
+    assert!(port.get("region").is_none());
+}
@@ -0,0 +1,44 @@
+mod common;
+
+use common::{stderr, stdout, MockServer, Response};
+use serde_json::json;
+
+#[test]
+fn it_keeps_the_new_token_when_the_old_one_cannot_be_deleted() {
+    let server = MockServer::start();
+    server.write_config("lab", &server.url, "old-secret");
+    server
+        .on("GET", "/api/api-token/", |_| {
+            Response::ok(json!({ "items": [{
+                "id": "token-1",
+                "name": "lab-token",
+                "role": "admin",
+                "expiresAt": "2030-01-01T00:00:00.000Z",
+                "scopes": ["read", "write"],
+            }]}))
+        })
+        .on("POST", "/api/api-token/", |_| {
+            Response::ok(json!({
+                "id": "token-2",
+                "name": "edgectl-test",
+                "token": "new-secret",
+                "role": "admin",
+                "expiresAt": "2031-01-01T00:00:00.000Z",
+                "scopes": ["read", "write"],
+            }))
+        })
+        .on("DELETE", "/api/api-token/token-1", |_| {
+            Response::error(409, "Conflict", "The token is in use")
+        });
+
+    let result = server.command(&["token", "rotate"]).output().unwrap();
+
+    assert!(result.status.success(), "{}", stderr(&result));
+    assert!(stdout(&result).contains("the new token 'edgectl-test'"));
+    assert!(stderr(&result).contains("failed to delete the old API token lab-token"));
+    let config = std::fs::read_to_string(server.config_file()).unwrap();
+    assert!(config.contains("new-secret"));
+    assert!(!config.contains("old-secret"));
+    let delete = &server.requests_to("DELETE", "/api/api-token/token-1")[0];
+    assert_eq!(delete.headers["edge-api-token"], "new-secret");
+}